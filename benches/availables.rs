@@ -0,0 +1,41 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Positions selectable by every player, which are updated after every move.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::Rng;
+use triversi::board::{Availables, Board, Player};
+use triversi::rng;
+
+/// Board of `range` after random moves filling about a third of it, where players who cannot move pass.
+fn midgame_board(range: usize, seed: u64) -> Board {
+    let mut rng = rng::seeded(seed);
+    let mut board = Board::try_new(range).unwrap();
+    let mut player = Player::default();
+    for _ in 0..board.num_cells() / 3 {
+        let positions = board.selectable_positions(player);
+        if !positions.is_empty() {
+            board.apply_move(player, positions[rng.gen_range(0..positions.len())]);
+        }
+        player.advance();
+    }
+    board
+}
+
+fn update_availables(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_availables");
+    for range in [14, 50, 100] {
+        let board = midgame_board(range, 0);
+        let mut availables = Availables::default();
+        group.bench_with_input(BenchmarkId::from_parameter(range), &board, |b, board| {
+            b.iter(|| black_box(board).update_availables(&mut availables))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, update_availables);
+criterion_main!(benches);
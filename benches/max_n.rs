@@ -0,0 +1,50 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Max-n search split at the root over threads, which selects the same position faster.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::Rng;
+use triversi::ai::{MaxN, SearchConfig, Strategy};
+use triversi::board::{Availables, Board, Player};
+use triversi::rng;
+
+/// Board of range 20 after random moves filling about a third of it and the player to move.
+fn midgame_board(seed: u64) -> (Board, Player) {
+    let mut rng = rng::seeded(seed);
+    let mut board = Board::try_new(20).unwrap();
+    let mut player = Player::default();
+    for _ in 0..board.num_cells() / 3 {
+        let positions = board.selectable_positions(player);
+        if !positions.is_empty() {
+            board.apply_move(player, positions[rng.gen_range(0..positions.len())]);
+        }
+        player.advance();
+    }
+    (board, player)
+}
+
+fn threads(c: &mut Criterion) {
+    let (board, player) = midgame_board(0);
+    let mut availables = Availables::default();
+    board.update_availables(&mut availables);
+    let mut group = c.benchmark_group("max_n_depth_3");
+    group.sample_size(10);
+    for threads in [1, 2, 4] {
+        // The table of a single thread is kept between searches, so no table is used for a fair comparison.
+        let max_n = MaxN::new(3).with_search_config(SearchConfig {
+            table_size: 0,
+            threads,
+            ..SearchConfig::default()
+        });
+        group.bench_with_input(BenchmarkId::new("threads", threads), &max_n, |b, max_n| {
+            b.iter(|| max_n.select(&board, &availables, player))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, threads);
+criterion_main!(benches);
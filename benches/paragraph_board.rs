@@ -0,0 +1,97 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Cells of the ascii board made for each frame, which are restyled only where they change.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use triversi::app::board_display::paragraph_board::{FrameStyleKind, ParagraphBoard};
+use triversi::app::board_display::BoardDisplay;
+use triversi::app::system::Play;
+use triversi::app::ColorConfig;
+use triversi::board::{Board, Player};
+use tui::backend::TestBackend;
+use tui::Terminal;
+
+/// Board of range 30 at distance 6 with frames and home regions, which fills a large terminal.
+fn paragraph_board() -> ParagraphBoard {
+    ParagraphBoard::try_new(
+        6,
+        "Cyan,Magenta,Yellow",
+        "C,M,Y",
+        false,
+        FrameStyleKind::Ascii.into(),
+        true,
+        true,
+        false,
+    )
+    .unwrap()
+}
+
+/// Draws the board block with the cursor at `position` over the whole terminal.
+fn draw(
+    terminal: &mut Terminal<TestBackend>,
+    paragraph_board: &ParagraphBoard,
+    board: &Board,
+    position: (usize, usize),
+) {
+    terminal
+        .draw(|frame| {
+            let rect = frame.size();
+            paragraph_board.render_board_block(
+                frame,
+                rect,
+                "Board",
+                board,
+                ColorConfig::default(),
+                Play::Turn,
+                Player::default(),
+                position,
+                None,
+                None,
+                None,
+                None,
+            )
+        })
+        .unwrap();
+}
+
+fn make_board_cells(c: &mut Criterion) {
+    let board = Board::try_new(30).unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(400, 200)).unwrap();
+    let mut group = c.benchmark_group("make_board_cells");
+    let mut paragraph_board = paragraph_board();
+    let center = board.center();
+    group.bench_function("same_frame", |b| {
+        b.iter(|| draw(&mut terminal, &paragraph_board, &board, center))
+    });
+    let positions = [center, board.initial_position()];
+    let mut i_position = 0;
+    group.bench_function("cursor_moved", |b| {
+        b.iter(|| {
+            i_position = 1 - i_position;
+            draw(
+                &mut terminal,
+                &paragraph_board,
+                &board,
+                positions[i_position],
+            )
+        })
+    });
+    let mut scrolled = false;
+    group.bench_function("scrolled", |b| {
+        b.iter(|| {
+            match scrolled {
+                true => paragraph_board.scroll_left(),
+                false => paragraph_board.scroll_right(),
+            }
+            scrolled = !scrolled;
+            draw(&mut terminal, &paragraph_board, &board, center)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, make_board_cells);
+criterion_main!(benches);
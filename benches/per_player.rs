@@ -0,0 +1,66 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Per-player values of `Count` and `Availables`, which are read and written for every move.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::Rng;
+use triversi::board::{sorted_moves, Availables, Board, Count, Player, PLAYERS};
+use triversi::rng;
+
+/// Board of `range` after `n_moves` random moves, where players who cannot move pass.
+fn midgame_board(range: usize, n_moves: usize, seed: u64) -> Board {
+    let mut rng = rng::seeded(seed);
+    let mut board = Board::try_new(range).unwrap();
+    let mut player = Player::default();
+    for _ in 0..n_moves {
+        let positions = board.selectable_positions(player);
+        if !positions.is_empty() {
+            board.apply_move(player, positions[rng.gen_range(0..positions.len())]);
+        }
+        player.advance();
+    }
+    board
+}
+
+fn count(c: &mut Criterion) {
+    let board = midgame_board(14, 40, 0);
+    c.bench_function("count_from_board", |b| {
+        b.iter(|| Count::from_board(black_box(&board)))
+    });
+    let count = Count::from_board(&board);
+    c.bench_function("count_ranking", |b| b.iter(|| black_box(&count).ranking()));
+}
+
+fn availables(c: &mut Criterion) {
+    let board = midgame_board(14, 40, 0);
+    let mut availables = Availables::default();
+    board.update_availables(&mut availables);
+    let positions = board.iter_empty().collect::<Vec<_>>();
+    c.bench_function("availables_lookup", |b| {
+        b.iter(|| {
+            PLAYERS
+                .iter()
+                .map(|player| {
+                    positions
+                        .iter()
+                        .filter(|position| availables[*player].contains_key(*position))
+                        .count()
+                })
+                .sum::<usize>()
+        })
+    });
+    c.bench_function("availables_sorted_moves", |b| {
+        b.iter(|| {
+            PLAYERS
+                .iter()
+                .map(|player| sorted_moves(black_box(&availables), *player).len())
+                .sum::<usize>()
+        })
+    });
+}
+
+criterion_group!(benches, count, availables);
+criterion_main!(benches);
@@ -0,0 +1,102 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Computer players of Triversi.
+
+pub mod evaluation;
+pub mod greedy;
+pub mod max_n;
+pub mod opening_book;
+pub mod phased;
+pub mod random;
+pub mod transposition_table;
+
+pub use evaluation::{Evaluation, Weights};
+pub use greedy::Greedy;
+pub use max_n::MaxN;
+pub use opening_book::OpeningBook;
+pub use phased::{Phased, Phases};
+pub use random::Random;
+pub use transposition_table::{TranspositionTable, DEFAULT_TABLE_SIZE};
+
+use crate::board::{Availables, Board, Player};
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use serde_derive::{Deserialize, Serialize};
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Rule for a computer player to select a position.
+pub trait Strategy {
+    /// Position selected by `player` from `availables`, or `None` if `player` has no position to select.
+    fn select(
+        &self,
+        board: &Board,
+        availables: &Availables,
+        player: Player,
+    ) -> Option<(usize, usize)>;
+
+    /// Shares the depth searched so far while selecting, which is written only by searching strategies.
+    fn share_depth(&mut self, _depth_reached: Arc<AtomicUsize>) {}
+}
+
+/// Resources of searching strategies.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SearchConfig {
+    /// Number of entries of the transposition table, which is not used if it is 0.
+    pub table_size: usize,
+    /// Number of threads evaluating the positions selectable by the player to move,
+    /// which does not change the selected position.
+    pub threads: usize,
+    /// Time of a search, which deepens until it runs out instead of searching a fixed depth.
+    pub time_budget: Option<Duration>,
+    /// Weights of the evaluation at the end of a search, which counts only stones by default.
+    pub weights: Weights,
+    /// Whether a position in the built-in opening book is selected without searching.
+    pub book: bool,
+    /// Fills of the board where the phased strategy begins the midgame and the endgame.
+    pub phases: Phases,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            table_size: DEFAULT_TABLE_SIZE,
+            threads: 1,
+            time_budget: None,
+            weights: Weights::COUNT,
+            book: false,
+            phases: Phases::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StrategyKind {
+    /// Flips the most stones.
+    #[default]
+    Greedy,
+    /// Searches three moves ahead where each player maximizes its own value by --evaluation.
+    MaxN,
+    /// Selects a position at random.
+    Random,
+    /// Selects at random, greedily, and by max-n as the board is filled past the phases.
+    Phased,
+}
+
+impl StrategyKind {
+    /// Strategy of this kind, where `rng` is used only by randomized strategies
+    /// and `search_config` only by searching strategies.
+    pub fn strategy(&self, rng: StdRng, search_config: SearchConfig) -> Box<dyn Strategy + Send> {
+        match self {
+            StrategyKind::Greedy => Box::new(Greedy),
+            StrategyKind::MaxN => Box::new(MaxN::default().with_search_config(search_config)),
+            StrategyKind::Random => Box::new(Random::new(rng)),
+            StrategyKind::Phased => Box::new(Phased::new(rng, search_config)),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::{Board, PLAYERS};
+use crate::error::TriversiError;
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+
+/// How searching strategies value a position at the end of the search.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Evaluation {
+    /// Number of stones.
+    Count,
+    /// Weighted sum of stones on the corners and the edges, the mobility, and the number of stones.
+    #[default]
+    Positional,
+}
+
+/// Weights of the positional evaluation, where a missing weight takes its default.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Weights {
+    /// Weight of a stone on one of the three vertices of the board.
+    pub corner: u64,
+    /// Weight of a stone on an edge of the board other than the vertices.
+    pub edge: u64,
+    /// Weight of a position selectable by the player.
+    pub mobility: u64,
+    /// Weight of any stone, which is added to the weights of the corners and the edges.
+    pub count: u64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            corner: 10,
+            edge: 3,
+            mobility: 1,
+            count: 1,
+        }
+    }
+}
+
+impl Weights {
+    /// Weights of the count-only evaluation, whose values are the numbers of stones.
+    pub const COUNT: Self = Self {
+        corner: 0,
+        edge: 0,
+        mobility: 0,
+        count: 1,
+    };
+
+    /// Weights of `evaluation`, where `weights` is used by the positional evaluation.
+    pub fn of(evaluation: Evaluation, weights: Self) -> Self {
+        match evaluation {
+            Evaluation::Count => Self::COUNT,
+            Evaluation::Positional => weights,
+        }
+    }
+
+    /// Values of players indexed by `Player::index`.
+    pub fn evaluate(&self, board: &Board) -> [u64; 3] {
+        let mut values = board.count().into_array().map(|count| self.count * count);
+        if self.corner != 0 || self.edge != 0 {
+            let last = board.range() - 1;
+            for (y, row) in board.board().iter().enumerate() {
+                for (x, player) in row.iter().enumerate() {
+                    let Some(player) = player else {
+                        continue;
+                    };
+                    let n_edges = [x == 0, x == y, y == last]
+                        .iter()
+                        .filter(|on_edge| **on_edge)
+                        .count();
+                    values[player.index()] += match n_edges {
+                        0 => 0,
+                        1 => self.edge,
+                        _ => self.corner,
+                    };
+                }
+            }
+        }
+        if self.mobility != 0 {
+            for player in PLAYERS {
+                values[player.index()] +=
+                    self.mobility * board.selectable_positions(*player).len() as u64;
+            }
+        }
+        values
+    }
+}
+
+/// Weights like `corner=10,edge=3`, where missing weights take their defaults.
+impl TryFrom<String> for Weights {
+    type Error = TriversiError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let mut weights = Self::default();
+        for item in s.split(',') {
+            let (name, value) = item
+                .split_once('=')
+                .ok_or_else(|| TriversiError::InvalidStringForWeights(s.clone()))?;
+            let value = value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| TriversiError::InvalidStringForWeights(s.clone()))?;
+            let weight = match name.trim() {
+                "corner" => &mut weights.corner,
+                "edge" => &mut weights.edge,
+                "mobility" => &mut weights.mobility,
+                "count" => &mut weights.count,
+                _ => return Err(TriversiError::InvalidStringForWeights(s)),
+            };
+            *weight = value;
+        }
+        Ok(weights)
+    }
+}
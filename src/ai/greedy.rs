@@ -0,0 +1,29 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::Strategy;
+use crate::board::{Availables, Board, Player, Position};
+use std::cmp;
+
+/// Selects the position flipping the most stones.
+/// Ties are broken by the smallest position so that the selection is deterministic.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Greedy;
+
+impl Strategy for Greedy {
+    fn select(
+        &self,
+        _board: &Board,
+        availables: &Availables,
+        player: Player,
+    ) -> Option<(usize, usize)> {
+        availables[player]
+            .iter()
+            .min_by_key(|(position, positions)| {
+                (cmp::Reverse(positions.len()), Position::from(**position))
+            })
+            .map(|(position, _)| *position)
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::{OpeningBook, SearchConfig, Strategy, TranspositionTable};
+use crate::board::{sorted_moves, Availables, Board, Player, Position, PLAYERS};
+use std::cell::RefCell;
+use std::cmp;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// Max^n search, the extension of minimax to three players,
+/// where each player maximizes its own value `depth` moves ahead,
+/// which is the number of stones or the weighted sum by the weights of `SearchConfig`.
+/// Positions reached by different orders of moves are searched once by the transposition table.
+/// With the time budget of `SearchConfig`, the search deepens from one move until the budget runs out instead.
+/// Positions in the opening book are selected without searching if the book is enabled.
+#[derive(Clone, Debug)]
+pub struct MaxN {
+    depth: usize,
+    search_config: SearchConfig,
+    table: RefCell<TranspositionTable>,
+    /// Deepest depth whose search has been completed, which is shared with the caller.
+    depth_reached: Arc<AtomicUsize>,
+}
+
+impl Default for MaxN {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl MaxN {
+    pub fn new(depth: usize) -> Self {
+        let search_config = SearchConfig::default();
+        Self {
+            depth: cmp::max(1, depth),
+            search_config,
+            table: RefCell::new(TranspositionTable::new(search_config.table_size)),
+            depth_reached: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Search using the transposition table, the threads, and the time budget of `search_config`.
+    pub fn with_search_config(mut self, search_config: SearchConfig) -> Self {
+        self.search_config = search_config;
+        self.table = RefCell::new(TranspositionTable::new(search_config.table_size));
+        self
+    }
+
+    /// Values of players indexed by `Player::index`
+    /// after `player` selects `position` and each player plays its best until the depth.
+    pub fn evaluate(&self, board: &Board, player: Player, position: (usize, usize)) -> [u64; 3] {
+        self.evaluate_to(board, player, position, self.depth, None)
+            .unwrap()
+    }
+
+    /// Values of `positions` by `evaluate` in the same order and the depth of the search,
+    /// which is the deepest depth completed within the time budget if it is given.
+    pub fn evaluate_all(
+        &self,
+        board: &Board,
+        player: Player,
+        positions: &[(usize, usize)],
+    ) -> (Vec<[u64; 3]>, usize) {
+        let Some(time_budget) = self.search_config.time_budget else {
+            let values = self
+                .evaluate_all_to(board, player, positions, self.depth, None)
+                .unwrap();
+            self.depth_reached.store(self.depth, Ordering::Relaxed);
+            return (values, self.depth);
+        };
+        let deadline = Instant::now() + time_budget;
+        // A game lasts at most three turns per empty position, since three passes in a row finish it.
+        let n_empty = board
+            .board()
+            .iter()
+            .flatten()
+            .filter(|cell| cell.is_none())
+            .count();
+        let max_depth = cmp::max(1, PLAYERS.len() * n_empty);
+        // The first depth is always completed so that a position is selected.
+        let mut values = self
+            .evaluate_all_to(board, player, positions, 1, None)
+            .unwrap();
+        let mut depth = 1;
+        self.depth_reached.store(depth, Ordering::Relaxed);
+        while depth < max_depth {
+            match self.evaluate_all_to(board, player, positions, depth + 1, Some(deadline)) {
+                Some(deeper_values) => {
+                    values = deeper_values;
+                    depth += 1;
+                    self.depth_reached.store(depth, Ordering::Relaxed);
+                }
+                None => break,
+            }
+        }
+        (values, depth)
+    }
+
+    /// Values of `positions` searched `depth` moves ahead, or `None` if `deadline` has passed,
+    /// where consecutive positions are evaluated on each thread with its own transposition table.
+    fn evaluate_all_to(
+        &self,
+        board: &Board,
+        player: Player,
+        positions: &[(usize, usize)],
+        depth: usize,
+        deadline: Option<Instant>,
+    ) -> Option<Vec<[u64; 3]>> {
+        let threads = cmp::max(1, self.search_config.threads);
+        if threads == 1 || positions.len() <= 1 {
+            return positions
+                .iter()
+                .map(|position| self.evaluate_to(board, player, *position, depth, deadline))
+                .collect();
+        }
+        let search_config = self.search_config;
+        thread::scope(|scope| {
+            let handles = positions
+                .chunks(positions.len().div_ceil(threads))
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let max_n = MaxN::new(depth).with_search_config(search_config);
+                        chunk
+                            .iter()
+                            .map(|position| {
+                                max_n.evaluate_to(board, player, *position, depth, deadline)
+                            })
+                            .collect::<Option<Vec<_>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Option<Vec<_>>>()
+                .map(|chunks| chunks.into_iter().flatten().collect())
+        })
+    }
+
+    /// Value of `position` searched `depth` moves ahead, or `None` if `deadline` has passed.
+    fn evaluate_to(
+        &self,
+        board: &Board,
+        player: Player,
+        position: (usize, usize),
+        depth: usize,
+        deadline: Option<Instant>,
+    ) -> Option<[u64; 3]> {
+        let mut board = board.clone();
+        board.apply_move(player, position);
+        self.search(&mut board, next(player), depth - 1, deadline)
+    }
+
+    /// Values of players after the best play of each player, or `None` if `deadline` has passed.
+    fn search(
+        &self,
+        board: &mut Board,
+        player: Player,
+        depth: usize,
+        deadline: Option<Instant>,
+    ) -> Option<[u64; 3]> {
+        if depth == 0 {
+            return Some(self.search_config.weights.evaluate(board));
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return None;
+        }
+        if let Some(value) = self.table.borrow().get(board.hash(), player, depth) {
+            return Some(value);
+        }
+        let value = self.search_moves(board, player, depth, deadline)?;
+        self.table
+            .borrow_mut()
+            .insert(board.hash(), player, depth, value);
+        Some(value)
+    }
+
+    /// Values of players after the best play of each player, searched without the table at this depth.
+    fn search_moves(
+        &self,
+        board: &mut Board,
+        player: Player,
+        depth: usize,
+        deadline: Option<Instant>,
+    ) -> Option<[u64; 3]> {
+        let positions = board.selectable_positions(player);
+        if positions.is_empty() {
+            if PLAYERS
+                .iter()
+                .all(|other| board.selectable_positions(*other).is_empty())
+            {
+                return Some(self.search_config.weights.evaluate(board));
+            }
+            return self.search(board, next(player), depth - 1, deadline);
+        }
+        let mut best: Option<[u64; 3]> = None;
+        for position in positions {
+            let undo = board.apply_move(player, position);
+            let value = self.search(board, next(player), depth - 1, deadline);
+            board.undo_move(undo);
+            let value = value?;
+            if best.is_none_or(|best| value[player.index()] > best[player.index()]) {
+                best = Some(value);
+            }
+        }
+        best
+    }
+}
+
+impl Strategy for MaxN {
+    fn select(
+        &self,
+        board: &Board,
+        availables: &Availables,
+        player: Player,
+    ) -> Option<(usize, usize)> {
+        if let Some(position) = self
+            .search_config
+            .book
+            .then(|| OpeningBook::builtin(board)?.lookup(board, player))
+            .flatten()
+            .filter(|position| availables[player].contains_key(position))
+        {
+            return Some(position);
+        }
+        let positions = sorted_moves(availables, player)
+            .into_iter()
+            .map(|Position(position)| position)
+            .collect::<Vec<_>>();
+        let (values, _) = self.evaluate_all(board, player, &positions);
+        let mut best: Option<((usize, usize), u64)> = None;
+        for (position, value) in positions.into_iter().zip(values) {
+            let value = value[player.index()];
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((position, value));
+            }
+        }
+        best.map(|(position, _)| position)
+    }
+
+    fn share_depth(&mut self, depth_reached: Arc<AtomicUsize>) {
+        self.depth_reached = depth_reached;
+    }
+}
+
+fn next(mut player: Player) -> Player {
+    player.advance();
+    player
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use rand::Rng;
+
+    /// Board of `range` after `n_moves` random moves from `seed` and the player to move.
+    fn random_position(range: usize, n_moves: usize, seed: u64) -> (Board, Player) {
+        let mut rng = rng::seeded(seed);
+        let mut board = Board::try_new(range).unwrap();
+        let mut player = Player::default();
+        for _ in 0..n_moves {
+            let positions = board.selectable_positions(player);
+            if !positions.is_empty() {
+                board.apply_move(player, positions[rng.gen_range(0..positions.len())]);
+            }
+            player.advance();
+        }
+        (board, player)
+    }
+
+    fn max_n(depth: usize, threads: usize) -> MaxN {
+        MaxN::new(depth).with_search_config(SearchConfig {
+            threads,
+            ..SearchConfig::default()
+        })
+    }
+
+    #[test]
+    fn threads_do_not_change_the_selected_position() {
+        for seed in 0..8 {
+            let (board, player) = random_position(8, 2 * seed as usize, seed);
+            let mut availables = Availables::default();
+            board.update_availables(&mut availables);
+            let positions = board.selectable_positions(player);
+            for depth in 1..=3 {
+                let single = max_n(depth, 1);
+                let multi = max_n(depth, 4);
+                assert_eq!(
+                    single.evaluate_all(&board, player, &positions),
+                    multi.evaluate_all(&board, player, &positions),
+                    "seed {} depth {}",
+                    seed,
+                    depth
+                );
+                assert_eq!(
+                    single.select(&board, &availables, player),
+                    multi.select(&board, &availables, player),
+                    "seed {} depth {}",
+                    seed,
+                    depth
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::MaxN;
+use crate::board::{Board, CaptureRules, Player};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Books generated by the book command for the standard openings of ranges 14 and 15.
+const BUILTIN_BOOKS: &[&str] = &[
+    include_str!("../../books/range14.json"),
+    include_str!("../../books/range15.json"),
+];
+
+/// Moves recommended in positions near the start of a game, keyed by the Zobrist hash of the board and the player to move.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpeningBook {
+    range: usize,
+    /// Entries sorted by the hash and the player.
+    entries: Vec<BookEntry>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BookEntry {
+    hash: u64,
+    player: Player,
+    position: (usize, usize),
+    /// Value of the move for the player, where the heaviest entry is selected among those of a position.
+    weight: u64,
+}
+
+impl OpeningBook {
+    /// Book of every position reached within `plies` moves from `board` where `player` moves first,
+    /// whose moves are the best ones by `max_n`.
+    pub fn generate(board: &Board, player: Player, plies: usize, max_n: &MaxN) -> Self {
+        let mut entries = Vec::new();
+        Self::add_entries(
+            &mut board.clone(),
+            player,
+            plies,
+            max_n,
+            &mut HashSet::new(),
+            &mut entries,
+        );
+        entries.sort_by_key(|entry| (entry.hash, entry.player.index()));
+        Self {
+            range: board.range(),
+            entries,
+        }
+    }
+
+    fn add_entries(
+        board: &mut Board,
+        player: Player,
+        plies: usize,
+        max_n: &MaxN,
+        visited: &mut HashSet<(u64, Player)>,
+        entries: &mut Vec<BookEntry>,
+    ) {
+        if plies == 0 {
+            return;
+        }
+        let mut positions = board.selectable_positions(player);
+        let mut player = player;
+        // Players who cannot select any position are skipped as in games.
+        for _ in 1..3 {
+            if !positions.is_empty() {
+                break;
+            }
+            player.advance();
+            positions = board.selectable_positions(player);
+        }
+        // Positions reached by different orders of moves are added once.
+        if positions.is_empty() || !visited.insert((board.hash(), player)) {
+            return;
+        }
+        let (values, _) = max_n.evaluate_all(board, player, &positions);
+        let (position, value) = positions
+            .iter()
+            .zip(values)
+            .fold(
+                None,
+                |best: Option<((usize, usize), u64)>, (position, value)| {
+                    let value = value[player.index()];
+                    match best {
+                        Some((_, best_value)) if best_value >= value => best,
+                        _ => Some((*position, value)),
+                    }
+                },
+            )
+            .unwrap();
+        entries.push(BookEntry {
+            hash: board.hash(),
+            player,
+            position,
+            weight: value,
+        });
+        let mut next_player = player;
+        next_player.advance();
+        for position in positions {
+            let undo = board.apply_move(player, position);
+            Self::add_entries(board, next_player, plies - 1, max_n, visited, entries);
+            board.undo_move(undo);
+        }
+    }
+
+    /// Built-in book of the board, which is only for the standard capture rules.
+    pub fn builtin(board: &Board) -> Option<&'static Self> {
+        static BOOKS: OnceLock<Vec<OpeningBook>> = OnceLock::new();
+        if *board.capture_rules() != CaptureRules::default() {
+            return None;
+        }
+        BOOKS
+            .get_or_init(|| {
+                BUILTIN_BOOKS
+                    .iter()
+                    .map(|text| serde_json::from_str(text).unwrap())
+                    .collect()
+            })
+            .iter()
+            .find(|book| book.range == board.range())
+    }
+
+    /// Recommended position of `player` on `board`, or `None` if the book does not have it.
+    pub fn lookup(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        if board.range() != self.range {
+            return None;
+        }
+        let key = (board.hash(), player.index());
+        let start = self
+            .entries
+            .partition_point(|entry| (entry.hash, entry.player.index()) < key);
+        self.entries[start..]
+            .iter()
+            .take_while(|entry| (entry.hash, entry.player.index()) == key)
+            .max_by_key(|entry| entry.weight)
+            .map(|entry| entry.position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
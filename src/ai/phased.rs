@@ -0,0 +1,199 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::{Greedy, MaxN, Random, SearchConfig, Strategy};
+use crate::board::{Availables, Board, Player};
+use crate::error::TriversiError;
+use rand::rngs::StdRng;
+use serde_derive::{Deserialize, Serialize};
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+/// Stages of a game told by how much of the board is filled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Opening,
+    Midgame,
+    Endgame,
+}
+
+/// Percentages of the board filled with stones where the midgame and the endgame begin,
+/// where a missing percentage takes its default.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Phases {
+    pub midgame: u64,
+    pub endgame: u64,
+}
+
+impl Default for Phases {
+    fn default() -> Self {
+        Self {
+            midgame: 30,
+            endgame: 70,
+        }
+    }
+}
+
+impl Phases {
+    /// Checks that the midgame begins no later than the endgame, which begins within the board.
+    pub fn validate(&self) -> Result<(), TriversiError> {
+        if self.midgame <= self.endgame && self.endgame <= 100 {
+            Ok(())
+        } else {
+            Err(TriversiError::InvalidPhases(self.midgame, self.endgame))
+        }
+    }
+
+    /// Phase of the game on `board`.
+    pub fn phase(&self, board: &Board) -> Phase {
+        let filled = 100 * board.count().total() / board.num_cells() as u64;
+        if filled >= self.endgame {
+            Phase::Endgame
+        } else if filled >= self.midgame {
+            Phase::Midgame
+        } else {
+            Phase::Opening
+        }
+    }
+}
+
+/// Gets stronger as the board is filled; it selects at random in the opening,
+/// flips the most stones in the midgame, and searches by max-n in the endgame.
+pub struct Phased {
+    phases: Phases,
+    opening: Box<dyn Strategy + Send>,
+    midgame: Box<dyn Strategy + Send>,
+    endgame: Box<dyn Strategy + Send>,
+}
+
+impl Phased {
+    pub fn new(rng: StdRng, search_config: SearchConfig) -> Self {
+        Self {
+            phases: search_config.phases,
+            opening: Box::new(Random::new(rng)),
+            midgame: Box::new(Greedy),
+            endgame: Box::new(MaxN::default().with_search_config(search_config)),
+        }
+    }
+
+    /// Strategy selecting a position on `board`.
+    fn active(&self, board: &Board) -> &(dyn Strategy + Send) {
+        match self.phases.phase(board) {
+            Phase::Opening => self.opening.as_ref(),
+            Phase::Midgame => self.midgame.as_ref(),
+            Phase::Endgame => self.endgame.as_ref(),
+        }
+    }
+}
+
+impl Strategy for Phased {
+    fn select(
+        &self,
+        board: &Board,
+        availables: &Availables,
+        player: Player,
+    ) -> Option<(usize, usize)> {
+        self.active(board).select(board, availables, player)
+    }
+
+    fn share_depth(&mut self, depth_reached: Arc<AtomicUsize>) {
+        self.opening.share_depth(depth_reached.clone());
+        self.midgame.share_depth(depth_reached.clone());
+        self.endgame.share_depth(depth_reached);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use rand::Rng;
+
+    #[test]
+    fn phase_is_told_by_the_filled_percentage() {
+        let phases = Phases {
+            midgame: 50,
+            endgame: 80,
+        };
+        let mut board = Board::try_new(8).unwrap();
+        let mut player = Player::default();
+        let mut seen = Vec::new();
+        let mut passes = 0;
+        while passes < 3 {
+            let filled = 100 * board.count().total() / board.num_cells() as u64;
+            let expected = match filled {
+                0..=49 => Phase::Opening,
+                50..=79 => Phase::Midgame,
+                _ => Phase::Endgame,
+            };
+            assert_eq!(phases.phase(&board), expected, "filled {}", filled);
+            if seen.last() != Some(&expected) {
+                seen.push(expected);
+            }
+            match board.selectable_positions(player).first() {
+                Some(position) => {
+                    board.apply_move(player, *position);
+                    passes = 0;
+                }
+                None => passes += 1,
+            }
+            player.advance();
+        }
+        assert_eq!(seen, [Phase::Opening, Phase::Midgame, Phase::Endgame]);
+    }
+
+    #[test]
+    fn strategy_switches_with_the_phase() {
+        let search_config = SearchConfig {
+            phases: Phases {
+                midgame: 50,
+                endgame: 80,
+            },
+            ..SearchConfig::default()
+        };
+        for seed in 0..4 {
+            let phased = Phased::new(rng::seeded(seed), search_config);
+            // Only the opening draws from the generator, so a random strategy from the same seed
+            // selects the same positions if it is asked only in the opening.
+            let random = Random::new(rng::seeded(seed));
+            let max_n = MaxN::default().with_search_config(search_config);
+            let mut rng = rng::seeded(seed + 100);
+            let mut board = Board::try_new(8).unwrap();
+            let mut availables = Availables::default();
+            let mut player = Player::default();
+            let mut seen = Vec::new();
+            let mut passes = 0;
+            while passes < 3 {
+                board.update_availables(&mut availables);
+                let phase = search_config.phases.phase(&board);
+                let expected = match phase {
+                    Phase::Opening => random.select(&board, &availables, player),
+                    Phase::Midgame => Greedy.select(&board, &availables, player),
+                    Phase::Endgame => max_n.select(&board, &availables, player),
+                };
+                assert_eq!(
+                    phased.select(&board, &availables, player),
+                    expected,
+                    "seed {} phase {:?}",
+                    seed,
+                    phase
+                );
+                if !seen.contains(&phase) {
+                    seen.push(phase);
+                }
+                let positions = board.selectable_positions(player);
+                if positions.is_empty() {
+                    passes += 1;
+                } else {
+                    board.apply_move(player, positions[rng.gen_range(0..positions.len())]);
+                    passes = 0;
+                }
+                player.advance();
+            }
+            assert_eq!(seen, [Phase::Opening, Phase::Midgame, Phase::Endgame]);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::Strategy;
+use crate::board::{sorted_moves, Availables, Board, Player};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::cell::RefCell;
+
+/// Selects a position uniformly at random.
+/// Positions are sorted before drawing so that the selection depends only on the generator.
+#[derive(Debug)]
+pub struct Random {
+    rng: RefCell<StdRng>,
+}
+
+impl Random {
+    pub fn new(rng: StdRng) -> Self {
+        Self {
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn select(
+        &self,
+        _board: &Board,
+        availables: &Availables,
+        player: Player,
+    ) -> Option<(usize, usize)> {
+        let positions = sorted_moves(availables, player);
+        positions
+            .choose(&mut *self.rng.borrow_mut())
+            .map(|position| position.0)
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::Player;
+
+/// Default number of entries of `TranspositionTable`.
+pub const DEFAULT_TABLE_SIZE: usize = 1 << 16;
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    hash: u64,
+    player: Player,
+    depth: usize,
+    value: [u64; 3],
+}
+
+/// Values of searched positions keyed by their Zobrist hashes and the players to move,
+/// where an entry searched deeper is kept when two positions share a slot.
+#[derive(Clone, Debug)]
+pub struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+}
+
+impl TranspositionTable {
+    /// Table of `size` entries, which stores nothing if `size` is 0.
+    pub fn new(size: usize) -> Self {
+        Self {
+            entries: vec![None; size],
+        }
+    }
+
+    /// Value of a position searched `depth` moves ahead with `player` to move.
+    pub fn get(&self, hash: u64, player: Player, depth: usize) -> Option<[u64; 3]> {
+        let entry = self.entries.get(self.slot(hash)?)?.as_ref()?;
+        (entry.hash == hash && entry.player == player && entry.depth == depth)
+            .then_some(entry.value)
+    }
+
+    pub fn insert(&mut self, hash: u64, player: Player, depth: usize, value: [u64; 3]) {
+        let Some(slot) = self.slot(hash) else {
+            return;
+        };
+        let entry = &mut self.entries[slot];
+        if entry.is_none_or(|entry| depth >= entry.depth) {
+            *entry = Some(Entry {
+                hash,
+                player,
+                depth,
+                value,
+            });
+        }
+    }
+
+    fn slot(&self, hash: u64) -> Option<usize> {
+        (!self.entries.is_empty()).then(|| (hash % self.entries.len() as u64) as usize)
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Evaluation of every position selectable by the player to move, shown by the analyze command.
+
+use crate::ai::MaxN;
+use crate::board::{Board, Player, Position, PLAYERS};
+use getset::{CopyGetters, Getters};
+use serde_derive::Serialize;
+use std::cmp;
+use std::fmt::Write as _;
+
+#[derive(Clone, Debug, CopyGetters, Serialize)]
+#[getset(get_copy = "pub")]
+pub struct MoveAnalysis {
+    position: (usize, usize),
+    /// Number of stones flipped by the move itself.
+    flips: u64,
+    /// Numbers of stones of the seats after the search.
+    counts: [u64; 3],
+}
+
+#[derive(Clone, Debug, Getters, Serialize)]
+pub struct Analysis {
+    /// Seat to move.
+    seat: usize,
+    /// Number of moves searched ahead.
+    depth: usize,
+    /// Whether the player to move cannot select any position and must pass.
+    pass: bool,
+    /// Whether no player can select any position.
+    finished: bool,
+    /// Moves ordered from the best for the player to move.
+    #[getset(get = "pub")]
+    moves: Vec<MoveAnalysis>,
+}
+
+/// Evaluates every position selectable by `player` with the max^n search `depth` moves ahead.
+pub fn analyze(board: &Board, player: Player, depth: usize) -> Analysis {
+    analyze_with(board, player, &MaxN::new(depth))
+}
+
+/// Evaluates every position selectable by `player` with `max_n`.
+pub fn analyze_with(board: &Board, player: Player, max_n: &MaxN) -> Analysis {
+    let positions = board.selectable_positions(player);
+    let (counts, depth) = max_n.evaluate_all(board, player, &positions);
+    let mut moves = positions
+        .into_iter()
+        .zip(counts)
+        .map(|(position, counts)| MoveAnalysis {
+            position,
+            flips: board.flips(player, position).len() as u64,
+            counts,
+        })
+        .collect::<Vec<_>>();
+    moves.sort_by_key(|analysis| {
+        (
+            cmp::Reverse(analysis.counts[player.index()]),
+            cmp::Reverse(analysis.flips),
+            Position::from(analysis.position),
+        )
+    });
+    Analysis {
+        seat: player.seat(),
+        depth,
+        pass: moves.is_empty(),
+        finished: PLAYERS
+            .iter()
+            .all(|player| board.selectable_positions(*player).is_empty()),
+        moves,
+    }
+}
+
+impl Analysis {
+    /// Table of the moves, where `player_names` are the names of the seats.
+    pub fn table(&self, player_names: &[String]) -> String {
+        let mut text = String::new();
+        if self.finished {
+            writeln!(text, "The game is finished.").unwrap();
+            return text;
+        }
+        let name = &player_names[self.seat - 1];
+        if self.pass {
+            writeln!(
+                text,
+                "Seat {} ({}) cannot select any position and must pass.",
+                self.seat, name
+            )
+            .unwrap();
+            return text;
+        }
+        writeln!(
+            text,
+            "Seat {} ({}) to move, searched {} moves ahead.",
+            self.seat, name, self.depth
+        )
+        .unwrap();
+        let widths = player_names
+            .iter()
+            .map(|name| cmp::max(name.len(), 5))
+            .collect::<Vec<_>>();
+        write!(text, "{:>8} {:>5}", "Position", "Flips").unwrap();
+        for (name, width) in player_names.iter().zip(&widths) {
+            write!(text, " {:>width$}", name, width = width).unwrap();
+        }
+        writeln!(text).unwrap();
+        for analysis in &self.moves {
+            let (x, y) = analysis.position;
+            write!(text, "{:>8} {:>5}", format!("{},{}", x, y), analysis.flips).unwrap();
+            for (count, width) in analysis.counts.iter().zip(&widths) {
+                write!(text, " {:>width$}", count, width = width).unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        text
+    }
+}
@@ -3,10 +3,28 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+pub mod ai_worker;
+pub mod annotator;
 pub mod board_display;
 pub mod color_config;
+pub mod description;
+pub mod event_log;
 pub mod key_binding;
+pub mod match_state;
+pub mod message_log;
+pub mod messages;
+pub mod notification;
+pub mod records;
+pub mod saves;
+pub mod screenshot;
+pub mod seat_config;
+pub mod settings;
+pub mod stopwatch;
 pub mod system;
 pub mod tui;
 
-pub use color_config::ColorConfig;
+pub use color_config::{ColorConfig, Theme};
+pub use messages::Messages;
+pub use notification::Notification;
+pub use seat_config::{Controller, SeatConfig};
+pub use settings::Settings;
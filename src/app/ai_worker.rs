@@ -0,0 +1,64 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::{SearchConfig, StrategyKind};
+use crate::board::{Availables, Board, Player};
+use getset::CopyGetters;
+use rand::rngs::StdRng;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvError, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+/// Computation of a computer player on a background thread.
+/// Dropping the worker cancels the computation, i.e., its result is discarded.
+#[derive(Debug, CopyGetters)]
+pub struct AiWorker {
+    #[getset(get_copy = "pub")]
+    player: Player,
+    receiver: Receiver<Option<(usize, usize)>>,
+    /// Depth searched so far by a searching strategy, which is 0 until a depth is completed.
+    depth_reached: Arc<AtomicUsize>,
+}
+
+impl AiWorker {
+    pub fn spawn(
+        strategy_kind: StrategyKind,
+        search_config: SearchConfig,
+        rng: StdRng,
+        board: Board,
+        availables: Availables,
+        player: Player,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let depth_reached = Arc::new(AtomicUsize::new(0));
+        let mut strategy = strategy_kind.strategy(rng, search_config);
+        strategy.share_depth(Arc::clone(&depth_reached));
+        thread::spawn(move || {
+            let position = strategy.select(&board, &availables, player);
+            // The receiver has been dropped if the computation is cancelled.
+            let _ = sender.send(position);
+        });
+        Self {
+            player,
+            receiver,
+            depth_reached,
+        }
+    }
+
+    pub fn depth_reached(&self) -> usize {
+        self.depth_reached.load(Ordering::Relaxed)
+    }
+
+    /// Selected position if the computation has finished.
+    pub fn try_result(&self) -> Result<Option<(usize, usize)>, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Selected position after waiting for the computation.
+    pub fn wait(&self) -> Result<Option<(usize, usize)>, RecvError> {
+        self.receiver.recv()
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::{SearchConfig, StrategyKind};
+use crate::board::{Availables, Board, Player};
+use getset::CopyGetters;
+use rand::rngs::StdRng;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// Differences from the best move above which a move is annotated as `?` or `??`.
+const MISTAKE_DELTA: u64 = 2;
+const BLUNDER_DELTA: u64 = 4;
+
+/// Class of a recorded move by its difference from the best move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quality {
+    /// The move selected by the strategy.
+    Best,
+    /// Another move flipping fewer than `MISTAKE_DELTA` stones less than the best move.
+    Good,
+    Inaccuracy,
+    Blunder,
+}
+
+impl Quality {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Best => "best",
+            Self::Good => "good",
+            Self::Inaccuracy => "inaccuracy",
+            Self::Blunder => "blunder",
+        }
+    }
+}
+
+/// Evaluation of a recorded move compared with the move the strategy selects in the position.
+#[derive(Clone, Copy, Debug, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct Annotation {
+    /// Position selected by the strategy, which is `None` if the strategy selects nothing.
+    best: Option<(usize, usize)>,
+    /// Number of stones the recorded move flips fewer than the best move.
+    delta: u64,
+    quality: Quality,
+}
+
+impl Annotation {
+    fn new(best: Option<(usize, usize)>, position: (usize, usize), delta: u64) -> Self {
+        let quality = if best.is_none_or(|best| best == position) {
+            Quality::Best
+        } else if delta >= BLUNDER_DELTA {
+            Quality::Blunder
+        } else if delta >= MISTAKE_DELTA {
+            Quality::Inaccuracy
+        } else {
+            Quality::Good
+        };
+        Self {
+            best,
+            delta,
+            quality,
+        }
+    }
+
+    /// `??` for a blunder, `?` for an inaccuracy, and empty otherwise.
+    pub fn mark(&self) -> &'static str {
+        match self.quality {
+            Quality::Blunder => "??",
+            Quality::Inaccuracy => "?",
+            Quality::Best | Quality::Good => "",
+        }
+    }
+
+    pub fn is_blunder(&self) -> bool {
+        self.quality == Quality::Blunder
+    }
+}
+
+/// Message from the annotating thread.
+#[derive(Clone, Debug)]
+pub enum Progress {
+    /// Numbers of annotated moves and all moves.
+    Annotating(usize, usize),
+    Finished(Vec<Annotation>),
+}
+
+/// Evaluation of recorded moves on a background thread.
+/// Dropping the annotator cancels the computation.
+#[derive(Debug)]
+pub struct Annotator {
+    receiver: Receiver<Progress>,
+}
+
+impl Annotator {
+    /// Annotates moves where each player of `moves` selected the position on the board before it,
+    /// comparing the number of flipped stones with the position selected by `strategy_kind`.
+    pub fn spawn(
+        moves: Vec<(Board, Player, (usize, usize))>,
+        strategy_kind: StrategyKind,
+        search_config: SearchConfig,
+        rng: StdRng,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let strategy = strategy_kind.strategy(rng, search_config);
+            let mut availables = Availables::default();
+            let mut annotations = Vec::with_capacity(moves.len());
+            for (board, player, position) in &moves {
+                board.update_availables(&mut availables);
+                let best = strategy.select(board, &availables, *player);
+                let flips = |position: (usize, usize)| board.flips(*player, position).len() as u64;
+                let delta = best.map_or(0, |best| flips(best).saturating_sub(flips(*position)));
+                annotations.push(Annotation::new(best, *position, delta));
+                // The receiver has been dropped if the computation is cancelled.
+                if sender
+                    .send(Progress::Annotating(annotations.len(), moves.len()))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            let _ = sender.send(Progress::Finished(annotations));
+        });
+        Self { receiver }
+    }
+
+    /// Latest progress if any has been made since the last call.
+    pub fn try_progress(&self) -> Result<Progress, TryRecvError> {
+        let mut progress = self.receiver.try_recv()?;
+        while let Ok(next) = self.receiver.try_recv() {
+            progress = next;
+        }
+        Ok(progress)
+    }
+}
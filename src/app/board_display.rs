@@ -3,39 +3,229 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+pub mod compact_board;
 pub mod paragraph_board;
+pub mod unicode_board;
 
+pub use compact_board::CompactBoard;
 pub use paragraph_board::ParagraphBoard;
+pub use unicode_board::UnicodeBoard;
 
 use crate::app::system::Play;
 use crate::app::ColorConfig;
-use crate::board::{Board, Player};
+use crate::board::{Board, Direction, Player};
+use std::cmp;
+use std::collections::HashSet;
 use tui::backend::Backend;
-use tui::layout::Rect;
+use tui::layout::{Alignment, Rect};
+use tui::style::Style;
 use tui::terminal::Frame;
+use tui::widgets::{Block, Borders, Paragraph};
+
+/// Lines from a placed stone through the stones flipped by it, drawn in the color of `player`.
+#[derive(Clone, Debug)]
+pub struct CaptureLines {
+    pub player: Player,
+    pub lines: Vec<Vec<(usize, usize)>>,
+}
+
+/// Directions in which a stone placed at `position` captures, drawn as arrows next to it in the color of `player`.
+#[derive(Clone, Debug)]
+pub struct CaptureArrows {
+    pub player: Player,
+    pub position: (usize, usize),
+    pub directions: Vec<Direction>,
+}
+
+/// Stone placed by the move browsed in the history and stones flipped by it,
+/// which are emphasized over the other positions dimmed.
+#[derive(Clone, Debug)]
+pub struct MoveDiff {
+    pub placed: (usize, usize),
+    pub flipped: Vec<(usize, usize)>,
+}
+
+impl MoveDiff {
+    /// Diff of the move at `placed` which turned `before` into `after`.
+    pub fn new(before: &Board, after: &Board, placed: (usize, usize)) -> Self {
+        let flipped = after
+            .iter_occupied()
+            .filter(|(position, player)| {
+                *position != placed && before.player(*position) != Some(*player)
+            })
+            .map(|(position, _)| position)
+            .collect();
+        Self { placed, flipped }
+    }
+
+    /// `style` of `position` with the modifier of its change by the move.
+    pub fn patch_style(
+        &self,
+        style: Style,
+        position: (usize, usize),
+        color_config: ColorConfig,
+    ) -> Style {
+        let modifier = if position == self.placed {
+            color_config.placed_modifier()
+        } else if self.flipped.contains(&position) {
+            color_config.flipped_modifier()
+        } else {
+            color_config.unchanged_modifier()
+        };
+        style.add_modifier(modifier)
+    }
+}
+
+/// Stones left visible in the fog, which hides the owners of the other stones
+/// so that a learner focuses on the options of `player`.
+#[derive(Clone, Debug)]
+pub struct Fog {
+    pub player: Player,
+    /// Stones of the other players which `player` can flip by a move.
+    pub capturable: HashSet<(usize, usize)>,
+}
+
+impl Fog {
+    /// `style` of a stone of `owner` at `position`, which is replaced by the fog style if it is hidden.
+    pub fn patch_style(
+        &self,
+        style: Style,
+        owner: Player,
+        position: (usize, usize),
+        color_config: ColorConfig,
+    ) -> Style {
+        if owner == self.player || self.capturable.contains(&position) {
+            style
+        } else {
+            style.patch(color_config.fog_style())
+        }
+    }
+}
 
 pub trait BoardDisplay {
     const MAX_DISTANCE: usize;
     fn player_name(&self, player: Player) -> &str;
+    /// Mark of the stones of `player` on the board.
+    fn player_mark(&self, player: Player) -> String;
     fn scroll_left(&mut self);
     fn scroll_right(&mut self);
     fn scroll_up(&mut self);
     fn scroll_down(&mut self);
     fn scroll_reset(&mut self);
+    /// Scrolls so that `position` is at the middle of the board block `rect`.
+    fn center_on(&mut self, board: &Board, position: (usize, usize), rect: Rect);
+    /// Scrolls as little as possible so that `position` is inside the board block `rect`.
+    fn scroll_into_view(&mut self, board: &Board, position: (usize, usize), rect: Rect);
+    /// Scrolls by `pages` times the size of the board block `rect` in each direction,
+    /// where positive pages show the right or the lower part, and a part of the board stays visible.
+    fn scroll_page(&mut self, board: &Board, pages: (i32, i32), rect: Rect);
     fn zoom_in(&mut self);
     fn zoom_out(&mut self);
     fn toggle_frame_visibility(&mut self);
-    fn render_scroll_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect);
-    fn render_zoom_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect);
+    fn toggle_home_region_visibility(&mut self);
+    /// Turns the board upside down or back, or returns false if the style cannot turn it.
+    fn toggle_upside_down(&mut self) -> bool {
+        false
+    }
+    /// Whether the wide edge of the board is at the top.
+    fn is_upside_down(&self) -> bool {
+        false
+    }
+    /// Size of the board block including its borders which shows at least a position at the current zoom.
+    fn min_board_block_size(&self) -> (u16, u16);
+    /// Offsets of the scroll, or the percentages of the board traversed in directions
+    /// where the board is larger than the board block `board_rect`.
+    fn scroll_text(&self, board: &Board, board_rect: Rect) -> String;
+    /// Level of the zoom, e.g., the distance between positions.
+    fn zoom_level(&self) -> usize;
+    fn render_scroll_block<B: Backend>(
+        &self,
+        frame: &mut Frame<B>,
+        rect: Rect,
+        title: &str,
+        board: &Board,
+        board_rect: Rect,
+    ) {
+        frame.render_widget(
+            Paragraph::new(self.scroll_text(board, board_rect))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            rect,
+        );
+    }
+    fn render_zoom_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect, title: &str) {
+        frame.render_widget(
+            Paragraph::new(self.zoom_level().to_string())
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            rect,
+        );
+    }
     #[allow(clippy::too_many_arguments)]
     fn render_board_block<B: Backend>(
         &self,
         frame: &mut Frame<B>,
         rect: Rect,
+        title: &str,
         boad: &Board,
         color_config: ColorConfig,
         play: Play,
         current_player: Player,
         current_position: (usize, usize),
+        capture_lines: Option<&CaptureLines>,
+        capture_arrows: Option<&CaptureArrows>,
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
     );
 }
+
+/// Offset in units of `step` cells changed as little as possible from `offset`
+/// so that the cell at `target` is within `length` cells.
+fn following_offset(offset: i32, target: usize, length: u16, step: usize) -> i32 {
+    let (offset, target, step) = (offset as i64, target as i64, step as i64);
+    let shown_at = target + offset * step;
+    if length == 0 {
+        offset as i32
+    } else if shown_at < 0 {
+        -target.div_euclid(step) as i32
+    } else if shown_at >= length as i64 {
+        (length as i64 - 1 - target).div_euclid(step) as i32
+    } else {
+        offset as i32
+    }
+}
+
+/// Offset in units of `step` cells which brings the cell at `target` to the middle of `length` cells.
+/// It is clamped so that a part of the board spanning `extent` cells stays visible.
+fn centering_offset(target: usize, extent: usize, length: u16, step: usize) -> i32 {
+    let step = step as i64;
+    let offset = (length as i64 / 2 - target as i64 + step / 2).div_euclid(step);
+    clamp_offset(offset, extent, length, step as usize)
+}
+
+/// Offset in units of `step` cells moved from `offset` by `pages` times `length` cells,
+/// which is clamped as in `centering_offset`.
+fn paged_offset(offset: i32, pages: i32, extent: usize, length: u16, step: usize) -> i32 {
+    let page = cmp::max(1, length as i64 / step as i64);
+    clamp_offset(offset as i64 - pages as i64 * page, extent, length, step)
+}
+
+/// `offset` limited so that a part of the board spanning `extent` cells stays within `length` cells.
+fn clamp_offset(offset: i64, extent: usize, length: u16, step: usize) -> i32 {
+    let step = step as i64;
+    offset.clamp(
+        -((extent as i64 - 1) / step),
+        (length as i64 - 1).max(0) / step,
+    ) as i32
+}
+
+/// Percentage of the board spanning `extent` cells scrolled past by `offset` in units of `step` cells
+/// if it is larger than `length` cells, or `offset` itself otherwise.
+fn scroll_label(offset: i32, extent: usize, length: u16, step: usize) -> String {
+    let hidden = extent as i64 - length as i64;
+    if hidden <= 0 {
+        return offset.to_string();
+    }
+    let scrolled = (-(offset as i64) * step as i64).clamp(0, hidden);
+    format!("{}%", scrolled * 100 / hidden)
+}
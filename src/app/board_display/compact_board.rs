@@ -0,0 +1,331 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Board display using one column per position and no frames, for narrow terminals,
+//! where a mark of two columns takes the empty column on its right.
+//!
+//! Zoom changes only the number of lines between rows.
+//! As there are no frames to show, toggling frames hides marks of empty positions instead.
+
+use crate::app::board_display::paragraph_board::PlayerMark;
+use crate::app::board_display::{
+    centering_offset, following_offset, paged_offset, scroll_label, BoardDisplay, CaptureArrows,
+    CaptureLines, ColorConfig, Fog, MoveDiff,
+};
+use crate::app::system::Play;
+use crate::board::{Board, Player};
+use crate::error::TriversiError;
+use std::cmp;
+use tui::backend::Backend;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::terminal::Frame;
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph};
+
+pub struct CompactBoard {
+    /// Number of lines from a row to the next row.
+    row_spacing: usize,
+    offset: (i32, i32),
+    player_mark: PlayerMark,
+    player_name: (String, String, String),
+    frame_visibility: bool,
+    home_region_visibility: bool,
+}
+
+impl CompactBoard {
+    /// Marks are read from `player_marks_str` separated by ',' as in `PlayerMark`.
+    pub fn try_new(
+        player_names_str: &str,
+        player_marks_str: &str,
+        frame_visibility: bool,
+        home_region_visibility: bool,
+    ) -> Result<Self, TriversiError> {
+        let names = player_names_str.split(',').collect::<Vec<_>>();
+        if names.len() != 3 {
+            return Err(TriversiError::InvalidStringForPlayerNames(
+                player_names_str.to_owned(),
+            ));
+        }
+        Ok(Self {
+            row_spacing: 1,
+            offset: (0, 0),
+            player_mark: PlayerMark::try_from(player_marks_str.to_owned())?,
+            player_name: (
+                names.first().unwrap().to_string(),
+                names.get(1).unwrap().to_string(),
+                names.get(2).unwrap().to_string(),
+            ),
+            frame_visibility,
+            home_region_visibility,
+        })
+    }
+
+    fn lattice_size(&self, board: &Board) -> (usize, usize) {
+        (
+            2 * (board.range() - 1) + 1,
+            self.row_spacing * (board.range() - 1) + 1,
+        )
+    }
+
+    /// Position drawn at `(col, row)` of the board, if any.
+    fn position(&self, board: &Board, (col, row): (usize, usize)) -> Option<(usize, usize)> {
+        if !row.is_multiple_of(self.row_spacing) {
+            return None;
+        }
+        let y = row / self.row_spacing;
+        if y >= board.range() {
+            return None;
+        }
+        // Column of the leftmost position of the row `y`.
+        let left_edge = board.range() - y - 1;
+        if col < left_edge || !(col - left_edge).is_multiple_of(2) {
+            return None;
+        }
+        let x = (col - left_edge) / 2;
+        (x <= y).then_some((x, y))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn make_span(
+        &self,
+        board: &Board,
+        position: Option<(usize, usize)>,
+        color_config: ColorConfig,
+        current_player: Player,
+        current_position: (usize, usize),
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) -> Span<'static> {
+        let Some(position) = position else {
+            return Span::raw(" ");
+        };
+        let player = board.player(position);
+        let mut style = Style::default();
+        if self.home_region_visibility {
+            if let Some(home_player) = board.home_player(position) {
+                style = style.bg(color_config.home_background(home_player));
+            }
+        }
+        let mark = match player {
+            Some(player) => {
+                style = style.patch(color_config.player_style(player));
+                if let Some(fog) = fog {
+                    style = fog.patch_style(style, player, position, color_config);
+                }
+                if player == current_player && move_diff.is_none() {
+                    style = style.add_modifier(color_config.current_player_modifier());
+                }
+                self.player_mark.convert(player)
+            }
+            None => match self.frame_visibility {
+                true => " ",
+                false => ".",
+            },
+        };
+        if let Some(move_diff) = move_diff {
+            style = move_diff.patch_style(style, position, color_config);
+        }
+        if position == current_position {
+            style = color_config.cursor_style(style, player.is_some());
+        }
+        Span::styled(mark.to_owned(), style)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn make_board_cells(
+        &self,
+        board: &Board,
+        rect: Rect,
+        color_config: ColorConfig,
+        current_player: Player,
+        current_position: (usize, usize),
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) -> Vec<Spans<'static>> {
+        let shift_x = self.offset.0 as i64 * 2;
+        let shift_y = self.offset.1 as i64 * self.row_spacing as i64;
+        (0..rect.height as i64)
+            .map(|screen_row| {
+                let row = screen_row - shift_y;
+                let mut spans = Vec::new();
+                let mut screen_col = 0;
+                // A wide mark takes the empty column on its right.
+                while screen_col < rect.width as i64 {
+                    let col = screen_col - shift_x;
+                    let position = if row < 0 || col < 0 {
+                        None
+                    } else {
+                        self.position(board, (col as usize, row as usize))
+                    };
+                    let span = self.make_span(
+                        board,
+                        position,
+                        color_config,
+                        current_player,
+                        current_position,
+                        move_diff,
+                        fog,
+                    );
+                    screen_col += cmp::max(1, span.width()) as i64;
+                    spans.push(span);
+                }
+                Spans::from(spans)
+            })
+            .collect()
+    }
+
+    fn make_boarder_style(
+        &self,
+        color_config: ColorConfig,
+        play: Play,
+        current_player: Player,
+    ) -> Style {
+        match play {
+            _ if color_config.alert_border() => Style::default().fg(color_config.error_color()),
+            Play::Finished | Play::History => Style::default(),
+            _ => color_config.player_style(current_player),
+        }
+    }
+}
+
+impl BoardDisplay for CompactBoard {
+    const MAX_DISTANCE: usize = 3;
+
+    fn player_name(&self, player: Player) -> &str {
+        match player {
+            Player::Zero => &self.player_name.0,
+            Player::One => &self.player_name.1,
+            Player::Two => &self.player_name.2,
+        }
+    }
+
+    fn player_mark(&self, player: Player) -> String {
+        self.player_mark.convert(player).to_owned()
+    }
+
+    fn scroll_left(&mut self) {
+        self.offset.0 += 1
+    }
+
+    fn scroll_right(&mut self) {
+        self.offset.0 -= 1
+    }
+
+    fn scroll_up(&mut self) {
+        self.offset.1 += 1
+    }
+
+    fn scroll_down(&mut self) {
+        self.offset.1 -= 1
+    }
+
+    fn scroll_reset(&mut self) {
+        self.offset = (0, 0)
+    }
+
+    fn center_on(&mut self, board: &Board, (x, y): (usize, usize), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let (col, row) = (board.range() - y - 1 + 2 * x, self.row_spacing * y);
+        let (width, height) = self.lattice_size(board);
+        self.offset = (
+            centering_offset(col, width, inner.width, 2),
+            centering_offset(row, height, inner.height, self.row_spacing),
+        );
+    }
+
+    fn scroll_into_view(&mut self, board: &Board, (x, y): (usize, usize), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let (col, row) = (board.range() - y - 1 + 2 * x, self.row_spacing * y);
+        self.offset = (
+            following_offset(self.offset.0, col, inner.width, 2),
+            following_offset(self.offset.1, row, inner.height, self.row_spacing),
+        );
+    }
+
+    fn scroll_page(&mut self, board: &Board, (pages_x, pages_y): (i32, i32), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let (width, height) = self.lattice_size(board);
+        self.offset = (
+            paged_offset(self.offset.0, pages_x, width, inner.width, 2),
+            paged_offset(
+                self.offset.1,
+                pages_y,
+                height,
+                inner.height,
+                self.row_spacing,
+            ),
+        );
+    }
+
+    fn zoom_in(&mut self) {
+        if self.row_spacing < Self::MAX_DISTANCE {
+            self.row_spacing += 1;
+        }
+    }
+
+    fn zoom_out(&mut self) {
+        if self.row_spacing > 1 {
+            self.row_spacing -= 1;
+        }
+    }
+
+    fn toggle_frame_visibility(&mut self) {
+        self.frame_visibility ^= true;
+    }
+
+    fn toggle_home_region_visibility(&mut self) {
+        self.home_region_visibility ^= true;
+    }
+
+    fn min_board_block_size(&self) -> (u16, u16) {
+        (5, self.row_spacing as u16 + 3)
+    }
+
+    fn scroll_text(&self, board: &Board, board_rect: Rect) -> String {
+        let inner = Block::default().borders(Borders::ALL).inner(board_rect);
+        let (width, height) = self.lattice_size(board);
+        format!(
+            "{}, {}",
+            scroll_label(self.offset.0, width, inner.width, 2),
+            scroll_label(self.offset.1, height, inner.height, self.row_spacing)
+        )
+    }
+
+    fn zoom_level(&self) -> usize {
+        self.row_spacing
+    }
+
+    fn render_board_block<B: Backend>(
+        &self,
+        frame: &mut Frame<B>,
+        rect: Rect,
+        title: &str,
+        board: &Board,
+        color_config: ColorConfig,
+        play: Play,
+        current_player: Player,
+        current_position: (usize, usize),
+        _capture_lines: Option<&CaptureLines>,
+        _capture_arrows: Option<&CaptureArrows>,
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(self.make_boarder_style(color_config, play, current_player));
+        let board_cells = self.make_board_cells(
+            board,
+            block.inner(rect),
+            color_config,
+            current_player,
+            current_position,
+            move_diff,
+            fog,
+        );
+        frame.render_widget(Paragraph::new(board_cells).block(block), rect);
+    }
+}
@@ -3,66 +3,315 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use crate::app::board_display::{BoardDisplay, ColorConfig};
+use crate::app::board_display::{
+    centering_offset, following_offset, paged_offset, scroll_label, BoardDisplay, CaptureArrows,
+    CaptureLines, ColorConfig, Fog, MoveDiff,
+};
 use crate::app::system::Play;
 use crate::board::{Board, Player};
 use crate::error::TriversiError;
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::cmp;
+use std::collections::HashMap;
 use tui::backend::Backend;
-use tui::layout::{Alignment, Rect};
+use tui::layout::Rect;
 use tui::style::{Modifier, Style};
 use tui::terminal::Frame;
 use tui::text::{Span, Spans};
 use tui::widgets::{Block, Borders, Paragraph};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-#[derive(Clone, Copy, Debug)]
-pub struct PlayerMark(char, char, char);
+/// Names of glyphs accepted as marks instead of ASCII characters, each of which occupies one cell.
+pub const MARK_GLYPHS: &[(&str, char)] = &[
+    ("circle", '●'),
+    ("square", '■'),
+    ("triangle", '▲'),
+    ("diamond", '◆'),
+    ("star", '★'),
+    ("heart", '♥'),
+];
+
+/// Widest mark, which takes two columns like a CJK character or an emoji.
+pub const MAX_MARK_WIDTH: usize = 2;
+
+/// Brackets enclosing the marks of each player with `--distinct-shapes`.
+pub const SHAPE_BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('<', '>')];
+
+/// Marks of players, each of which takes one or two columns, or two more with brackets.
+#[derive(Clone, Debug)]
+pub struct PlayerMark(String, String, String);
 
 impl PlayerMark {
-    // fn new(mark_0: char, mark_1: char, mark_2: char) -> Self {
-    //     Self(mark_0, mark_1, mark_2)
-    // }
-    fn convert(&self, player: Player) -> char {
+    /// Encloses the marks in `SHAPE_BRACKETS`, e.g., `(C)`, `[M]`, and `<Y>`,
+    /// which tell players apart without their colors.
+    pub fn with_shapes(self) -> Self {
+        let enclose = |mark: String, (open, close): (char, char)| format!("{open}{mark}{close}");
+        Self(
+            enclose(self.0, SHAPE_BRACKETS[0]),
+            enclose(self.1, SHAPE_BRACKETS[1]),
+            enclose(self.2, SHAPE_BRACKETS[2]),
+        )
+    }
+
+    pub fn convert(&self, player: Player) -> &str {
         match player {
-            Player::Zero => self.0,
-            Player::One => self.1,
-            Player::Two => self.2,
+            Player::Zero => &self.0,
+            Player::One => &self.1,
+            Player::Two => &self.2,
         }
     }
+
+    /// Number of columns of the widest mark.
+    pub fn width(&self) -> usize {
+        [&self.0, &self.1, &self.2]
+            .iter()
+            .map(|mark| mark.width())
+            .max()
+            .unwrap()
+    }
 }
 
 impl TryFrom<String> for PlayerMark {
     type Error = TriversiError;
+    /// Each mark is a name in `MARK_GLYPHS`, or a string of one or two columns, e.g., `()` or `██`,
+    /// whose characters take one or two columns each.
     fn try_from(s: String) -> Result<Self, Self::Error> {
-        let mark_list = s.split(',').collect::<Vec<_>>();
-        if mark_list.len() != 3
-            || mark_list.iter().any(|mark| mark.is_empty())
-            || mark_list
-                .iter()
-                .any(|mark| !mark.chars().next().unwrap().is_ascii())
-        {
-            return Err(TriversiError::InvalidStringForPlayerMarks(s));
+        let mut mark_list = Vec::new();
+        for mark in s.split(',') {
+            if let Some((_, glyph)) = MARK_GLYPHS.iter().find(|(name, _)| *name == mark) {
+                mark_list.push(glyph.to_string());
+                continue;
+            }
+            if mark.is_empty() || mark.chars().any(|c| c.width().unwrap_or(0) == 0) {
+                return Err(TriversiError::InvalidStringForPlayerMarks(s));
+            }
+            if mark.width() > MAX_MARK_WIDTH {
+                return Err(TriversiError::PlayerMarkTooWide(
+                    mark.to_owned(),
+                    mark.width(),
+                ));
+            }
+            mark_list.push(mark.to_owned());
+        }
+        match <[String; 3]>::try_from(mark_list) {
+            Ok([mark_0, mark_1, mark_2]) => Ok(Self(mark_0, mark_1, mark_2)),
+            Err(_) => Err(TriversiError::InvalidStringForPlayerMarks(s)),
+        }
+    }
+}
+
+/// Presets of characters of frames.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FrameStyleKind {
+    /// `-`, `/`, and `\`.
+    #[default]
+    Ascii,
+    /// Thin box-drawing lines `─`, `╱`, and `╲`.
+    Light,
+    /// Thick lines `━`, `⧸`, and `⧹`.
+    Heavy,
+}
+
+/// Characters of frames along the bottom, left, and right bonds of positions.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameStyle {
+    bottom: char,
+    left: char,
+    right: char,
+}
+
+impl From<FrameStyleKind> for FrameStyle {
+    fn from(kind: FrameStyleKind) -> Self {
+        let (bottom, left, right) = match kind {
+            FrameStyleKind::Ascii => ('-', '/', '\\'),
+            FrameStyleKind::Light => ('─', '╱', '╲'),
+            FrameStyleKind::Heavy => ('━', '⧸', '⧹'),
+        };
+        Self {
+            bottom,
+            left,
+            right,
+        }
+    }
+}
+
+impl FrameStyle {
+    /// Replaces characters by `frame_chars_str` like `-,/,\` of the bottom, left, and right frames,
+    /// each of which takes one column.
+    pub fn try_with_chars(self, frame_chars_str: &str) -> Result<Self, TriversiError> {
+        let chars = frame_chars_str
+            .split(',')
+            .map(|frame| {
+                let mut chars = frame.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.width() == Some(1) => Some(c),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<_>>>();
+        match chars.as_deref() {
+            Some(&[bottom, left, right]) => Ok(Self {
+                bottom,
+                left,
+                right,
+            }),
+            _ => Err(TriversiError::InvalidStringForFrameChars(
+                frame_chars_str.to_owned(),
+            )),
+        }
+    }
+}
+
+/// Character and style of a cell of the board block, where raw cells have the default style.
+type Cell = (char, Style);
+
+/// Character of the cell covered by the wide character on its left, which is not drawn.
+const COVERED: char = '\0';
+
+/// Cells in view of the board block, where `left` and `top` are the column and the row of the board
+/// shown at the top-left corner, which are negative if the board is shifted right or down.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Window {
+    left: i64,
+    top: i64,
+    width: usize,
+    height: usize,
+}
+
+impl Window {
+    fn contains(&self, (col, row): (usize, usize)) -> bool {
+        let (col, row) = (col as i64 - self.left, row as i64 - self.top);
+        (0..self.width as i64).contains(&col) && (0..self.height as i64).contains(&row)
+    }
+
+    /// Puts characters of `text` from the column and the row of the board,
+    /// where a wide character cut by the edge of the window is replaced by spaces.
+    fn put_str(
+        &self,
+        board_cells: &mut [Vec<Cell>],
+        (col, row): (usize, usize),
+        text: &str,
+        style: Style,
+    ) {
+        let mut col = col;
+        for c in text.chars() {
+            let width = c.width().unwrap_or(1);
+            if (col..col + width).all(|col| self.contains((col, row))) {
+                self.put(board_cells, (col, row), (c, style));
+                for covered in col + 1..col + width {
+                    self.put(board_cells, (covered, row), (COVERED, style));
+                }
+            } else {
+                for cut in col..col + width {
+                    self.put(board_cells, (cut, row), (' ', style));
+                }
+            }
+            col += width;
+        }
+    }
+
+    /// Puts `cell` at the column and the row of the board if it is in view.
+    fn put(&self, board_cells: &mut [Vec<Cell>], (col, row): (usize, usize), cell: Cell) {
+        if !self.contains((col, row)) {
+            return;
+        }
+        let (col, row) = (
+            (col as i64 - self.left) as usize,
+            (row as i64 - self.top) as usize,
+        );
+        if let Some(target) = board_cells.get_mut(row).and_then(|line| line.get_mut(col)) {
+            *target = cell;
+        }
+    }
+
+    /// Cell at the column and the row of the board, or `None` if it is out of view.
+    fn get(&self, board_cells: &[Vec<Cell>], (col, row): (usize, usize)) -> Option<Cell> {
+        if !self.contains((col, row)) {
+            return None;
+        }
+        let (col, row) = (
+            (col as i64 - self.left) as usize,
+            (row as i64 - self.top) as usize,
+        );
+        board_cells.get(row).and_then(|line| line.get(col)).copied()
+    }
+
+    /// Puts `cell` into the cells over the background by the column and the row of the board if it is in view.
+    fn put_over(
+        &self,
+        overlay: &mut HashMap<(usize, usize), Cell>,
+        (col, row): (usize, usize),
+        cell: Cell,
+    ) {
+        if self.contains((col, row)) {
+            overlay.insert((col, row), cell);
         }
-        Ok(Self(
-            mark_list.first().unwrap().chars().next().unwrap(),
-            mark_list.get(1).unwrap().chars().next().unwrap(),
-            mark_list.get(2).unwrap().chars().next().unwrap(),
-        ))
     }
 }
 
+/// Everything the background and the frames of the board block depend on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Layout {
+    window: Window,
+    range: usize,
+    distance: usize,
+    frame_visibility: bool,
+    upside_down: bool,
+    frame_style: Style,
+}
+
+/// Cells of the last frame in layers, so that the next frame rebuilds only the cells that changed.
+#[derive(Debug, Default)]
+struct BoardCells {
+    /// Layout of `base`, or `None` before the first frame.
+    layout: Option<Layout>,
+    /// Background and frames, which are made again only when the layout changes.
+    base: Vec<Vec<Cell>>,
+    /// Capture lines and arrows drawn over `base` by the column and the row of the board.
+    overlay: HashMap<(usize, usize), Cell>,
+    /// Occupant and style of each position drawn over the others.
+    marks: HashMap<(usize, usize), (Option<Player>, Style)>,
+    /// Cells of the last frame, which are `base` with `overlay` and `marks` drawn over it.
+    cells: Vec<Vec<Cell>>,
+}
+
 pub struct ParagraphBoard {
     distance: usize,
-    offset: (i16, i16),
+    offset: (i32, i32),
     player_mark: PlayerMark,
     player_name: (String, String, String),
+    frame_style: FrameStyle,
     frame_visibility: bool,
+    home_region_visibility: bool,
+    /// Whether the wide edge of the board is at the top, where the rows of cells are mirrored.
+    upside_down: bool,
+    /// Cells drawn in the last frame, which are restyled only where the next frame differs.
+    cells: RefCell<BoardCells>,
 }
 
 impl ParagraphBoard {
-    pub fn try_new(distance: usize, player_names_str: &str) -> Result<Self, TriversiError> {
+    /// Marks are read from `player_marks_str` separated by ',' as in `PlayerMark`,
+    /// which are enclosed in brackets if `distinct_shapes` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        distance: usize,
+        player_names_str: &str,
+        player_marks_str: &str,
+        distinct_shapes: bool,
+        frame_style: FrameStyle,
+        frame_visibility: bool,
+        home_region_visibility: bool,
+        upside_down: bool,
+    ) -> Result<Self, TriversiError> {
         let names = player_names_str.split(',').collect::<Vec<_>>();
-        let player_mark = PlayerMark::try_from(player_names_str.to_owned())?;
+        let mut player_mark = PlayerMark::try_from(player_marks_str.to_owned())?;
+        if distinct_shapes {
+            player_mark = player_mark.with_shapes();
+        }
+        Self::validate_distance(distance, &player_mark)?;
         if names.len() != 3 {
             return Err(TriversiError::InvalidStringForPlayerNames(
                 player_names_str.to_owned(),
@@ -77,16 +326,50 @@ impl ParagraphBoard {
                 names.get(1).unwrap().to_string(),
                 names.get(2).unwrap().to_string(),
             ),
-            frame_visibility: false,
+            frame_style,
+            frame_visibility,
+            home_region_visibility,
+            upside_down,
+            cells: RefCell::default(),
         })
     }
 
+    /// Checks that `distance` is at least `min_distance` of `player_mark`.
+    pub fn validate_distance(
+        distance: usize,
+        player_mark: &PlayerMark,
+    ) -> Result<(), TriversiError> {
+        let min_distance = Self::min_distance(player_mark);
+        if distance < min_distance {
+            return Err(TriversiError::DistanceTooSmallForMarks(
+                distance,
+                player_mark.width(),
+                min_distance,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Least distance keeping a column between marks and the frames next to them,
+    /// where the bottom frame from a mark ends two columns before the next position.
+    fn min_distance(player_mark: &PlayerMark) -> usize {
+        cmp::max(2, (player_mark.width() + 4) / 2)
+    }
+
     fn cell_position(&self, board: &Board, (x, y): (usize, usize)) -> (usize, usize) {
         let x_block = self.distance * (board.range() - y - 1) + x * self.distance * 2;
-        let y_block = self.distance * y;
+        let y_block = self.cell_row(board, self.distance * y);
         (x_block, y_block)
     }
 
+    /// Row of cells drawn for `row` of the board with the apex at the top.
+    fn cell_row(&self, board: &Board, row: usize) -> usize {
+        match self.upside_down {
+            true => self.board_height(board) - 1 - row,
+            false => row,
+        }
+    }
+
     fn cell_none(&self) -> char {
         ' '
     }
@@ -97,86 +380,110 @@ impl ParagraphBoard {
 
     fn cell_bottom_frame(&self) -> char {
         match self.frame_visibility {
-            true => '-',
+            true => self.frame_style.bottom,
             false => ' ',
         }
     }
 
+    /// Frame going down to the left from a position, which goes up to the left on the board turned upside down.
     fn cell_left_frame(&self) -> char {
-        match self.frame_visibility {
-            true => '/',
-            false => ' ',
+        match (self.frame_visibility, self.upside_down) {
+            (true, false) => self.frame_style.left,
+            (true, true) => self.frame_style.right,
+            (false, _) => ' ',
         }
     }
 
+    /// Frame going down to the right from a position, which goes up to the right on the board turned upside down.
     fn cell_right_frame(&self) -> char {
-        match self.frame_visibility {
-            true => '\\',
-            false => ' ',
+        match (self.frame_visibility, self.upside_down) {
+            (true, false) => self.frame_style.right,
+            (true, true) => self.frame_style.left,
+            (false, _) => ' ',
         }
     }
 
-    fn cell_player(&self, player: Option<Player>) -> char {
+    fn cell_player(&self, player: Option<Player>) -> &str {
         match player {
             Some(player) => self.player_mark.convert(player),
             None => match self.frame_visibility {
-                true => ' ',
-                false => '.',
+                true => " ",
+                false => ".",
             },
         }
     }
 
+    /// Number of rows of cells of the board, where positions are `distance` rows apart.
+    fn board_height(&self, board: &Board) -> usize {
+        self.distance * (board.range() - 1) + 1
+    }
+
+    /// Number of columns of cells of the board, where positions are `2 * distance` columns apart.
+    fn board_width(&self, board: &Board) -> usize {
+        2 * self.distance * (board.range() - 1) + 1
+    }
+
+    /// Fills `board_cells` with rows of the background in `window`, keeping the buffers of the rows.
     fn make_empty_board_cells(
         &self,
         board: &Board,
-        (net_offset_x, net_offset_y): (usize, usize),
-    ) -> Vec<Spans> {
-        let mut board_cells = Vec::new();
-        for _ in 0..net_offset_y {
-            board_cells.push(Spans::from(vec![Span::raw("")]));
-        }
-        for _ in 0..=self.distance * (board.range() - 1) {
-            let mut line =
-                Vec::with_capacity(net_offset_x + 2 * self.distance * (board.range() - 1) + 1);
-            line.extend(vec![
-                Span::raw(format!("{}", self.cell_none()));
-                net_offset_x
-            ]);
-            line.extend(vec![
-                Span::raw(format!("{}", self.cell_background()));
-                2 * self.distance * (board.range() - 1) + 1
-            ]);
-            board_cells.push(Spans::from(line))
+        window: &Window,
+        board_cells: &mut Vec<Vec<Cell>>,
+    ) {
+        let n_rows = (self.board_height(board) as i64 - window.top).clamp(0, window.height as i64);
+        board_cells.resize_with(n_rows as usize, Vec::new);
+        for (i_row, line) in board_cells.iter_mut().enumerate() {
+            line.clear();
+            // Rows above the board are left empty.
+            if window.top + (i_row as i64) < 0 {
+                continue;
+            }
+            line.extend((0..window.width).map(|i_col| {
+                let col = window.left + i_col as i64;
+                if (0..self.board_width(board) as i64).contains(&col) {
+                    (self.cell_background(), Style::default())
+                } else {
+                    (self.cell_none(), Style::default())
+                }
+            }));
+        }
+    }
+
+    /// Rows of positions whose cells or the frames next to them may be in `window`.
+    fn position_rows_in_view(&self, board: &Board, window: &Window) -> std::ops::Range<usize> {
+        let distance = self.distance as i64;
+        let first = (window.top.div_euclid(distance) - 1).max(0);
+        let last = ((window.top + window.height as i64).div_euclid(distance) + 1)
+            .min(board.range() as i64);
+        let (first, last) = (first as usize, cmp::max(first, last) as usize);
+        // Rows of cells are counted from the wide edge on the board turned upside down.
+        match self.upside_down {
+            true => board.range() - last..board.range() - first,
+            false => first..last,
         }
-        board_cells
     }
 
     fn put_bottom_frame(
         &self,
         board: &Board,
-        (net_offset_x, net_offset_y): (usize, usize),
-        board_cells: &mut [Spans],
+        window: &Window,
+        color_config: ColorConfig,
+        board_cells: &mut [Vec<Cell>],
     ) {
-        for (i_row, row) in board_cells
-            .iter_mut()
-            .skip(net_offset_y + self.distance)
-            .step_by(self.distance)
-            .enumerate()
-        {
-            for offset_in_board in 1..=2 * self.distance - 3 {
-                for cell in row
-                    .0
-                    .iter_mut()
-                    .skip(
-                        net_offset_x
-                            + self.distance * (board.range() - i_row - 2)
-                            + offset_in_board
-                            + 1,
-                    )
-                    .step_by(2 * self.distance)
-                    .take(i_row + 1)
-                {
-                    *cell = Span::raw(format!("{}", self.cell_bottom_frame()));
+        let cell = (self.cell_bottom_frame(), color_config.frame_style());
+        for i_row in self.position_rows_in_view(board, window) {
+            if i_row + 1 >= board.range() {
+                continue;
+            }
+            let row = self.cell_row(board, self.distance * (i_row + 1));
+            // A column is left after the marks, which are as wide as `PlayerMark::width`.
+            for offset_in_board in self.player_mark.width()..=2 * self.distance - 3 {
+                for i_col in 0..=i_row {
+                    let col = self.distance * (board.range() - i_row - 2)
+                        + offset_in_board
+                        + 1
+                        + 2 * self.distance * i_col;
+                    window.put(board_cells, (col, row), cell);
                 }
             }
         }
@@ -185,27 +492,21 @@ impl ParagraphBoard {
     fn put_left_frame(
         &self,
         board: &Board,
-        (net_offset_x, net_offset_y): (usize, usize),
-        board_cells: &mut [Spans],
+        window: &Window,
+        color_config: ColorConfig,
+        board_cells: &mut [Vec<Cell>],
     ) {
-        for offset_in_board in 1..=(self.distance - 1) {
-            for (i_row, row) in board_cells
-                .iter_mut()
-                .skip(net_offset_y + offset_in_board)
-                .step_by(self.distance)
-                .enumerate()
-            {
-                for cell in row
-                    .0
-                    .iter_mut()
-                    .skip(
-                        net_offset_x + self.distance * (board.range() - i_row - 1)
-                            - offset_in_board,
-                    )
-                    .step_by(2 * self.distance)
-                    .take(i_row + 1)
-                {
-                    *cell = Span::raw(format!("{}", self.cell_left_frame()));
+        let cell = (self.cell_left_frame(), color_config.frame_style());
+        for i_row in self.position_rows_in_view(board, window) {
+            if i_row + 1 >= board.range() {
+                continue;
+            }
+            for offset_in_board in 1..=(self.distance - 1) {
+                let row = self.cell_row(board, self.distance * i_row + offset_in_board);
+                for i_col in 0..=i_row {
+                    let col = self.distance * (board.range() - i_row - 1) - offset_in_board
+                        + 2 * self.distance * i_col;
+                    window.put(board_cells, (col, row), cell);
                 }
             }
         }
@@ -214,28 +515,22 @@ impl ParagraphBoard {
     fn put_right_frame(
         &self,
         board: &Board,
-        (net_offset_x, net_offset_y): (usize, usize),
-        board_cells: &mut [Spans],
+        window: &Window,
+        color_config: ColorConfig,
+        board_cells: &mut [Vec<Cell>],
     ) {
-        for offset_in_board in 1..=(self.distance - 1) {
-            for (i_row, row) in board_cells
-                .iter_mut()
-                .skip(net_offset_y + offset_in_board)
-                .step_by(self.distance)
-                .enumerate()
-            {
-                for cell in row
-                    .0
-                    .iter_mut()
-                    .skip(
-                        net_offset_x
-                            + self.distance * (board.range() - i_row - 1)
-                            + offset_in_board,
-                    )
-                    .step_by(2 * self.distance)
-                    .take(i_row + 1)
-                {
-                    *cell = Span::raw(format!("{}", self.cell_right_frame()));
+        let cell = (self.cell_right_frame(), color_config.frame_style());
+        for i_row in self.position_rows_in_view(board, window) {
+            if i_row + 1 >= board.range() {
+                continue;
+            }
+            for offset_in_board in 1..=(self.distance - 1) {
+                let row = self.cell_row(board, self.distance * i_row + offset_in_board);
+                for i_col in 0..=i_row {
+                    let col = self.distance * (board.range() - i_row - 1)
+                        + offset_in_board
+                        + 2 * self.distance * i_col;
+                    window.put(board_cells, (col, row), cell);
                 }
             }
         }
@@ -245,70 +540,244 @@ impl ParagraphBoard {
     fn put_player(
         &self,
         board: &Board,
-        (net_offset_x, net_offset_y): (usize, usize),
+        window: &Window,
         net_scroll: (usize, usize),
         color_config: ColorConfig,
         current_player: Player,
         current_position: (usize, usize),
-        board_cells: &mut [Spans],
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+        board_cells: &mut BoardCells,
     ) {
-        for (i_row, row) in board_cells
-            .iter_mut()
-            .skip(net_offset_y)
-            .step_by(self.distance)
-            .enumerate()
-        {
-            for (i_col, cell) in row
-                .0
-                .iter_mut()
-                .skip(net_offset_x + self.distance * (board.range() - i_row - 1))
-                .step_by(self.distance * 2)
-                .take(i_row + 1)
-                .enumerate()
-            {
+        for i_row in self.position_rows_in_view(board, window) {
+            for i_col in 0..=i_row {
+                let cell_position = self.cell_position(board, (i_col, i_row));
+                if !window.contains(cell_position) {
+                    continue;
+                }
                 let player = board.player((i_col, i_row));
-                *cell = Span::styled(
-                    format!("{}", self.cell_player(player)),
-                    self.make_player_style(
-                        board,
-                        net_scroll,
-                        color_config,
-                        current_player,
-                        current_position,
-                        player,
-                        (i_col, i_row),
-                    ),
+                let style = self.make_player_style(
+                    board,
+                    net_scroll,
+                    color_config,
+                    current_player,
+                    current_position,
+                    move_diff,
+                    fog,
+                    player,
+                    (i_col, i_row),
                 );
+                if board_cells.marks.get(&(i_col, i_row)) == Some(&(player, style)) {
+                    continue;
+                }
+                // Marks differ in width, so the cells of the widest mark are restored first.
+                let (col, row) = cell_position;
+                for col in col..col + self.player_mark.width() {
+                    let cell = board_cells
+                        .overlay
+                        .get(&(col, row))
+                        .copied()
+                        .or_else(|| window.get(&board_cells.base, (col, row)));
+                    if let Some(cell) = cell {
+                        window.put(&mut board_cells.cells, (col, row), cell);
+                    }
+                }
+                window.put_str(
+                    &mut board_cells.cells,
+                    cell_position,
+                    self.cell_player(player),
+                    style,
+                );
+                board_cells.marks.insert((i_col, i_row), (player, style));
+            }
+        }
+    }
+
+    /// Puts faint bonds between the positions of each capture line, which are drawn over the frames
+    /// and under the marks.
+    fn put_capture_lines(
+        &self,
+        board: &Board,
+        window: &Window,
+        (net_scroll_x, _): (usize, usize),
+        color_config: ColorConfig,
+        capture_lines: &CaptureLines,
+        overlay: &mut HashMap<(usize, usize), Cell>,
+    ) {
+        let style = color_config
+            .player_style(capture_lines.player)
+            .add_modifier(Modifier::DIM);
+        for line in &capture_lines.lines {
+            for pair in line.windows(2) {
+                let (col_0, row_0) = self.cell_position(board, pair[0]);
+                let (col_1, row_1) = self.cell_position(board, pair[1]);
+                let (d_col, d_row) = (col_1 as i64 - col_0 as i64, row_1 as i64 - row_0 as i64);
+                let bond = match (d_col.signum() * d_row.signum(), d_row) {
+                    (_, 0) => '-',
+                    (1, _) => '\\',
+                    _ => '/',
+                };
+                let n_steps = cmp::max(d_col.abs(), d_row.abs());
+                for i_step in 1..n_steps {
+                    let col = (col_0 as i64 + d_col * i_step / n_steps) as usize;
+                    let row = (row_0 as i64 + d_row * i_step / n_steps) as usize;
+                    let cell = if col >= net_scroll_x {
+                        (bond, style)
+                    } else {
+                        (bond, Style::default())
+                    };
+                    window.put_over(overlay, (col, row), cell);
+                }
+            }
+        }
+    }
+
+    /// Puts an arrow next to the position of `capture_arrows` toward each direction it captures,
+    /// which is drawn over the capture lines and omitted if the positions are too close to leave a cell between them.
+    fn put_capture_arrows(
+        &self,
+        board: &Board,
+        window: &Window,
+        color_config: ColorConfig,
+        capture_arrows: &CaptureArrows,
+        overlay: &mut HashMap<(usize, usize), Cell>,
+    ) {
+        let style = color_config
+            .player_style(capture_arrows.player)
+            .add_modifier(Modifier::BOLD);
+        let (col_0, row_0) = self.cell_position(board, capture_arrows.position);
+        for direction in &capture_arrows.directions {
+            let Some(neighbor) = board.neighbor(capture_arrows.position, *direction) else {
+                continue;
+            };
+            let (col_1, row_1) = self.cell_position(board, neighbor);
+            let (d_col, d_row) = (col_1 as i64 - col_0 as i64, row_1 as i64 - row_0 as i64);
+            let n_steps = cmp::max(d_col.abs(), d_row.abs());
+            if n_steps < 2 {
+                continue;
             }
+            let arrow = match (d_col.signum(), d_row.signum()) {
+                (-1, 0) => '←',
+                (1, 0) => '→',
+                (-1, -1) => '↖',
+                (1, -1) => '↗',
+                (-1, 1) => '↙',
+                (1, 1) => '↘',
+                (0, -1) => '↑',
+                _ => '↓',
+            };
+            let col = (col_0 as i64 + d_col / n_steps) as usize;
+            let row = (row_0 as i64 + d_row / n_steps) as usize;
+            window.put_over(overlay, (col, row), (arrow, style));
         }
     }
 
+    /// Rows of cells in view of the board block whose inner size is `size`,
+    /// where only the cells in view are made even if the board is much larger,
+    /// and only those differing from the last frame are made again.
+    #[allow(clippy::too_many_arguments)]
     fn make_board_cells(
         &self,
         board: &Board,
         net_scroll: (usize, usize),
+        size: (u16, u16),
         color_config: ColorConfig,
         current_player: Player,
         current_position: (usize, usize),
-    ) -> Vec<Spans> {
-        let net_offset = (
-            cmp::max(0, self.offset.0 * self.distance as i16) as usize,
-            cmp::max(0, self.offset.1 * self.distance as i16) as usize,
-        );
-        let mut board_cells = self.make_empty_board_cells(board, net_offset);
-        self.put_bottom_frame(board, net_offset, &mut board_cells);
-        self.put_left_frame(board, net_offset, &mut board_cells);
-        self.put_right_frame(board, net_offset, &mut board_cells);
+        capture_lines: Option<&CaptureLines>,
+        capture_arrows: Option<&CaptureArrows>,
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) -> Vec<Spans<'static>> {
+        let distance = self.distance as i64;
+        let window = Window {
+            left: net_scroll.0 as i64 - cmp::max(0, self.offset.0 as i64 * distance),
+            top: net_scroll.1 as i64 - cmp::max(0, self.offset.1 as i64 * distance),
+            width: size.0 as usize,
+            height: size.1 as usize,
+        };
+        let layout = Layout {
+            window,
+            range: board.range(),
+            distance: self.distance,
+            frame_visibility: self.frame_visibility,
+            upside_down: self.upside_down,
+            frame_style: color_config.frame_style(),
+        };
+        let mut board_cells = self.cells.borrow_mut();
+        let board_cells = &mut *board_cells;
+        if board_cells.layout != Some(layout) {
+            let base = &mut board_cells.base;
+            self.make_empty_board_cells(board, &window, base);
+            self.put_bottom_frame(board, &window, color_config, base);
+            self.put_left_frame(board, &window, color_config, base);
+            self.put_right_frame(board, &window, color_config, base);
+            board_cells.cells.clone_from(base);
+            board_cells.overlay.clear();
+            board_cells.marks.clear();
+            board_cells.layout = Some(layout);
+        }
+        let mut overlay = HashMap::new();
+        if let Some(capture_lines) = capture_lines {
+            self.put_capture_lines(
+                board,
+                &window,
+                net_scroll,
+                color_config,
+                capture_lines,
+                &mut overlay,
+            );
+        }
+        if let Some(capture_arrows) = capture_arrows {
+            self.put_capture_arrows(board, &window, color_config, capture_arrows, &mut overlay);
+        }
+        if overlay != board_cells.overlay {
+            for position in board_cells.overlay.keys() {
+                if let Some(cell) = window.get(&board_cells.base, *position) {
+                    window.put(&mut board_cells.cells, *position, cell);
+                }
+            }
+            for (position, cell) in &overlay {
+                window.put(&mut board_cells.cells, *position, *cell);
+            }
+            board_cells.overlay = overlay;
+            // Marks are drawn over the overlay, which may have covered or uncovered their cells.
+            board_cells.marks.clear();
+        }
         self.put_player(
             board,
-            net_offset,
+            &window,
             net_scroll,
             color_config,
             current_player,
             current_position,
-            &mut board_cells,
+            move_diff,
+            fog,
+            board_cells,
         );
         board_cells
+            .cells
+            .iter()
+            .map(|row| Self::make_spans(row))
+            .collect()
+    }
+
+    /// Spans of a row, one for each run of cells in the same style.
+    fn make_spans(row: &[Cell]) -> Spans<'static> {
+        let mut spans = Vec::new();
+        let mut text = String::new();
+        let mut run_style = None;
+        for (c, style) in row.iter().filter(|(c, _)| *c != COVERED) {
+            if run_style.is_some_and(|run_style| run_style != *style) {
+                spans.push(Span::styled(std::mem::take(&mut text), run_style.unwrap()));
+            }
+            run_style = Some(*style);
+            text.push(*c);
+        }
+        if let Some(run_style) = run_style {
+            spans.push(Span::styled(text, run_style));
+        }
+        Spans::from(spans)
     }
 
     fn make_boarder_style(
@@ -319,10 +788,13 @@ impl ParagraphBoard {
     ) -> Style {
         let mut boarder_style_of_board = Style::default();
         match play {
+            _ if color_config.alert_border() => {
+                boarder_style_of_board = boarder_style_of_board.fg(color_config.error_color())
+            }
             Play::Finished | Play::History => (),
             _ => {
                 boarder_style_of_board =
-                    boarder_style_of_board.fg(color_config.player(current_player))
+                    boarder_style_of_board.patch(color_config.player_style(current_player))
             }
         }
         boarder_style_of_board
@@ -336,19 +808,32 @@ impl ParagraphBoard {
         color_config: ColorConfig,
         current_player: Player,
         current_position: (usize, usize),
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
         player: Option<Player>,
         position: (usize, usize),
     ) -> Style {
         let mut style = Style::default();
         if self.cell_position(board, position).0 as i64 - net_scroll_x as i64 >= 0 {
+            if self.home_region_visibility {
+                if let Some(home_player) = board.home_player(position) {
+                    style = style.bg(color_config.home_background(home_player));
+                }
+            }
             if let Some(player) = player {
-                style = style.fg(color_config.player(player));
-                if player == current_player {
-                    style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                style = style.patch(color_config.player_style(player));
+                if let Some(fog) = fog {
+                    style = fog.patch_style(style, player, position, color_config);
+                }
+                if player == current_player && move_diff.is_none() {
+                    style = style.add_modifier(color_config.current_player_modifier());
                 }
             }
+            if let Some(move_diff) = move_diff {
+                style = move_diff.patch_style(style, position, color_config);
+            }
             if current_position == position {
-                style = style.add_modifier(Modifier::REVERSED);
+                style = color_config.cursor_style(style, player.is_some());
             }
         }
         style
@@ -366,6 +851,10 @@ impl BoardDisplay for ParagraphBoard {
         }
     }
 
+    fn player_mark(&self, player: Player) -> String {
+        self.player_mark.convert(player).to_owned()
+    }
+
     fn scroll_left(&mut self) {
         self.offset.0 += 1
     }
@@ -386,6 +875,44 @@ impl BoardDisplay for ParagraphBoard {
         self.offset = (0, 0)
     }
 
+    fn center_on(&mut self, board: &Board, position: (usize, usize), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let (col, row) = self.cell_position(board, position);
+        self.offset = (
+            centering_offset(col, self.board_width(board), inner.width, self.distance),
+            centering_offset(row, self.board_height(board), inner.height, self.distance),
+        );
+    }
+
+    fn scroll_into_view(&mut self, board: &Board, position: (usize, usize), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let (col, row) = self.cell_position(board, position);
+        self.offset = (
+            following_offset(self.offset.0, col, inner.width, self.distance),
+            following_offset(self.offset.1, row, inner.height, self.distance),
+        );
+    }
+
+    fn scroll_page(&mut self, board: &Board, (pages_x, pages_y): (i32, i32), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        self.offset = (
+            paged_offset(
+                self.offset.0,
+                pages_x,
+                self.board_width(board),
+                inner.width,
+                self.distance,
+            ),
+            paged_offset(
+                self.offset.1,
+                pages_y,
+                self.board_height(board),
+                inner.height,
+                self.distance,
+            ),
+        );
+    }
+
     fn zoom_in(&mut self) {
         if self.distance < Self::MAX_DISTANCE {
             self.distance += 1;
@@ -393,7 +920,7 @@ impl BoardDisplay for ParagraphBoard {
     }
 
     fn zoom_out(&mut self) {
-        if self.distance > 2 {
+        if self.distance > Self::min_distance(&self.player_mark) {
             self.distance -= 1;
         }
     }
@@ -402,53 +929,298 @@ impl BoardDisplay for ParagraphBoard {
         self.frame_visibility ^= true;
     }
 
-    fn render_scroll_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect) {
-        frame.render_widget(
-            Paragraph::new(format!("{}, {}", self.offset.0, self.offset.1))
-                .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).title("Scroll")),
-            rect,
-        );
+    fn toggle_home_region_visibility(&mut self) {
+        self.home_region_visibility ^= true;
     }
 
-    fn render_zoom_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect) {
-        frame.render_widget(
-            Paragraph::new(format!("{}", self.distance))
-                .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).title("Zoom")),
-            rect,
+    fn toggle_upside_down(&mut self) -> bool {
+        self.upside_down ^= true;
+        true
+    }
+
+    fn is_upside_down(&self) -> bool {
+        self.upside_down
+    }
+
+    fn min_board_block_size(&self) -> (u16, u16) {
+        (2 * self.distance as u16 + 3, self.distance as u16 + 3)
+    }
+
+    fn scroll_text(&self, board: &Board, board_rect: Rect) -> String {
+        let inner = Block::default().borders(Borders::ALL).inner(board_rect);
+        let label_x = scroll_label(
+            self.offset.0,
+            self.board_width(board),
+            inner.width,
+            self.distance,
+        );
+        let label_y = scroll_label(
+            self.offset.1,
+            self.board_height(board),
+            inner.height,
+            self.distance,
         );
+        format!("{}, {}", label_x, label_y)
+    }
+
+    fn zoom_level(&self) -> usize {
+        self.distance
     }
 
     fn render_board_block<B: Backend>(
         &self,
         frame: &mut Frame<B>,
         rect: Rect,
+        title: &str,
         board: &Board,
         color_config: ColorConfig,
         play: Play,
         current_player: Player,
         current_position: (usize, usize),
+        capture_lines: Option<&CaptureLines>,
+        capture_arrows: Option<&CaptureArrows>,
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
     ) {
-        let net_scroll_x = cmp::max(0, -self.offset.0 * self.distance as i16) as u16;
-        let net_scroll_y = cmp::max(0, -self.offset.1 * self.distance as i16) as u16;
+        let distance = self.distance as i64;
+        let net_scroll_x = cmp::max(0, -self.offset.0 as i64 * distance) as usize;
+        let net_scroll_y = cmp::max(0, -self.offset.1 as i64 * distance) as usize;
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
         let board_cells = self.make_board_cells(
             board,
-            (net_scroll_x as usize, net_scroll_y as usize),
+            (net_scroll_x, net_scroll_y),
+            (inner.width, inner.height),
             color_config,
             current_player,
             current_position,
+            capture_lines,
+            capture_arrows,
+            move_diff,
+            fog,
         );
         frame.render_widget(
-            Paragraph::new(board_cells)
-                .scroll((net_scroll_y, net_scroll_x))
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title("Board")
-                        .border_style(self.make_boarder_style(color_config, play, current_player)),
-                ),
+            Paragraph::new(board_cells).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(self.make_boarder_style(color_config, play, current_player)),
+            ),
             rect,
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::DIRECTIONS;
+    use crate::rng;
+    use rand::Rng;
+
+    /// Board display with the settings of `paragraph_board` and no cells of a last frame.
+    fn fresh(paragraph_board: &ParagraphBoard) -> ParagraphBoard {
+        ParagraphBoard {
+            distance: paragraph_board.distance,
+            offset: paragraph_board.offset,
+            player_mark: paragraph_board.player_mark.clone(),
+            player_name: paragraph_board.player_name.clone(),
+            frame_style: paragraph_board.frame_style,
+            frame_visibility: paragraph_board.frame_visibility,
+            home_region_visibility: paragraph_board.home_region_visibility,
+            upside_down: paragraph_board.upside_down,
+            cells: RefCell::default(),
+        }
+    }
+
+    #[test]
+    fn restyled_cells_equal_cells_made_from_scratch() {
+        let mut rng = rng::seeded(0);
+        let mut paragraph_board = ParagraphBoard::try_new(
+            3,
+            "Cyan,Magenta,Yellow",
+            "a,bb,c",
+            false,
+            FrameStyleKind::Ascii.into(),
+            true,
+            true,
+            false,
+        )
+        .unwrap();
+        let color_config = ColorConfig::default();
+        let mut board = Board::try_new(8).unwrap();
+        let mut player = Player::default();
+        let mut position = board.initial_position();
+        let mut capture_lines = None;
+        let mut capture_arrows = None;
+        for step in 0..300 {
+            match rng.gen_range(0..10) {
+                0..=2 => {
+                    let positions = board.selectable_positions(player);
+                    if !positions.is_empty() {
+                        let placed = positions[rng.gen_range(0..positions.len())];
+                        let flips = board.flips(player, placed);
+                        board.apply_move(player, placed);
+                        capture_lines = Some(CaptureLines {
+                            player,
+                            lines: flips.iter().map(|flip| vec![placed, *flip]).collect(),
+                        });
+                    }
+                    player.advance();
+                }
+                3..=4 => {
+                    let y = rng.gen_range(0..board.range());
+                    position = (rng.gen_range(0..=y), y);
+                    capture_arrows = rng.gen_bool(0.5).then(|| CaptureArrows {
+                        player,
+                        position,
+                        directions: DIRECTIONS.to_vec(),
+                    });
+                }
+                5 => capture_lines = None,
+                6 => paragraph_board.offset = (rng.gen_range(-3..=1), rng.gen_range(-3..=1)),
+                7 => paragraph_board.toggle_frame_visibility(),
+                8 => paragraph_board.toggle_home_region_visibility(),
+                _ => paragraph_board.distance = rng.gen_range(3..=5),
+            }
+            let make = |paragraph_board: &ParagraphBoard| {
+                paragraph_board.make_board_cells(
+                    &board,
+                    (0, 0),
+                    (60, 30),
+                    color_config,
+                    player,
+                    position,
+                    capture_lines.as_ref(),
+                    capture_arrows.as_ref(),
+                    None,
+                    None,
+                )
+            };
+            assert_eq!(
+                make(&paragraph_board),
+                make(&fresh(&paragraph_board)),
+                "step {}",
+                step
+            );
+        }
+    }
+
+    /// Rows of `cells` followed by a row of letters naming the style of each character, and the
+    /// styles named by the letters.
+    fn dump(cells: &[Spans]) -> String {
+        let mut styles: Vec<Style> = Vec::new();
+        let mut text = String::new();
+        for spans in cells {
+            let mut letters = String::new();
+            for span in &spans.0 {
+                text.push_str(&span.content);
+                let i_style = match styles.iter().position(|style| *style == span.style) {
+                    Some(i_style) => i_style,
+                    None => {
+                        styles.push(span.style);
+                        styles.len() - 1
+                    }
+                };
+                let letter = char::from(b'a' + i_style as u8);
+                letters.extend(span.content.chars().map(|_| letter));
+            }
+            text.push('\n');
+            text.push_str(&letters);
+            text.push('\n');
+        }
+        for (i_style, style) in styles.iter().enumerate() {
+            text.push_str(&format!(
+                "{}: {:?}\n",
+                char::from(b'a' + i_style as u8),
+                style
+            ));
+        }
+        text
+    }
+
+    /// Frames drawn one after another by the same board display, as `(name, snapshot)`.
+    /// The snapshots were taken with the renderer that made every frame from scratch.
+    const SNAPSHOTS: &[(&str, &str)] = &[
+        ("plain", include_str!("snapshots/plain.txt")),
+        ("scrolled", include_str!("snapshots/scrolled.txt")),
+        ("upside_down", include_str!("snapshots/upside_down.txt")),
+        ("move_diff", include_str!("snapshots/move_diff.txt")),
+        ("fog", include_str!("snapshots/fog.txt")),
+    ];
+
+    #[test]
+    fn cells_equal_snapshots_of_the_renderer_without_restyling() {
+        let mut paragraph_board = ParagraphBoard::try_new(
+            3,
+            "Cyan,Magenta,Yellow",
+            "a,bb,c",
+            false,
+            FrameStyleKind::Ascii.into(),
+            true,
+            true,
+            false,
+        )
+        .unwrap();
+        let color_config = ColorConfig::default();
+        let mut board = Board::try_new(6).unwrap();
+        let mut player = Player::default();
+        let mut last_move = None;
+        for _ in 0..6 {
+            let placed = board.selectable_positions(player)[0];
+            let before = board.clone();
+            let flips = board.flips(player, placed);
+            board.apply_move(player, placed);
+            last_move = Some((before, placed, flips, player));
+            player.advance();
+        }
+        let (before, placed, flips, mover) = last_move.unwrap();
+        let capture_lines = CaptureLines {
+            player: mover,
+            lines: flips.iter().map(|flip| vec![placed, *flip]).collect(),
+        };
+        let capture_arrows = CaptureArrows {
+            player,
+            position: (1, 3),
+            directions: DIRECTIONS.to_vec(),
+        };
+        let move_diff = MoveDiff::new(&before, &board, placed);
+        let fog = Fog {
+            player,
+            capturable: board
+                .selectable_positions(player)
+                .iter()
+                .flat_map(|position| board.flips(player, *position))
+                .collect(),
+        };
+        for (name, snapshot) in SNAPSHOTS {
+            let mut net_scroll = (0, 0);
+            let mut move_diff_shown = None;
+            let mut fog_shown = None;
+            match *name {
+                "scrolled" => net_scroll = (4, 3),
+                "upside_down" => {
+                    paragraph_board.toggle_upside_down();
+                }
+                "move_diff" => {
+                    paragraph_board.toggle_upside_down();
+                    move_diff_shown = Some(&move_diff);
+                }
+                "fog" => fog_shown = Some(&fog),
+                _ => {}
+            }
+            let cells = paragraph_board.make_board_cells(
+                &board,
+                net_scroll,
+                (40, 20),
+                color_config,
+                player,
+                (1, 3),
+                Some(&capture_lines),
+                Some(&capture_arrows),
+                move_diff_shown,
+                fog_shown,
+            );
+            assert_eq!(dump(&cells), *snapshot, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,433 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Board display using Unicode stones and box-drawing bonds.
+//!
+//! Glyphs such as `●` may occupy two columns depending on the font,
+//! so every cell of the lattice is padded to the widest glyph in use.
+
+use crate::app::board_display::{
+    centering_offset, following_offset, paged_offset, scroll_label, BoardDisplay, CaptureArrows,
+    CaptureLines, ColorConfig, Fog, MoveDiff,
+};
+use crate::app::system::Play;
+use crate::board::{Board, Player};
+use crate::error::TriversiError;
+use tui::backend::Backend;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::terminal::Frame;
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph};
+use unicode_width::UnicodeWidthChar;
+
+const BOTTOM_FRAME: char = '─';
+const LEFT_FRAME: char = '╱';
+const RIGHT_FRAME: char = '╲';
+const EMPTY_CELL: char = '·';
+
+#[derive(Clone, Copy, Debug)]
+pub struct StoneMark(char, char, char);
+
+impl StoneMark {
+    fn convert(&self, player: Player) -> char {
+        match player {
+            Player::Zero => self.0,
+            Player::One => self.1,
+            Player::Two => self.2,
+        }
+    }
+}
+
+impl TryFrom<String> for StoneMark {
+    type Error = TriversiError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let stone_list = s.split(',').collect::<Vec<_>>();
+        if stone_list.len() != 3
+            || stone_list.iter().any(|stone| stone.chars().count() != 1)
+            || stone_list
+                .iter()
+                .any(|stone| !matches!(stone.chars().next().unwrap().width(), Some(1) | Some(2)))
+        {
+            return Err(TriversiError::InvalidStringForStoneMarks(s));
+        }
+        Ok(Self(
+            stone_list.first().unwrap().chars().next().unwrap(),
+            stone_list.get(1).unwrap().chars().next().unwrap(),
+            stone_list.get(2).unwrap().chars().next().unwrap(),
+        ))
+    }
+}
+
+/// What is drawn in one cell of the lattice.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Cell {
+    Background,
+    Stone((usize, usize)),
+    BottomFrame,
+    LeftFrame,
+    RightFrame,
+}
+
+pub struct UnicodeBoard {
+    distance: usize,
+    offset: (i32, i32),
+    stone_mark: StoneMark,
+    player_name: (String, String, String),
+    frame_visibility: bool,
+    home_region_visibility: bool,
+}
+
+impl UnicodeBoard {
+    pub fn try_new(
+        distance: usize,
+        player_names_str: &str,
+        stone_marks_str: &str,
+        frame_visibility: bool,
+        home_region_visibility: bool,
+    ) -> Result<Self, TriversiError> {
+        let names = player_names_str.split(',').collect::<Vec<_>>();
+        if names.len() != 3 {
+            return Err(TriversiError::InvalidStringForPlayerNames(
+                player_names_str.to_owned(),
+            ));
+        }
+        Ok(Self {
+            distance,
+            offset: (0, 0),
+            stone_mark: StoneMark::try_from(stone_marks_str.to_owned())?,
+            player_name: (
+                names.first().unwrap().to_string(),
+                names.get(1).unwrap().to_string(),
+                names.get(2).unwrap().to_string(),
+            ),
+            frame_visibility,
+            home_region_visibility,
+        })
+    }
+
+    /// Number of terminal columns used by one cell of the lattice.
+    fn cell_width(&self) -> usize {
+        [
+            self.stone_mark.0,
+            self.stone_mark.1,
+            self.stone_mark.2,
+            BOTTOM_FRAME,
+            LEFT_FRAME,
+            RIGHT_FRAME,
+            EMPTY_CELL,
+        ]
+        .iter()
+        .map(|c| c.width().unwrap_or(1))
+        .max()
+        .unwrap()
+    }
+
+    fn lattice_size(&self, board: &Board) -> (usize, usize) {
+        (
+            2 * self.distance * (board.range() - 1) + 1,
+            self.distance * (board.range() - 1) + 1,
+        )
+    }
+
+    /// Classifies a cell of the lattice, where `(col, row)` are lattice coordinates.
+    fn cell(&self, board: &Board, (col, row): (usize, usize)) -> Cell {
+        let (width, height) = self.lattice_size(board);
+        if col >= width || row >= height {
+            return Cell::Background;
+        }
+        let y = row / self.distance;
+        let offset_in_board = row % self.distance;
+        // Column of the leftmost stone of the row `y`.
+        let left_edge = self.distance * (board.range() - y - 1);
+        if offset_in_board == 0 {
+            if col < left_edge {
+                return Cell::Background;
+            }
+            let x = (col - left_edge) / (2 * self.distance);
+            let offset_in_row = (col - left_edge) % (2 * self.distance);
+            if x > y {
+                Cell::Background
+            } else if offset_in_row == 0 {
+                Cell::Stone((x, y))
+            } else if x < y && 1 < offset_in_row && offset_in_row < 2 * self.distance - 1 {
+                Cell::BottomFrame
+            } else {
+                Cell::Background
+            }
+        } else {
+            // Between the rows `y` and `y + 1`.
+            if col + offset_in_board < left_edge {
+                return Cell::Background;
+            }
+            let left_col = col + offset_in_board - left_edge;
+            if left_col.is_multiple_of(2 * self.distance) && left_col / (2 * self.distance) <= y {
+                return Cell::LeftFrame;
+            }
+            if col >= left_edge + offset_in_board {
+                let right_col = col - offset_in_board - left_edge;
+                if right_col.is_multiple_of(2 * self.distance)
+                    && right_col / (2 * self.distance) <= y
+                {
+                    return Cell::RightFrame;
+                }
+            }
+            Cell::Background
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn make_span(
+        &self,
+        board: &Board,
+        cell: Cell,
+        color_config: ColorConfig,
+        current_player: Player,
+        current_position: (usize, usize),
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) -> Span<'static> {
+        let frame_style = color_config.frame_style();
+        let (glyph, style) = match cell {
+            Cell::Background => (' ', Style::default()),
+            Cell::BottomFrame if self.frame_visibility => (BOTTOM_FRAME, frame_style),
+            Cell::LeftFrame if self.frame_visibility => (LEFT_FRAME, frame_style),
+            Cell::RightFrame if self.frame_visibility => (RIGHT_FRAME, frame_style),
+            Cell::BottomFrame | Cell::LeftFrame | Cell::RightFrame => (' ', Style::default()),
+            Cell::Stone(position) => {
+                let player = board.player(position);
+                let mut style = Style::default();
+                if self.home_region_visibility {
+                    if let Some(home_player) = board.home_player(position) {
+                        style = style.bg(color_config.home_background(home_player));
+                    }
+                }
+                let glyph = match player {
+                    Some(player) => {
+                        style = style.patch(color_config.player_style(player));
+                        if let Some(fog) = fog {
+                            style = fog.patch_style(style, player, position, color_config);
+                        }
+                        if player == current_player && move_diff.is_none() {
+                            style = style.add_modifier(color_config.current_player_modifier());
+                        }
+                        self.stone_mark.convert(player)
+                    }
+                    None => match self.frame_visibility {
+                        true => ' ',
+                        false => EMPTY_CELL,
+                    },
+                };
+                if let Some(move_diff) = move_diff {
+                    style = move_diff.patch_style(style, position, color_config);
+                }
+                if position == current_position {
+                    style = color_config.cursor_style(style, player.is_some());
+                }
+                (glyph, style)
+            }
+        };
+        let padding = self.cell_width() - glyph.width().unwrap_or(1);
+        Span::styled(format!("{}{}", glyph, " ".repeat(padding)), style)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn make_board_cells(
+        &self,
+        board: &Board,
+        rect: Rect,
+        color_config: ColorConfig,
+        current_player: Player,
+        current_position: (usize, usize),
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) -> Vec<Spans<'static>> {
+        let shift_x = self.offset.0 as i64 * self.distance as i64;
+        let shift_y = self.offset.1 as i64 * self.distance as i64;
+        let visible_cols = rect.width as usize / self.cell_width();
+        (0..rect.height as i64)
+            .map(|screen_row| {
+                let row = screen_row - shift_y;
+                Spans::from(
+                    (0..visible_cols as i64)
+                        .map(|screen_col| {
+                            let col = screen_col - shift_x;
+                            let cell = if row < 0 || col < 0 {
+                                Cell::Background
+                            } else {
+                                self.cell(board, (col as usize, row as usize))
+                            };
+                            self.make_span(
+                                board,
+                                cell,
+                                color_config,
+                                current_player,
+                                current_position,
+                                move_diff,
+                                fog,
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect()
+    }
+
+    fn make_boarder_style(
+        &self,
+        color_config: ColorConfig,
+        play: Play,
+        current_player: Player,
+    ) -> Style {
+        match play {
+            _ if color_config.alert_border() => Style::default().fg(color_config.error_color()),
+            Play::Finished | Play::History => Style::default(),
+            _ => color_config.player_style(current_player),
+        }
+    }
+}
+
+impl BoardDisplay for UnicodeBoard {
+    const MAX_DISTANCE: usize = 10;
+
+    fn player_name(&self, player: Player) -> &str {
+        match player {
+            Player::Zero => &self.player_name.0,
+            Player::One => &self.player_name.1,
+            Player::Two => &self.player_name.2,
+        }
+    }
+
+    fn player_mark(&self, player: Player) -> String {
+        self.stone_mark.convert(player).to_string()
+    }
+
+    fn scroll_left(&mut self) {
+        self.offset.0 += 1
+    }
+
+    fn scroll_right(&mut self) {
+        self.offset.0 -= 1
+    }
+
+    fn scroll_up(&mut self) {
+        self.offset.1 += 1
+    }
+
+    fn scroll_down(&mut self) {
+        self.offset.1 -= 1
+    }
+
+    fn scroll_reset(&mut self) {
+        self.offset = (0, 0)
+    }
+
+    fn center_on(&mut self, board: &Board, (x, y): (usize, usize), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let visible_cols = inner.width / self.cell_width() as u16;
+        let (col, row) = (
+            self.distance * (board.range() - y - 1) + 2 * self.distance * x,
+            self.distance * y,
+        );
+        let (width, height) = self.lattice_size(board);
+        self.offset = (
+            centering_offset(col, width, visible_cols, self.distance),
+            centering_offset(row, height, inner.height, self.distance),
+        );
+    }
+
+    fn scroll_into_view(&mut self, board: &Board, (x, y): (usize, usize), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let visible_cols = inner.width / self.cell_width() as u16;
+        let (col, row) = (
+            self.distance * (board.range() - y - 1) + 2 * self.distance * x,
+            self.distance * y,
+        );
+        self.offset = (
+            following_offset(self.offset.0, col, visible_cols, self.distance),
+            following_offset(self.offset.1, row, inner.height, self.distance),
+        );
+    }
+
+    fn scroll_page(&mut self, board: &Board, (pages_x, pages_y): (i32, i32), rect: Rect) {
+        let inner = Block::default().borders(Borders::ALL).inner(rect);
+        let visible_cols = inner.width / self.cell_width() as u16;
+        let (width, height) = self.lattice_size(board);
+        self.offset = (
+            paged_offset(self.offset.0, pages_x, width, visible_cols, self.distance),
+            paged_offset(self.offset.1, pages_y, height, inner.height, self.distance),
+        );
+    }
+
+    fn zoom_in(&mut self) {
+        if self.distance < Self::MAX_DISTANCE {
+            self.distance += 1;
+        }
+    }
+
+    fn zoom_out(&mut self) {
+        if self.distance > 2 {
+            self.distance -= 1;
+        }
+    }
+
+    fn toggle_frame_visibility(&mut self) {
+        self.frame_visibility ^= true;
+    }
+
+    fn toggle_home_region_visibility(&mut self) {
+        self.home_region_visibility ^= true;
+    }
+
+    fn min_board_block_size(&self) -> (u16, u16) {
+        (2 * self.distance as u16 + 3, self.distance as u16 + 3)
+    }
+
+    fn scroll_text(&self, board: &Board, board_rect: Rect) -> String {
+        let inner = Block::default().borders(Borders::ALL).inner(board_rect);
+        let visible_cols = inner.width / self.cell_width() as u16;
+        let (width, height) = self.lattice_size(board);
+        format!(
+            "{}, {}",
+            scroll_label(self.offset.0, width, visible_cols, self.distance),
+            scroll_label(self.offset.1, height, inner.height, self.distance)
+        )
+    }
+
+    fn zoom_level(&self) -> usize {
+        self.distance
+    }
+
+    fn render_board_block<B: Backend>(
+        &self,
+        frame: &mut Frame<B>,
+        rect: Rect,
+        title: &str,
+        board: &Board,
+        color_config: ColorConfig,
+        play: Play,
+        current_player: Player,
+        current_position: (usize, usize),
+        _capture_lines: Option<&CaptureLines>,
+        _capture_arrows: Option<&CaptureArrows>,
+        move_diff: Option<&MoveDiff>,
+        fog: Option<&Fog>,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(self.make_boarder_style(color_config, play, current_player));
+        let board_cells = self.make_board_cells(
+            board,
+            block.inner(rect),
+            color_config,
+            current_player,
+            current_position,
+            move_diff,
+            fog,
+        );
+        frame.render_widget(Paragraph::new(board_cells).block(block), rect);
+    }
+}
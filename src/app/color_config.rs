@@ -4,23 +4,194 @@
 // see https://opensource.org/licenses/mit-license.php
 
 use crate::board::Player;
-use derive_new::new;
-use tui::style::Color;
+use crate::error::TriversiError;
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+use tui::style::{Color, Modifier, Style};
 
-#[derive(Clone, Copy, Debug, new)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Cyan, magenta, and yellow.
+    #[default]
+    Default,
+    /// Okabe-Ito palette which is distinguishable for colorblind people.
+    Colorblind,
+    /// No colors; players are distinguished by bold, italic, and underline.
+    Mono,
+}
+
+#[derive(Clone, Copy, Debug)]
 pub struct ColorConfig {
     player: (Color, Color, Color),
+    player_modifier: (Modifier, Modifier, Modifier),
+    /// Modifier emphasizing stones of the current player.
+    current_player_modifier: Modifier,
+    /// Modifier of the current position.
+    cursor_modifier: Modifier,
+    /// Foreground and background of the current position, or `None` if only `cursor_modifier` marks it.
+    cursor_colors: Option<(Color, Color)>,
+    /// Colors of the current position where the player to move can select it.
+    legal_cursor_colors: Option<(Color, Color)>,
+    /// Colors of the current position where the player to move cannot select it.
+    illegal_cursor_colors: Option<(Color, Color)>,
+    /// Color of errors in the message and alerts on the screen.
+    error_color: Color,
+    /// Color of warnings in the message, e.g., a better move told by the coach.
+    warning_color: Color,
+    /// Color of frames between positions, which are dimmed to stay behind stones.
+    frame_color: Color,
+    /// Modifier of the stone placed by the move browsed in the history while its diff is shown.
+    placed_modifier: Modifier,
+    /// Modifier of the stones flipped by the move browsed in the history while its diff is shown.
+    flipped_modifier: Modifier,
+    /// Modifier of the positions the move browsed in the history did not change while its diff is shown.
+    unchanged_modifier: Modifier,
+    /// Whether the border of the board is in `error_color` to alert a blunder shown in the history or to flash a notification.
+    alert_border: bool,
 }
 
 impl Default for ColorConfig {
     fn default() -> Self {
-        Self {
-            player: (Color::Cyan, Color::Magenta, Color::Yellow),
+        Self::from(Theme::default())
+    }
+}
+
+impl From<Theme> for ColorConfig {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Default => Self {
+                player: (Color::Cyan, Color::Magenta, Color::Yellow),
+                player_modifier: (Modifier::empty(), Modifier::empty(), Modifier::empty()),
+                current_player_modifier: Modifier::BOLD | Modifier::UNDERLINED,
+                cursor_modifier: Modifier::empty(),
+                cursor_colors: Some((Color::Black, Color::Gray)),
+                legal_cursor_colors: Some((Color::Black, Color::Green)),
+                illegal_cursor_colors: Some((Color::Black, Color::Red)),
+                error_color: Color::Red,
+                warning_color: Color::Yellow,
+                frame_color: Color::DarkGray,
+                placed_modifier: Modifier::BOLD | Modifier::UNDERLINED,
+                flipped_modifier: Modifier::REVERSED,
+                unchanged_modifier: Modifier::DIM,
+                alert_border: false,
+            },
+            Theme::Colorblind => Self {
+                player: (
+                    Color::Rgb(86, 180, 233),
+                    Color::Rgb(213, 94, 0),
+                    Color::Rgb(240, 228, 66),
+                ),
+                player_modifier: (Modifier::empty(), Modifier::empty(), Modifier::empty()),
+                current_player_modifier: Modifier::BOLD | Modifier::UNDERLINED,
+                cursor_modifier: Modifier::empty(),
+                cursor_colors: Some((Color::Black, Color::Gray)),
+                // Bluish green and reddish purple of the Okabe-Ito palette.
+                legal_cursor_colors: Some((Color::Black, Color::Rgb(0, 158, 115))),
+                illegal_cursor_colors: Some((Color::Black, Color::Rgb(204, 121, 167))),
+                error_color: Color::Red,
+                warning_color: Color::Yellow,
+                frame_color: Color::DarkGray,
+                placed_modifier: Modifier::BOLD | Modifier::UNDERLINED,
+                flipped_modifier: Modifier::REVERSED,
+                unchanged_modifier: Modifier::DIM,
+                alert_border: false,
+            },
+            Theme::Mono => Self {
+                player: (Color::Reset, Color::Reset, Color::Reset),
+                player_modifier: (Modifier::BOLD, Modifier::ITALIC, Modifier::UNDERLINED),
+                current_player_modifier: Modifier::empty(),
+                cursor_modifier: Modifier::REVERSED,
+                cursor_colors: None,
+                legal_cursor_colors: None,
+                illegal_cursor_colors: None,
+                error_color: Color::Red,
+                warning_color: Color::Yellow,
+                frame_color: Color::DarkGray,
+                // Stones are told by bold, italic, and underline, so the flipped ones are only left undimmed.
+                placed_modifier: Modifier::REVERSED,
+                flipped_modifier: Modifier::empty(),
+                unchanged_modifier: Modifier::DIM,
+                alert_border: false,
+            },
         }
     }
 }
 
 impl ColorConfig {
+    /// Replaces colors of players by `player_colors_str` like `cyan,#ff8000,11` (delimiters are ','),
+    /// which fails with `InvalidColor` at the first invalid color.
+    pub fn try_with_player_colors(self, player_colors_str: &str) -> Result<Self, TriversiError> {
+        let colors = player_colors_str
+            .split(',')
+            .map(|color| parse_color(color.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match colors.as_slice() {
+            &[color_0, color_1, color_2] => Ok(Self {
+                player: (color_0, color_1, color_2),
+                ..self
+            }),
+            _ => Err(TriversiError::InvalidStringForPlayerColors(
+                player_colors_str.to_owned(),
+            )),
+        }
+    }
+
+    /// Replaces the color of frames by `frame_color_str` written like a color of players.
+    pub fn try_with_frame_color(self, frame_color_str: &str) -> Result<Self, TriversiError> {
+        let frame_color = parse_color(frame_color_str.trim())?;
+        Ok(Self {
+            frame_color,
+            ..self
+        })
+    }
+
+    /// Makes the border of the board in the error color.
+    pub fn with_alert_border(self) -> Self {
+        Self {
+            alert_border: true,
+            ..self
+        }
+    }
+
+    pub fn alert_border(&self) -> bool {
+        self.alert_border
+    }
+
+    pub fn error_color(&self) -> Color {
+        self.error_color
+    }
+
+    pub fn warning_color(&self) -> Color {
+        self.warning_color
+    }
+
+    pub fn frame_style(&self) -> Style {
+        Style::default()
+            .fg(self.frame_color)
+            .add_modifier(Modifier::DIM)
+    }
+
+    /// Makes the current position blink to show a suggested position.
+    pub fn with_blinking_cursor(self) -> Self {
+        Self {
+            cursor_modifier: self.cursor_modifier | Modifier::SLOW_BLINK,
+            ..self
+        }
+    }
+
+    /// Colors the current position by whether the player to move can select it.
+    pub fn with_cursor_legality(self, is_legal: bool) -> Self {
+        Self {
+            cursor_colors: if is_legal {
+                self.legal_cursor_colors
+            } else {
+                self.illegal_cursor_colors
+            },
+            ..self
+        }
+    }
+
     pub fn player(&self, player: Player) -> Color {
         match player {
             Player::Zero => self.player.0,
@@ -28,4 +199,126 @@ impl ColorConfig {
             Player::Two => self.player.2,
         }
     }
+
+    pub fn player_modifier(&self, player: Player) -> Modifier {
+        match player {
+            Player::Zero => self.player_modifier.0,
+            Player::One => self.player_modifier.1,
+            Player::Two => self.player_modifier.2,
+        }
+    }
+
+    /// Style distinguishing `player` from the others.
+    pub fn player_style(&self, player: Player) -> Style {
+        Style::default()
+            .fg(self.player(player))
+            .add_modifier(self.player_modifier(player))
+    }
+
+    pub fn current_player_modifier(&self) -> Modifier {
+        self.current_player_modifier
+    }
+
+    /// Style of stones hidden by the fog, which tells only that the positions are occupied.
+    pub fn fog_style(&self) -> Style {
+        Style::default()
+            .fg(self.frame_color)
+            .remove_modifier(Modifier::all())
+    }
+
+    pub fn placed_modifier(&self) -> Modifier {
+        self.placed_modifier
+    }
+
+    pub fn flipped_modifier(&self) -> Modifier {
+        self.flipped_modifier
+    }
+
+    pub fn unchanged_modifier(&self) -> Modifier {
+        self.unchanged_modifier
+    }
+
+    /// `style` of a position marked as the current one, where the foreground is kept on stones
+    /// to tell their players.
+    pub fn cursor_style(&self, style: Style, has_stone: bool) -> Style {
+        let style = style.add_modifier(self.cursor_modifier);
+        match self.cursor_colors {
+            Some((_, background)) if has_stone => style.bg(background),
+            Some((foreground, background)) => style.fg(foreground).bg(background),
+            None => style,
+        }
+    }
+
+    /// Dim background of the home region of `player`.
+    /// Colors which cannot be dimmed, e.g., indices of 256 colors, give no background.
+    pub fn home_background(&self, player: Player) -> Color {
+        match rgb(self.player(player)) {
+            Some((r, g, b)) => Color::Rgb(r / 5, g / 5, b / 5),
+            None => Color::Reset,
+        }
+    }
+}
+
+/// RGB values of a color, where named colors are those of xterm.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let rgb = match color {
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Reset | Color::Indexed(_) => return None,
+    };
+    Some(rgb)
+}
+
+/// Parses a color name like `light-red`, a hex code like `#ff8000`, or an index of 256 colors.
+pub fn parse_color(s: &str) -> Result<Color, TriversiError> {
+    parse_color_value(s).ok_or_else(|| TriversiError::InvalidColor(s.to_owned()))
+}
+
+fn parse_color_value(s: &str) -> Option<Color> {
+    let color = match s.to_ascii_lowercase().replace('_', "-").as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "dark-gray" => Color::DarkGray,
+        "light-red" => Color::LightRed,
+        "light-green" => Color::LightGreen,
+        "light-yellow" => Color::LightYellow,
+        "light-blue" => Color::LightBlue,
+        "light-magenta" => Color::LightMagenta,
+        "light-cyan" => Color::LightCyan,
+        "white" => Color::White,
+        s => {
+            if let Some(hex) = s.strip_prefix('#') {
+                if hex.len() != 6 {
+                    return None;
+                }
+                let rgb = u32::from_str_radix(hex, 16).ok()?;
+                Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+            } else {
+                Color::Indexed(s.parse().ok()?)
+            }
+        }
+    };
+    Some(color)
 }
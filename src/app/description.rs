@@ -0,0 +1,220 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Descriptions of the game in sentences, which are read by screen readers from the message log.
+//! Positions are written in the notation of game archives followed by their coordinates, e.g. `c3 (2, 2)`.
+
+use crate::app::event_log::GameEvent;
+use crate::board::archive::format_move;
+use crate::board::{sorted_moves, Availables, Board, Move, Player, PLAYERS};
+
+/// Position in the notation of game archives with its coordinates.
+pub fn position_text((x, y): (usize, usize)) -> String {
+    format!("{} ({}, {})", format_move(Move::Put((x, y))), x, y)
+}
+
+/// Lines describing the board, where `headline` tells the state of the game,
+/// the selectable positions of `player` are listed only if `availables` is given,
+/// and the rows of the board are read out from the apex if `rows` is true.
+pub fn describe(
+    board: &Board,
+    headline: &str,
+    player_names: &[&str],
+    player: Player,
+    cursor: (usize, usize),
+    availables: Option<&Availables>,
+    rows: bool,
+) -> Vec<String> {
+    let name = |player: Player| player_names[player.index()];
+    let mut lines = vec![format!("{}.", headline)];
+    let scores = board.scores();
+    lines.push(format!(
+        "Score: {}.",
+        PLAYERS
+            .iter()
+            .map(|player| format!("{} {}", name(*player), scores[*player]))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    let contents = match board.player(cursor) {
+        Some(owner) => format!("{} stone", name(owner)),
+        None => match availables.and_then(|availables| availables[player].get(&cursor)) {
+            // The selected position itself is included in the positions.
+            Some(positions) => format!("empty, selectable, flips {}", positions.len() - 1),
+            None => "empty".to_owned(),
+        },
+    };
+    lines.push(format!("Cursor: {}, {}.", position_text(cursor), contents));
+    if let Some(availables) = availables {
+        let moves = sorted_moves(availables, player)
+            .into_iter()
+            .map(|position| format_move(Move::Put(position.into())))
+            .collect::<Vec<_>>();
+        match moves.is_empty() {
+            true => lines.push(format!("Player-{} has no legal move.", name(player))),
+            false => lines.push(format!(
+                "Legal moves of Player-{}: {}.",
+                name(player),
+                moves.join(", ")
+            )),
+        }
+    }
+    if rows {
+        for (y, row) in board.board().iter().enumerate() {
+            let cells = row
+                .iter()
+                .enumerate()
+                .map(|(x, owner)| {
+                    format!(
+                        "{} {}",
+                        format_move(Move::Put((x, y))),
+                        owner.map_or("empty", name)
+                    )
+                })
+                .collect::<Vec<_>>();
+            lines.push(format!("Row {}: {}.", y + 1, cells.join(", ")));
+        }
+    }
+    lines
+}
+
+/// Sentence announcing a move or a skip, or `None` for other events,
+/// including the pass following an announced skip.
+pub fn announce(event: &GameEvent, player_names: &[&str]) -> Option<String> {
+    let name = |player: &Player| player_names[player.index()];
+    match event {
+        GameEvent::Move {
+            turn,
+            player,
+            position,
+            flipped,
+            ..
+        } => Some(format!(
+            "Turn {}: Player-{} placed a stone at {} and flipped {} {}.",
+            turn,
+            name(player),
+            position_text(*position),
+            flipped.len(),
+            if flipped.len() == 1 {
+                "stone"
+            } else {
+                "stones"
+            }
+        )),
+        GameEvent::Skip { turn, player, .. } => Some(format!(
+            "Turn {}: Player-{} cannot select any position and passes.",
+            turn,
+            name(player)
+        )),
+        GameEvent::Init { .. }
+        | GameEvent::Pass { .. }
+        | GameEvent::Finish { .. }
+        | GameEvent::History { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::fen::parse_fen;
+
+    const NAMES: [&str; 3] = ["Cyan", "Magenta", "Yellow"];
+
+    #[test]
+    fn opening_is_described() {
+        let board = Board::try_new(5).unwrap();
+        let mut availables = Availables::default();
+        board.update_availables(&mut availables);
+        let player = Player::default();
+        assert_eq!(
+            describe(
+                &board,
+                "Turn 1",
+                &NAMES,
+                player,
+                (0, 0),
+                Some(&availables),
+                true
+            ),
+            [
+                "Turn 1.",
+                "Score: Cyan 4, Magenta 4, Yellow 4.",
+                "Cursor: a1 (0, 0), empty, selectable, flips 4.",
+                "Legal moves of Player-Cyan: a1, a5, e5.",
+                "Row 1: a1 empty.",
+                "Row 2: a2 Magenta, b2 Yellow.",
+                "Row 3: a3 Yellow, b3 Cyan, c3 Magenta.",
+                "Row 4: a4 Cyan, b4 Magenta, c4 Yellow, d4 Cyan.",
+                "Row 5: a5 empty, b5 Yellow, c5 Cyan, d5 Magenta, e5 empty.",
+            ]
+        );
+        assert_eq!(
+            describe(&board, "Turn 1", &NAMES, player, (1, 2), None, false),
+            [
+                "Turn 1.",
+                "Score: Cyan 4, Magenta 4, Yellow 4.",
+                "Cursor: b3 (1, 2), Cyan stone.",
+            ]
+        );
+    }
+
+    #[test]
+    fn player_without_legal_moves_is_described() {
+        let (board, player) = parse_fen("1/23/123/1231/23123 1").unwrap();
+        let mut availables = Availables::default();
+        board.update_availables(&mut availables);
+        assert_eq!(
+            describe(
+                &board,
+                "Game over",
+                &NAMES,
+                player,
+                (0, 4),
+                Some(&availables),
+                false
+            ),
+            [
+                "Game over.",
+                "Score: Cyan 5, Magenta 5, Yellow 5.",
+                "Cursor: a5 (0, 4), Magenta stone.",
+                "Player-Cyan has no legal move.",
+            ]
+        );
+    }
+
+    #[test]
+    fn moves_and_skips_are_announced() {
+        let flipped = |n_stones: usize| GameEvent::Move {
+            turn: 3,
+            player: Player::One,
+            position: (2, 4),
+            flipped: vec![(1, 3); n_stones],
+            counts: vec![4, 4, 4],
+            next_player: Player::Two,
+        };
+        assert_eq!(
+            announce(&flipped(1), &NAMES).unwrap(),
+            "Turn 3: Player-Magenta placed a stone at c5 (2, 4) and flipped 1 stone."
+        );
+        assert_eq!(
+            announce(&flipped(2), &NAMES).unwrap(),
+            "Turn 3: Player-Magenta placed a stone at c5 (2, 4) and flipped 2 stones."
+        );
+        let skip = GameEvent::Skip {
+            turn: 4,
+            player: Player::default(),
+            counts: vec![4, 4, 4],
+        };
+        assert_eq!(
+            announce(&skip, &NAMES).unwrap(),
+            "Turn 4: Player-Cyan cannot select any position and passes."
+        );
+        let init = GameEvent::Init {
+            range: 5,
+            counts: vec![4, 4, 4],
+        };
+        assert_eq!(announce(&init, &NAMES), None);
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::{Board, Move, Player};
+use crate::net::{AppliedMove, Host};
+use serde_derive::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Event of a game for spectators and other frontends.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// A new game is started.
+    Init { range: usize, counts: Vec<u64> },
+    Move {
+        /// Turn after the move, which starts from 1.
+        turn: usize,
+        player: Player,
+        position: (usize, usize),
+        /// Positions flipped by the move, excluding `position`.
+        flipped: Vec<(usize, usize)>,
+        counts: Vec<u64>,
+        /// Player whose turn it is after the move, which is `player` if the move finishes the game.
+        next_player: Player,
+    },
+    /// The player cannot select any position.
+    Skip {
+        turn: usize,
+        player: Player,
+        counts: Vec<u64>,
+    },
+    /// The skipped player passes by the select key, where turns are not skipped automatically.
+    Pass {
+        turn: usize,
+        player: Player,
+        counts: Vec<u64>,
+        next_player: Player,
+    },
+    Finish {
+        turn: usize,
+        winner: Option<Player>,
+        counts: Vec<u64>,
+    },
+    /// The board at `turn` of the history is shown by browsing the history or undoing moves.
+    History { turn: usize, counts: Vec<u64> },
+}
+
+/// Receiver of events registered to `System`, e.g., a recorder of the events of a scripted game.
+pub trait Observer {
+    fn notify(&mut self, event: &GameEvent);
+}
+
+impl<F: FnMut(&GameEvent)> Observer for F {
+    fn notify(&mut self, event: &GameEvent) {
+        self(event)
+    }
+}
+
+/// Host of a network game, which sends moves and passes applied on this terminal to the clients.
+impl Observer for Host {
+    fn notify(&mut self, event: &GameEvent) {
+        let applied_move = match event {
+            GameEvent::Move {
+                player,
+                position,
+                flipped,
+                next_player,
+                ..
+            } => AppliedMove {
+                player: *player,
+                player_move: Move::Put(*position),
+                flipped: flipped.clone(),
+                next_player: *next_player,
+            },
+            GameEvent::Pass {
+                player,
+                next_player,
+                ..
+            } => AppliedMove {
+                player: *player,
+                player_move: Move::Pass,
+                flipped: Vec::new(),
+                next_player: *next_player,
+            },
+            _ => return,
+        };
+        self.broadcast_move(applied_move);
+    }
+}
+
+/// Numbers of stones in `PLAYERS` order.
+pub fn counts(board: &Board) -> Vec<u64> {
+    board.count().values().copied().collect()
+}
+
+/// Destination of events, where each event is written as one line of JSON.
+/// A file or a FIFO is appended to so that a reader can follow it.
+#[derive(Debug)]
+pub struct EventLog {
+    file: File,
+}
+
+impl EventLog {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    pub fn write(&mut self, event: &GameEvent) -> io::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()
+    }
+}
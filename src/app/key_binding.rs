@@ -3,6 +3,9 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+use crate::error::TriversiError;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
 use termion::event::Key;
 
 #[cfg(feature = "alternative_key_binding")]
@@ -22,14 +25,44 @@ pub mod default {
     pub const SCROLL_LEFT: Key = Key::Left;
     pub const SCROLL_RIGHT: Key = Key::Right;
     pub const SCROLL_RESET: Key = Key::Home;
+    pub const SCROLL_PAGE_UP: Key = Key::PageUp;
+    pub const SCROLL_PAGE_DOWN: Key = Key::PageDown;
+    pub const SCROLL_PAGE_LEFT: Key = Key::Char('<');
+    pub const SCROLL_PAGE_RIGHT: Key = Key::Char('>');
+    pub const CENTER_VIEW: Key = Key::Char('c');
+    pub const CENTER_CURSOR: Key = Key::Char('z');
     pub const FRAME_TOGGLE: Key = Key::Char('f');
+    pub const HOME_TOGGLE: Key = Key::Char('r');
+    pub const LEGEND_TOGGLE: Key = Key::Char('g');
+    pub const CAPTURE_LINES_TOGGLE: Key = Key::Char('x');
+    pub const FLIPS_TOGGLE: Key = Key::Char('F');
+    pub const DIFF_TOGGLE: Key = Key::Char('D');
+    pub const FOG_TOGGLE: Key = Key::Char('V');
+    pub const HUD_TOGGLE: Key = Key::Char('o');
+    pub const ROTATE: Key = Key::Char('R');
+    pub const DESCRIBE: Key = Key::Char('?');
+    pub const DUMP: Key = Key::Char('d');
+    pub const CODE: Key = Key::Char('C');
+    pub const SAVE: Key = Key::Char('W');
+    pub const EXPORT: Key = Key::Char('e');
+    pub const MESSAGE_LOG: Key = Key::Char('m');
+    pub const SUGGEST: Key = Key::Char('s');
+    pub const DEBUG_TOGGLE: Key = Key::F(12);
     pub const INTO_HISTORY: Key = Key::Char('t');
     pub const PREV_HISTORY: Key = Key::Char('p');
     pub const NEXT_HISTORY: Key = Key::Char('n');
+    pub const SWITCH_BRANCH: Key = Key::Char('b');
+    pub const UNDO: Key = Key::Char('u');
+    pub const REDO: Key = Key::Char('y');
+    pub const ANNOTATE: Key = Key::Char('a');
+    pub const REVEAL: Key = Key::Char('v');
+    pub const SWAP: Key = Key::Char('w');
     pub const ZOOM_IN: Key = Key::Char('+');
     pub const ZOOM_OUT: Key = Key::Char('-');
     pub const QUIT: Key = Key::Char('q');
+    pub const FORCE_QUIT: Key = Key::Ctrl('c');
     pub const INIT: Key = Key::Char('0');
+    pub const PAUSE: Key = Key::Char(' ');
     pub const SELECT: Key = Key::Char('\n');
 }
 
@@ -45,57 +78,442 @@ pub mod alternative {
     pub const SCROLL_LEFT: Key = Key::Left;
     pub const SCROLL_RIGHT: Key = Key::Right;
     pub const SCROLL_RESET: Key = Key::Home;
+    pub const SCROLL_PAGE_UP: Key = Key::PageUp;
+    pub const SCROLL_PAGE_DOWN: Key = Key::PageDown;
+    pub const SCROLL_PAGE_LEFT: Key = Key::Char('<');
+    pub const SCROLL_PAGE_RIGHT: Key = Key::Char('>');
+    pub const CENTER_VIEW: Key = Key::Char('c');
+    pub const CENTER_CURSOR: Key = Key::Char('z');
     pub const FRAME_TOGGLE: Key = Key::Char('f');
+    pub const HOME_TOGGLE: Key = Key::Char('r');
+    pub const LEGEND_TOGGLE: Key = Key::Char('g');
+    pub const CAPTURE_LINES_TOGGLE: Key = Key::Char('x');
+    pub const FLIPS_TOGGLE: Key = Key::Char('F');
+    pub const DIFF_TOGGLE: Key = Key::Char('D');
+    pub const FOG_TOGGLE: Key = Key::Char('V');
+    pub const HUD_TOGGLE: Key = Key::Char('o');
+    pub const ROTATE: Key = Key::Char('R');
+    pub const DESCRIBE: Key = Key::Char('?');
+    pub const DUMP: Key = Key::Char('d');
+    pub const CODE: Key = Key::Char('C');
+    pub const SAVE: Key = Key::Char('W');
+    pub const EXPORT: Key = Key::Char('e');
+    pub const MESSAGE_LOG: Key = Key::Char('m');
+    pub const SUGGEST: Key = Key::Char('s');
+    pub const DEBUG_TOGGLE: Key = Key::F(12);
     pub const INTO_HISTORY: Key = Key::Char('h');
     pub const PREV_HISTORY: Key = Key::Char('p');
     pub const NEXT_HISTORY: Key = Key::Char('n');
+    pub const SWITCH_BRANCH: Key = Key::Char('b');
+    pub const UNDO: Key = Key::Char('u');
+    pub const REDO: Key = Key::Char('y');
+    pub const ANNOTATE: Key = Key::Char('a');
+    pub const REVEAL: Key = Key::Char('v');
+    pub const SWAP: Key = Key::Char('w');
     pub const ZOOM_IN: Key = Key::Char('+');
     pub const ZOOM_OUT: Key = Key::Char('-');
     pub const QUIT: Key = Key::Char('q');
+    pub const FORCE_QUIT: Key = Key::Ctrl('c');
     pub const INIT: Key = Key::Char('0');
+    pub const PAUSE: Key = Key::Char(' ');
     pub const SELECT: Key = Key::Char('\n');
 }
 
-pub fn make_guidance_in_turn() -> String {
-    format!(" Quit [{}], Initialize [{}], History [{}], Frame On/Off [{}], Select [{}]\n Move ◀︎/▼/▲/▶︎ [{}/{}/{}/{}], Scroll ◀︎/▼/▲/▶︎/reset [{}/{}/{}/{}/{}], Zoom In/Out [{}/{}]",
-        change_key_to_str(key::QUIT),
-        change_key_to_str(key::INIT),
-        change_key_to_str(key::INTO_HISTORY),
-        change_key_to_str(key::FRAME_TOGGLE),
-        change_key_to_str(key::SELECT),
-        change_key_to_str(key::MOVE_LEFT),
-        change_key_to_str(key::MOVE_DOWN),
-        change_key_to_str(key::MOVE_UP),
-        change_key_to_str(key::MOVE_RIGHT),
-        change_key_to_str(key::SCROLL_LEFT),
-        change_key_to_str(key::SCROLL_DOWN),
-        change_key_to_str(key::SCROLL_UP),
-        change_key_to_str(key::SCROLL_RIGHT),
-        change_key_to_str(key::SCROLL_RESET),
-        change_key_to_str(key::ZOOM_IN),
-        change_key_to_str(key::ZOOM_OUT),
-    )
+/// Operations assigned to keys.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+    ScrollReset,
+    ScrollPageUp,
+    ScrollPageDown,
+    ScrollPageLeft,
+    ScrollPageRight,
+    CenterView,
+    CenterCursor,
+    FrameToggle,
+    HomeToggle,
+    LegendToggle,
+    CaptureLinesToggle,
+    FlipsToggle,
+    DiffToggle,
+    FogToggle,
+    HudToggle,
+    Rotate,
+    Describe,
+    Dump,
+    Code,
+    Save,
+    Export,
+    MessageLog,
+    Suggest,
+    DebugToggle,
+    IntoHistory,
+    PrevHistory,
+    NextHistory,
+    SwitchBranch,
+    Undo,
+    Redo,
+    Annotate,
+    Reveal,
+    Swap,
+    ZoomIn,
+    ZoomOut,
+    Quit,
+    ForceQuit,
+    Init,
+    Pause,
+    Select,
 }
-pub fn make_guidance_in_history() -> String {
-    format!(" Frame On/Off [{}], Select [{}]\n Prev/Next [{}/{}], Scroll ◀︎/▼/▲/▶︎/reset [{}/{}/{}/{}/{}], Zoom In/Out [{}/{}]",
-        change_key_to_str(key::FRAME_TOGGLE),
-        change_key_to_str(key::SELECT),
-        change_key_to_str(key::PREV_HISTORY),
-        change_key_to_str(key::NEXT_HISTORY),
-        change_key_to_str(key::SCROLL_LEFT),
-        change_key_to_str(key::SCROLL_DOWN),
-        change_key_to_str(key::SCROLL_UP),
-        change_key_to_str(key::SCROLL_RIGHT),
-        change_key_to_str(key::SCROLL_RESET),
-        change_key_to_str(key::ZOOM_IN),
-        change_key_to_str(key::ZOOM_OUT),
+
+pub const ACTIONS: &[Action] = &[
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::ScrollUp,
+    Action::ScrollDown,
+    Action::ScrollLeft,
+    Action::ScrollRight,
+    Action::ScrollReset,
+    Action::ScrollPageUp,
+    Action::ScrollPageDown,
+    Action::ScrollPageLeft,
+    Action::ScrollPageRight,
+    Action::CenterView,
+    Action::CenterCursor,
+    Action::FrameToggle,
+    Action::HomeToggle,
+    Action::LegendToggle,
+    Action::CaptureLinesToggle,
+    Action::FlipsToggle,
+    Action::DiffToggle,
+    Action::FogToggle,
+    Action::HudToggle,
+    Action::Rotate,
+    Action::Describe,
+    Action::Dump,
+    Action::Code,
+    Action::Save,
+    Action::Export,
+    Action::MessageLog,
+    Action::Suggest,
+    Action::DebugToggle,
+    Action::IntoHistory,
+    Action::PrevHistory,
+    Action::NextHistory,
+    Action::SwitchBranch,
+    Action::Undo,
+    Action::Redo,
+    Action::Annotate,
+    Action::Reveal,
+    Action::Swap,
+    Action::ZoomIn,
+    Action::ZoomOut,
+    Action::Quit,
+    Action::ForceQuit,
+    Action::Init,
+    Action::Pause,
+    Action::Select,
+];
+
+impl Action {
+    /// Name used in the configuration file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollLeft => "scroll_left",
+            Action::ScrollRight => "scroll_right",
+            Action::ScrollReset => "scroll_reset",
+            Action::ScrollPageUp => "scroll_page_up",
+            Action::ScrollPageDown => "scroll_page_down",
+            Action::ScrollPageLeft => "scroll_page_left",
+            Action::ScrollPageRight => "scroll_page_right",
+            Action::CenterView => "center_view",
+            Action::CenterCursor => "center_cursor",
+            Action::FrameToggle => "frame_toggle",
+            Action::HomeToggle => "home_toggle",
+            Action::LegendToggle => "legend_toggle",
+            Action::CaptureLinesToggle => "capture_lines_toggle",
+            Action::FlipsToggle => "flips_toggle",
+            Action::DiffToggle => "diff_toggle",
+            Action::FogToggle => "fog_toggle",
+            Action::HudToggle => "hud_toggle",
+            Action::Rotate => "rotate",
+            Action::Describe => "describe",
+            Action::Dump => "dump",
+            Action::Code => "code",
+            Action::Save => "save",
+            Action::Export => "export",
+            Action::MessageLog => "message_log",
+            Action::Suggest => "suggest",
+            Action::DebugToggle => "debug_toggle",
+            Action::IntoHistory => "into_history",
+            Action::PrevHistory => "prev_history",
+            Action::NextHistory => "next_history",
+            Action::SwitchBranch => "switch_branch",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::Annotate => "annotate",
+            Action::Reveal => "reveal",
+            Action::Swap => "swap",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::Quit => "quit",
+            Action::ForceQuit => "force_quit",
+            Action::Init => "init",
+            Action::Pause => "pause",
+            Action::Select => "select",
+        }
+    }
+
+    fn default_key(&self) -> Key {
+        match self {
+            Action::MoveUp => key::MOVE_UP,
+            Action::MoveDown => key::MOVE_DOWN,
+            Action::MoveLeft => key::MOVE_LEFT,
+            Action::MoveRight => key::MOVE_RIGHT,
+            Action::ScrollUp => key::SCROLL_UP,
+            Action::ScrollDown => key::SCROLL_DOWN,
+            Action::ScrollLeft => key::SCROLL_LEFT,
+            Action::ScrollRight => key::SCROLL_RIGHT,
+            Action::ScrollReset => key::SCROLL_RESET,
+            Action::ScrollPageUp => key::SCROLL_PAGE_UP,
+            Action::ScrollPageDown => key::SCROLL_PAGE_DOWN,
+            Action::ScrollPageLeft => key::SCROLL_PAGE_LEFT,
+            Action::ScrollPageRight => key::SCROLL_PAGE_RIGHT,
+            Action::CenterView => key::CENTER_VIEW,
+            Action::CenterCursor => key::CENTER_CURSOR,
+            Action::FrameToggle => key::FRAME_TOGGLE,
+            Action::HomeToggle => key::HOME_TOGGLE,
+            Action::LegendToggle => key::LEGEND_TOGGLE,
+            Action::CaptureLinesToggle => key::CAPTURE_LINES_TOGGLE,
+            Action::FlipsToggle => key::FLIPS_TOGGLE,
+            Action::DiffToggle => key::DIFF_TOGGLE,
+            Action::FogToggle => key::FOG_TOGGLE,
+            Action::HudToggle => key::HUD_TOGGLE,
+            Action::Rotate => key::ROTATE,
+            Action::Describe => key::DESCRIBE,
+            Action::Dump => key::DUMP,
+            Action::Code => key::CODE,
+            Action::Save => key::SAVE,
+            Action::Export => key::EXPORT,
+            Action::MessageLog => key::MESSAGE_LOG,
+            Action::Suggest => key::SUGGEST,
+            Action::DebugToggle => key::DEBUG_TOGGLE,
+            Action::IntoHistory => key::INTO_HISTORY,
+            Action::PrevHistory => key::PREV_HISTORY,
+            Action::NextHistory => key::NEXT_HISTORY,
+            Action::SwitchBranch => key::SWITCH_BRANCH,
+            Action::Undo => key::UNDO,
+            Action::Redo => key::REDO,
+            Action::Annotate => key::ANNOTATE,
+            Action::Reveal => key::REVEAL,
+            Action::Swap => key::SWAP,
+            Action::ZoomIn => key::ZOOM_IN,
+            Action::ZoomOut => key::ZOOM_OUT,
+            Action::Quit => key::QUIT,
+            Action::ForceQuit => key::FORCE_QUIT,
+            Action::Init => key::INIT,
+            Action::Pause => key::PAUSE,
+            Action::Select => key::SELECT,
+        }
+    }
+}
+
+/// Keys assigned to actions, which defaults to the compiled-in key binding.
+#[derive(Clone, Debug)]
+pub struct KeyBinding {
+    keys: HashMap<Action, Key>,
+}
+
+impl Default for KeyBinding {
+    fn default() -> Self {
+        Self {
+            keys: ACTIONS
+                .iter()
+                .map(|action| (*action, action.default_key()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+}
+
+impl KeyBinding {
+    /// Overrides the default keys by `keys` which maps actions to strings like `Ctrl-c`.
+    pub fn try_new(keys: &HashMap<Action, String>) -> Result<Self, TriversiError> {
+        let mut key_binding = Self::default();
+        for (action, key_str) in keys {
+            key_binding.keys.insert(*action, parse_key(key_str)?);
+        }
+        for action in ACTIONS {
+            if let Some(other) = ACTIONS.iter().find(|other| {
+                other.name() < action.name() && key_binding.key(**other) == key_binding.key(*action)
+            }) {
+                return Err(TriversiError::DuplicatedKey(
+                    change_key_to_str(key_binding.key(*action)),
+                    other.name().to_owned(),
+                    action.name().to_owned(),
+                ));
+            }
+        }
+        Ok(key_binding)
+    }
+
+    pub fn key(&self, action: Action) -> Key {
+        *self.keys.get(&action).unwrap()
+    }
+
+    pub fn key_str(&self, action: Action) -> String {
+        change_key_to_str(self.key(action))
+    }
+
+    pub fn action(&self, key: Key) -> Option<Action> {
+        ACTIONS
+            .iter()
+            .find(|action| self.key(**action) == key)
+            .copied()
+    }
+
+    pub fn make_guidance_in_turn(&self) -> String {
+        format!(" Quit/Force [{}/{}], Initialize [{}], Pause [{}], History [{}], Undo/Redo [{}/{}], Frame On/Off [{}], Home On/Off [{}], Legend On/Off [{}], Lines On/Off [{}], Flips On/Off [{}], Fog On/Off [{}], HUD Full/Compact [{}], Rotate [{}], Dump [{}], Code [{}], Save [{}], Log [{}], Describe [{}], Hint [{}], Debug [{}], Select [{}]\n Move ◀︎/▼/▲/▶︎ [{}/{}/{}/{}], Scroll ◀︎/▼/▲/▶︎/reset [{}/{}/{}/{}/{}], Page ◀︎/▼/▲/▶︎ [{}/{}/{}/{}], Center/Cursor [{}/{}], Zoom In/Out [{}/{}]",
+            self.key_str(Action::Quit),
+            self.key_str(Action::ForceQuit),
+            self.key_str(Action::Init),
+            self.key_str(Action::Pause),
+            self.key_str(Action::IntoHistory),
+            self.key_str(Action::Undo),
+            self.key_str(Action::Redo),
+            self.key_str(Action::FrameToggle),
+            self.key_str(Action::HomeToggle),
+            self.key_str(Action::LegendToggle),
+            self.key_str(Action::CaptureLinesToggle),
+            self.key_str(Action::FlipsToggle),
+            self.key_str(Action::FogToggle),
+            self.key_str(Action::HudToggle),
+            self.key_str(Action::Rotate),
+            self.key_str(Action::Dump),
+            self.key_str(Action::Code),
+            self.key_str(Action::Save),
+            self.key_str(Action::MessageLog),
+            self.key_str(Action::Describe),
+            self.key_str(Action::Suggest),
+            self.key_str(Action::DebugToggle),
+            self.key_str(Action::Select),
+            self.key_str(Action::MoveLeft),
+            self.key_str(Action::MoveDown),
+            self.key_str(Action::MoveUp),
+            self.key_str(Action::MoveRight),
+            self.key_str(Action::ScrollLeft),
+            self.key_str(Action::ScrollDown),
+            self.key_str(Action::ScrollUp),
+            self.key_str(Action::ScrollRight),
+            self.key_str(Action::ScrollReset),
+            self.key_str(Action::ScrollPageLeft),
+            self.key_str(Action::ScrollPageDown),
+            self.key_str(Action::ScrollPageUp),
+            self.key_str(Action::ScrollPageRight),
+            self.key_str(Action::CenterView),
+            self.key_str(Action::CenterCursor),
+            self.key_str(Action::ZoomIn),
+            self.key_str(Action::ZoomOut),
+        )
+    }
+
+    /// Guidance in the finished game, which can also be exported.
+    pub fn make_guidance_in_finished(&self) -> String {
+        format!(
+            " Export [{}],{}",
+            self.key_str(Action::Export),
+            self.make_guidance_in_turn()
+        )
+    }
+
+    /// Guidance after a move is selected in a puzzle, which is retried by the initialization key.
+    pub fn make_guidance_in_puzzle_result(&self) -> String {
+        format!(" Quit/Force [{}/{}], Retry [{}], Reveal [{}], Frame On/Off [{}], Home On/Off [{}], Legend On/Off [{}], HUD Full/Compact [{}], Rotate [{}], Dump [{}], Code [{}], Log [{}], Describe [{}], Debug [{}]\n Scroll ◀︎/▼/▲/▶︎/reset [{}/{}/{}/{}/{}], Page ◀︎/▼/▲/▶︎ [{}/{}/{}/{}], Center/Cursor [{}/{}], Zoom In/Out [{}/{}]",
+            self.key_str(Action::Quit),
+            self.key_str(Action::ForceQuit),
+            self.key_str(Action::Init),
+            self.key_str(Action::Reveal),
+            self.key_str(Action::FrameToggle),
+            self.key_str(Action::HomeToggle),
+            self.key_str(Action::LegendToggle),
+            self.key_str(Action::HudToggle),
+            self.key_str(Action::Rotate),
+            self.key_str(Action::Dump),
+            self.key_str(Action::Code),
+            self.key_str(Action::MessageLog),
+            self.key_str(Action::Describe),
+            self.key_str(Action::DebugToggle),
+            self.key_str(Action::ScrollLeft),
+            self.key_str(Action::ScrollDown),
+            self.key_str(Action::ScrollUp),
+            self.key_str(Action::ScrollRight),
+            self.key_str(Action::ScrollReset),
+            self.key_str(Action::ScrollPageLeft),
+            self.key_str(Action::ScrollPageDown),
+            self.key_str(Action::ScrollPageUp),
+            self.key_str(Action::ScrollPageRight),
+            self.key_str(Action::CenterView),
+            self.key_str(Action::CenterCursor),
+            self.key_str(Action::ZoomIn),
+            self.key_str(Action::ZoomOut),
+        )
+    }
+
+    pub fn make_guidance_in_history(&self) -> String {
+        format!(" Force Quit [{}], Frame On/Off [{}], Legend On/Off [{}], Diff On/Off [{}], HUD Full/Compact [{}], Rotate [{}], Dump [{}], Code [{}], Save [{}], Log [{}], Describe [{}], Debug [{}], Select [{}]\n Prev/Next [{}/{}], Branch [{}], Annotate [{}], Scroll ◀︎/▼/▲/▶︎/reset [{}/{}/{}/{}/{}], Page ◀︎/▼/▲/▶︎ [{}/{}/{}/{}], Center/Cursor [{}/{}], Zoom In/Out [{}/{}]",
+            self.key_str(Action::ForceQuit),
+            self.key_str(Action::FrameToggle),
+            self.key_str(Action::LegendToggle),
+            self.key_str(Action::DiffToggle),
+            self.key_str(Action::HudToggle),
+            self.key_str(Action::Rotate),
+            self.key_str(Action::Dump),
+            self.key_str(Action::Code),
+            self.key_str(Action::Save),
+            self.key_str(Action::MessageLog),
+            self.key_str(Action::Describe),
+            self.key_str(Action::DebugToggle),
+            self.key_str(Action::Select),
+            self.key_str(Action::PrevHistory),
+            self.key_str(Action::NextHistory),
+            self.key_str(Action::SwitchBranch),
+            self.key_str(Action::Annotate),
+            self.key_str(Action::ScrollLeft),
+            self.key_str(Action::ScrollDown),
+            self.key_str(Action::ScrollUp),
+            self.key_str(Action::ScrollRight),
+            self.key_str(Action::ScrollReset),
+            self.key_str(Action::ScrollPageLeft),
+            self.key_str(Action::ScrollPageDown),
+            self.key_str(Action::ScrollPageUp),
+            self.key_str(Action::ScrollPageRight),
+            self.key_str(Action::CenterView),
+            self.key_str(Action::CenterCursor),
+            self.key_str(Action::ZoomIn),
+            self.key_str(Action::ZoomOut),
         )
+    }
 }
 
 pub fn change_key_to_str(key: Key) -> String {
     match key {
         Key::Char('\n') => "Enter".into(),
         Key::Char('\t') => "Tab".into(),
+        Key::Char(' ') => "Space".into(),
         Key::Char(c) => c.into(),
         Key::Alt('\n') => "Alt-Enter".into(),
         Key::Alt('\t') => "Alt-Tab".into(),
@@ -120,3 +538,50 @@ pub fn change_key_to_str(key: Key) -> String {
         _ => unreachable!(),
     }
 }
+
+/// Inverse of `change_key_to_str`.
+pub fn parse_key(s: &str) -> Result<Key, TriversiError> {
+    let single_char = |c: &str| {
+        let mut chars = c.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    };
+    let key = match s {
+        "Enter" => Key::Char('\n'),
+        "Tab" => Key::Char('\t'),
+        "Space" => Key::Char(' '),
+        "Alt-Enter" => Key::Alt('\n'),
+        "Alt-Tab" => Key::Alt('\t'),
+        "Ctrl-Enter" => Key::Ctrl('\n'),
+        "Ctrl-Tab" => Key::Ctrl('\t'),
+        "BS" => Key::Backspace,
+        "Left" => Key::Left,
+        "Right" => Key::Right,
+        "Up" => Key::Up,
+        "Down" => Key::Down,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+        "BackTab" => Key::BackTab,
+        "Del" => Key::Delete,
+        "Insert" => Key::Insert,
+        "Esc" => Key::Esc,
+        _ => {
+            if let Some(c) = single_char(s) {
+                Key::Char(c)
+            } else if let Some(c) = s.strip_prefix("Alt-").and_then(single_char) {
+                Key::Alt(c)
+            } else if let Some(c) = s.strip_prefix("Ctrl-").and_then(single_char) {
+                Key::Ctrl(c)
+            } else if let Some(f) = s.strip_prefix('F').and_then(|f| f.parse().ok()) {
+                Key::F(f)
+            } else {
+                return Err(TriversiError::InvalidKey(s.to_owned()));
+            }
+        }
+    };
+    Ok(key)
+}
@@ -0,0 +1,150 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Match of several games whose results are summed up, where the first player rotates every game.
+
+use crate::board::{Player, PLAYERS};
+use crate::error::TriversiError;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use getset::{CopyGetters, Getters};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// What is summed up in the standings of a match.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchScoring {
+    /// 2, 1, and 0 points for the first, the second, and the last of each game.
+    #[default]
+    Points,
+    /// Numbers of stones at the end of each game.
+    Stones,
+}
+
+#[derive(Clone, Debug, CopyGetters, Getters, Serialize, Deserialize)]
+pub struct MatchState {
+    /// Maximum number of games.
+    #[getset(get_copy = "pub")]
+    games: usize,
+    #[getset(get_copy = "pub")]
+    scoring: MatchScoring,
+    /// Results of finished games indexed by `Player::index`, which are points or stones by `scoring`.
+    #[getset(get = "pub")]
+    results: Vec<[u64; 3]>,
+}
+
+impl MatchState {
+    pub fn new(games: usize, scoring: MatchScoring) -> Self {
+        Self {
+            games,
+            scoring,
+            results: Vec::new(),
+        }
+    }
+
+    /// Loads a match saved by `save` to resume it.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(TriversiError::io(path))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse a match in {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?).map_err(TriversiError::io(path))?;
+        Ok(())
+    }
+
+    /// Records the result of a game, where `points` and `stones` are indexed by `Player::index`.
+    pub fn finish_game(&mut self, points: [u64; 3], stones: [u64; 3]) {
+        self.results.push(match self.scoring {
+            MatchScoring::Points => points,
+            MatchScoring::Stones => stones,
+        });
+    }
+
+    /// Number of the game being played, which starts from 1.
+    pub fn game_number(&self) -> usize {
+        self.results.len() + 1
+    }
+
+    /// Player who moves first in the game being played, which rotates in the order of seats.
+    pub fn first_player(&self) -> Player {
+        Player::from_index(self.results.len() % PLAYERS.len()).unwrap()
+    }
+
+    /// Sums of the results indexed by `Player::index`.
+    pub fn totals(&self) -> [u64; 3] {
+        let mut totals = [0; 3];
+        for result in &self.results {
+            for (total, value) in totals.iter_mut().zip(result) {
+                *total += value;
+            }
+        }
+        totals
+    }
+
+    /// Whether all games are played, or the leader in points cannot be caught in the remaining games.
+    pub fn is_over(&self) -> bool {
+        let remaining = self.games.saturating_sub(self.results.len()) as u64;
+        if remaining == 0 {
+            return true;
+        }
+        if self.scoring == MatchScoring::Stones {
+            return false;
+        }
+        let mut totals = self.totals();
+        totals.sort_unstable();
+        totals[2] > totals[1] + 2 * remaining
+    }
+
+    /// Players having the best total.
+    pub fn leaders(&self) -> Vec<Player> {
+        let totals = self.totals();
+        let best = totals.iter().max().copied().unwrap_or_default();
+        PLAYERS
+            .iter()
+            .copied()
+            .filter(|player| totals[player.index()] == best)
+            .collect()
+    }
+
+    /// Table of the results of the games and their totals, where `player_names` are the names of the seats.
+    pub fn standings(&self, player_names: &[&str]) -> String {
+        let mut text = String::new();
+        let widths = player_names
+            .iter()
+            .map(|name| name.len().max(5))
+            .collect::<Vec<_>>();
+        writeln!(
+            text,
+            "Match of {} games ({})",
+            self.games,
+            self.scoring.to_possible_value().unwrap().get_name()
+        )
+        .unwrap();
+        write!(text, "{:<8}", "").unwrap();
+        for (name, width) in player_names.iter().zip(&widths) {
+            write!(text, " {:>width$}", name, width = width).unwrap();
+        }
+        writeln!(text).unwrap();
+        let rows = self
+            .results
+            .iter()
+            .enumerate()
+            .map(|(i_game, result)| (format!("Game {}", i_game + 1), *result))
+            .chain([("Total".to_owned(), self.totals())]);
+        for (label, values) in rows {
+            write!(text, "{:<8}", label).unwrap();
+            for (value, width) in values.iter().zip(&widths) {
+                write!(text, " {:>width$}", value, width = width).unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        text
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use getset::{CopyGetters, Getters};
+use std::collections::VecDeque;
+use tui::style::Color;
+
+#[derive(Clone, Debug, CopyGetters, Getters)]
+pub struct LogEntry {
+    /// Turn when the message was written.
+    #[getset(get_copy = "pub")]
+    turn: usize,
+    #[getset(get_copy = "pub")]
+    color: Color,
+    #[getset(get = "pub")]
+    text: String,
+}
+
+/// Messages written to the message block, where the oldest ones are dropped.
+#[derive(Clone, Debug, Default)]
+pub struct MessageLog {
+    entries: VecDeque<LogEntry>,
+}
+
+impl MessageLog {
+    pub const CAPACITY: usize = 100;
+
+    pub fn push(&mut self, turn: usize, color: Color, text: &str) {
+        if self.entries.len() == Self::CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            turn,
+            color,
+            text: text.to_owned(),
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries from the newest.
+    pub fn iter_newest_first(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().rev()
+    }
+}
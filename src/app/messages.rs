@@ -0,0 +1,429 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Messages shown on the screen, which are translated by locale files.
+//!
+//! A locale file is a TOML table of the fields of `Messages`, where missing fields stay in English.
+//! Values given to a message are written by their numbers as `{0}`, `{1}`, ..., which may be in any order.
+
+use crate::error::TriversiError;
+use anyhow::{Context, Result};
+use serde_derive::{Deserialize, Serialize};
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+/// Languages built in besides English, with their locale files.
+const LOCALES: &[(&str, &str)] = &[("ja", include_str!("../../locales/ja.toml"))];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Messages {
+    pub quit_question: String,
+    pub init_question: String,
+    /// `{0}` is the seat taken over and `{1}` is the seat left.
+    pub swap_question: String,
+    pub slot_question: String,
+    /// `{0}` is the longest length of the name.
+    pub slot_hint: String,
+    /// `{0}` is the name of the slot.
+    pub overwrite_question: String,
+    pub size_question: String,
+    /// `{0}` is the range and `{1}` is the number of positions.
+    pub size_entry: String,
+    /// `{0}`, `{1}`, `{2}`, and `{3}` are the keys to move up, move down, select, and quit.
+    pub size_hint: String,
+    pub yes_no: String,
+    pub yes_no_save: String,
+    /// `{0}` and `{1}` are the least width and height.
+    pub terminal_too_small: String,
+    /// `{0}` is the turn and `{1}` is the player.
+    pub status_turn: String,
+    /// `{0}` is the turn and `{1}` is the player.
+    pub status_pass: String,
+    /// `{0}` is the browsed turn and `{1}` is the number of turns.
+    pub status_history: String,
+    /// `{0}` is the player.
+    pub status_puzzle: String,
+    /// `{0}` is the winner.
+    pub status_won: String,
+    pub status_drawn: String,
+    /// `{0}` is the leader and `{1}` is the lead.
+    pub leader: String,
+    pub tied: String,
+    /// `{0}` is the player and `{1}` is the spinner.
+    pub thinking: String,
+    /// `{0}` is the player and `{1}` and `{2}` are the coordinates.
+    pub cannot_select: String,
+    /// `{0}` is the player and `{1}` is the number of flipped stones.
+    pub flipped: String,
+    /// `{0}` and `{1}` are the coordinates of the better move and `{2}` is the number of stones it flips more.
+    pub coach: String,
+    /// `{0}` is the player and `{1}` is the key to continue.
+    pub skipped: String,
+    /// `{0}` is the skipped players joined by `and_separator`.
+    pub auto_skipped: String,
+    pub and_separator: String,
+    /// `{0}` is the scores and `{1}` is the flips.
+    pub game_finished: String,
+    /// `{0}` is the limit of turns, `{1}` is the scores, and `{2}` is the flips.
+    pub game_finished_by_limit: String,
+    /// `{0}` is the total time each player spent on the moves.
+    pub think_totals: String,
+    /// `{0}` is the winner.
+    pub winner: String,
+    /// `{0}` is the winner and `{1}` is the tiebreak rule.
+    pub winner_by_tiebreak: String,
+    pub drawn: String,
+    /// `{0}` is the number of stones and `{1}` is the range.
+    pub new_record: String,
+    /// `{0}`, `{1}`, and `{2}` are the keys to scroll down, scroll up, and close.
+    pub message_log_title: String,
+    /// `{0}` is the turn.
+    pub message_log_turn: String,
+    /// `{0}` is the error.
+    pub records_not_kept: String,
+    /// `{0}` is the path of the event log and `{1}` is the error.
+    pub event_log_closed: String,
+    /// `{0}` is the error.
+    pub error: String,
+    /// `{0}` is the name of the player.
+    pub player: String,
+    pub list_separator: String,
+    /// `{0}` is the title of the puzzle.
+    pub puzzle_title: String,
+    /// `{0}` is the player to move.
+    pub puzzle_goal: String,
+    /// `{0}` and `{1}` are the coordinates.
+    pub puzzle_solved: String,
+    /// `{0}` and `{1}` are the coordinates, and `{2}` and `{3}` are the keys to retry and reveal the solution.
+    pub puzzle_failed: String,
+    /// `{0}` and `{1}` are the coordinates.
+    pub solution: String,
+    /// `{0}` and `{1}` are the coordinates and `{2}` is the other accepted positions joined by `list_separator`.
+    pub solution_with_others: String,
+    /// `{0}` is the number of the game, `{1}` is the number of games, and `{2}` is the first player.
+    pub game_number: String,
+    /// `{0}` is the key to continue and `{1}` is the number of the next game.
+    pub next_game: String,
+    /// `{0}` is the winner.
+    pub match_won: String,
+    /// `{0}` and `{1}` are the leaders.
+    pub match_drawn_between: String,
+    pub match_drawn: String,
+    /// `{0}` is the path of the file.
+    pub match_saved: String,
+    /// `{0}` is the error.
+    pub match_not_saved: String,
+    /// `{0}` is the first seat and `{1}` is the second seat.
+    pub seats_swapped: String,
+    /// `{0}` is the player to move, `{1}` is the seat which may be taken over, and `{2}` is the key to swap.
+    pub swap_offer: String,
+    /// `{0}` is the key to resume.
+    pub ai_paused: String,
+    pub ai_resumed: String,
+    /// `{0}` is the player.
+    pub joined: String,
+    /// `{0}` is the player.
+    pub left: String,
+    pub connection_lost: String,
+    pub out_of_sync: String,
+    pub move_not_sent: String,
+    pub game_paused: String,
+    /// `{0}` is the offline players joined by `and_separator`.
+    pub game_paused_waiting: String,
+    pub not_in_network: String,
+    /// `{0}` and `{1}` are the coordinates and `{2}` is the number of stones it flips.
+    pub suggested: String,
+    pub no_suggestion: String,
+    /// `{0}` is the player, `{1}` is the score, `{2}` is the number of stones, and `{3}` is the territory.
+    pub score_breakdown: String,
+    /// `{0}` is the browsed turn, `{1}` is the number of turns, and `{2}` is the key to play from there.
+    pub browsing_turn: String,
+    pub nothing_to_undo: String,
+    /// `{0}` is the turn, `{1}` is the number of moves to redo, and `{2}` is the key to redo.
+    pub undone: String,
+    pub nothing_to_redo: String,
+    /// `{0}` is the name of the branch, `{1}` is its number, `{2}` is the number of branches, and `{3}` is the number of its moves.
+    pub branch: String,
+    /// The values of `branch`, followed by the turn where it diverges as `{4}`.
+    pub branch_diverging: String,
+    /// `{0}` is the number of evaluated moves and `{1}` is the number of moves.
+    pub annotating: String,
+    /// `{0}`, `{1}`, `{2}`, and `{3}` are the numbers of best moves, good moves, inaccuracies, and blunders.
+    pub annotated: String,
+    /// `{0}` is the move, `{1}` is the player, `{2}` is the quality, `{3}` is its mark, and `{4}` is the stones fewer than the best move.
+    pub annotation: String,
+    /// The values of `annotation`, followed by the coordinates of the best move as `{5}` and `{6}`.
+    pub annotation_with_best: String,
+    pub quality_best: String,
+    pub quality_good: String,
+    pub quality_inaccuracy: String,
+    pub quality_blunder: String,
+    /// `{0}` is the code of the board.
+    pub code: String,
+    /// `{0}` is the path of the file.
+    pub dumped: String,
+    /// `{0}` is the path of the file and `{1}` is the error.
+    pub dump_failed: String,
+    /// `{0}` is the path of the file.
+    pub exported: String,
+    /// `{0}` is the path of the file and `{1}` is the error.
+    pub export_failed: String,
+    /// `{0}` is the path of the file and `{1}` is the error.
+    pub screenshot_failed: String,
+    /// `{0}` is the path of the file and `{1}` is the error.
+    pub stats_failed: String,
+    /// `{0}` is the error.
+    pub record_not_saved: String,
+    pub rotate_unavailable: String,
+    pub single_game_only: String,
+    pub saves_dir_unknown: String,
+    /// `{0}` is the name of the slot.
+    pub saved: String,
+    /// `{0}` is the error.
+    pub not_saved: String,
+    /// `{0}` is the depth reached by the search.
+    pub thinking_depth: String,
+    pub player_title: String,
+    pub position_title: String,
+    pub legend_title: String,
+    pub scroll_title: String,
+    pub zoom_title: String,
+    pub board_title: String,
+    pub message_title: String,
+    pub standings_title: String,
+    /// `{0}` is the player to move.
+    pub flips_title: String,
+    /// `{0}` is the number of stones flipped.
+    pub flips_count: String,
+    pub flips_by_direction: String,
+    pub flips_by_player: String,
+    pub not_legal_move: String,
+    /// `{0}` and `{1}` are the coordinates.
+    pub hud_position: String,
+    /// `{0}` is the scroll.
+    pub hud_scroll: String,
+    /// `{0}` is the zoom level.
+    pub hud_zoom: String,
+    /// `{0}` is the key to show the full HUD.
+    pub hud_full: String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            quit_question: "Are you sure to quit?".to_owned(),
+            init_question: "Are you sure to initialize?".to_owned(),
+            swap_question:
+                "Are you sure to take over the seat of {0} with its stones, leaving {1} to its player?"
+                    .to_owned(),
+            slot_question: "Name of the slot to save the game in:".to_owned(),
+            slot_hint: "Save [Enter] / Cancel [Esc] (up to {0} letters, digits, - and _)".to_owned(),
+            overwrite_question: "Are you sure to overwrite the game saved in the slot {0}?"
+                .to_owned(),
+            size_question: "Size of the board:".to_owned(),
+            size_entry: "Range {0} ({1} positions)".to_owned(),
+            size_hint: "Move ▲/▼ [{0}/{1}], Start [{2}], Quit [{3}]".to_owned(),
+            yes_no: "Y / [n]".to_owned(),
+            yes_no_save: "Y / [n] / s (save the match to resume it)".to_owned(),
+            terminal_too_small: "Terminal too small (need at least {0}×{1})".to_owned(),
+            status_turn: "Turn {0} — Player-{1} to move".to_owned(),
+            status_pass: "Turn {0} — Player-{1} has to pass".to_owned(),
+            status_history: "Viewing history (turn {0}/{1})".to_owned(),
+            status_puzzle: "Puzzle — Player-{0} has moved".to_owned(),
+            status_won: "Game over — Player-{0} wins".to_owned(),
+            status_drawn: "Game over — drawn".to_owned(),
+            leader: "Leader: {0} (+{1})".to_owned(),
+            tied: "Tied".to_owned(),
+            thinking: "Player-{0} is thinking… {1}".to_owned(),
+            cannot_select: "Player-{0}: You cannot select ({1}, {2}).".to_owned(),
+            flipped: "Player-{0}: +{1}".to_owned(),
+            coach: "A move at ({0}, {1}) would have flipped {2} more.".to_owned(),
+            skipped:
+                "Player-{0}: Your turn is skipped, you cannot select any position. Press [{1}]."
+                    .to_owned(),
+            auto_skipped: "{0} skipped, no position can be selected.".to_owned(),
+            and_separator: " and ".to_owned(),
+            game_finished: "Game is finished! Final Score is {0}. Flips are {1}.".to_owned(),
+            game_finished_by_limit:
+                "Game is finished by the limit of {0} turns! Final Score is {1}. Flips are {2}."
+                    .to_owned(),
+            think_totals: "Thinking times are {0}.".to_owned(),
+            winner: "Winner is {0}.".to_owned(),
+            winner_by_tiebreak: "Winner is {0} by the {1} tiebreak.".to_owned(),
+            drawn: "Game is drawn.".to_owned(),
+            new_record: "New record! {0} stones at range {1}.".to_owned(),
+            message_log_title: "Message Log (newest first), Scroll ▼/▲ [{0}/{1}], Close [{2}]"
+                .to_owned(),
+            message_log_turn: "[Turn {0}]".to_owned(),
+            records_not_kept: "Best scores are not kept: {0}".to_owned(),
+            event_log_closed: "Events are no longer written to {0}: {1}".to_owned(),
+            error: "Error: {0}".to_owned(),
+            player: "Player-{0}".to_owned(),
+            list_separator: ", ".to_owned(),
+            puzzle_title: "{0}:".to_owned(),
+            puzzle_goal: "Find the best move of Player-{0}.".to_owned(),
+            puzzle_solved: "Solved! ({0}, {1}) is the best move.".to_owned(),
+            puzzle_failed: "({0}, {1}) is not the best move. Retry [{2}] or reveal the solution [{3}].".to_owned(),
+            solution: "Solution: ({0}, {1}).".to_owned(),
+            solution_with_others: "Solution: ({0}, {1}), also accepted: {2}.".to_owned(),
+            game_number: "Game {0} of {1}: Player-{2} moves first.".to_owned(),
+            next_game: "Press [{0}] for game {1}.".to_owned(),
+            match_won: "Match is won by {0}!".to_owned(),
+            match_drawn_between: "Match is drawn between {0} and {1}.".to_owned(),
+            match_drawn: "Match is drawn.".to_owned(),
+            match_saved: "Match is saved to {0} (resume it with --resume-match).".to_owned(),
+            match_not_saved: "Match cannot be saved: {0}".to_owned(),
+            seats_swapped: "Seats are swapped: the player of {1} now plays {0}, and the player of {0} moves as {1}.".to_owned(),
+            swap_offer: "Player-{0} may take over the seat of Player-{1} by [{2}].".to_owned(),
+            ai_paused: "Computer players are paused. Resume [{0}].".to_owned(),
+            ai_resumed: "Computer players are resumed.".to_owned(),
+            joined: "Player-{0} joined.".to_owned(),
+            left: "Player-{0} left, the game is paused until the player rejoins.".to_owned(),
+            connection_lost: "Connection to the host is lost.".to_owned(),
+            out_of_sync: "Board is out of sync with the host.".to_owned(),
+            move_not_sent: "Move cannot be sent to the host.".to_owned(),
+            game_paused: "Game is paused.".to_owned(),
+            game_paused_waiting: "Game is paused, waiting for {0}.".to_owned(),
+            not_in_network: "It is not available in network games.".to_owned(),
+            suggested: "Suggested: ({0}, {1}) flips {2}".to_owned(),
+            no_suggestion: "No position can be suggested.".to_owned(),
+            score_breakdown: "{0} = {1} ({2} stones + {3} territory)".to_owned(),
+            browsing_turn: "Turn {0} of {1}. Press [{2}] to play from here.".to_owned(),
+            nothing_to_undo: "There is no move to undo.".to_owned(),
+            undone: "Moves are undone to turn {0}, and {1} moves can be redone by [{2}].".to_owned(),
+            nothing_to_redo: "There is no move to redo.".to_owned(),
+            branch: "{0} ({1}/{2}), {3} moves.".to_owned(),
+            branch_diverging: "{0} ({1}/{2}), {3} moves, diverging at turn {4}.".to_owned(),
+            annotating: "Annotating moves... {0}/{1}".to_owned(),
+            annotated: "Moves are annotated: {0} best, {1} good, {2} inaccuracies (?), and {3} blunders (??).".to_owned(),
+            annotation: "Move {0} of Player-{1} is {2} ({3}), {4} stones fewer than the best move.".to_owned(),
+            annotation_with_best: "Move {0} of Player-{1} is {2} ({3}), {4} stones fewer than the best move ({5}, {6}).".to_owned(),
+            quality_best: "best".to_owned(),
+            quality_good: "good".to_owned(),
+            quality_inaccuracy: "inaccuracy".to_owned(),
+            quality_blunder: "blunder".to_owned(),
+            code: "Code: {0}".to_owned(),
+            dumped: "Board is dumped to {0}.".to_owned(),
+            dump_failed: "Failed to dump board to {0}: {1}".to_owned(),
+            exported: "Game is exported to {0}.".to_owned(),
+            export_failed: "Failed to export the game to {0}: {1}".to_owned(),
+            screenshot_failed: "Failed to write the screenshot to {0}: {1}".to_owned(),
+            stats_failed: "Failed to write statistics to {0}: {1}".to_owned(),
+            record_not_saved: "New record, which is not saved: {0}.".to_owned(),
+            rotate_unavailable: "The board can be turned upside down only in the ascii style.".to_owned(),
+            single_game_only: "Only a single local game can be saved.".to_owned(),
+            saves_dir_unknown: "Game cannot be saved: the directory of the slots is unknown.".to_owned(),
+            saved: "Game is saved in the slot {0}, which is restored by --load-slot {0}.".to_owned(),
+            not_saved: "Game cannot be saved: {0}".to_owned(),
+            thinking_depth: "depth {0}".to_owned(),
+            player_title: "Player".to_owned(),
+            position_title: "Position".to_owned(),
+            legend_title: "Legend".to_owned(),
+            scroll_title: "Scroll".to_owned(),
+            zoom_title: "Zoom".to_owned(),
+            board_title: "Board".to_owned(),
+            message_title: "Message".to_owned(),
+            standings_title: "Standings".to_owned(),
+            flips_title: "Flips of Player-{0}".to_owned(),
+            flips_count: "Flips {0}:".to_owned(),
+            flips_by_direction: "By direction:".to_owned(),
+            flips_by_player: "By player:".to_owned(),
+            not_legal_move: "Not a legal move".to_owned(),
+            hud_position: "Position {0}, {1}".to_owned(),
+            hud_scroll: "Scroll {0}".to_owned(),
+            hud_zoom: "Zoom {0}".to_owned(),
+            hud_full: "Full HUD [{0}]".to_owned(),
+        }
+    }
+}
+
+impl Messages {
+    /// Messages of `lang`, which is `en`, a built-in language like `ja`, or a locale file ending with `.toml`.
+    pub fn load(lang: &str) -> Result<Self> {
+        if lang.ends_with(".toml") {
+            let path = Path::new(lang);
+            let text = fs::read_to_string(path).map_err(TriversiError::io(path))?;
+            return toml::from_str(&text)
+                .with_context(|| format!("failed to parse messages in {}", path.display()));
+        }
+        if lang == "en" {
+            return Ok(Self::default());
+        }
+        let Some((_, text)) = LOCALES.iter().find(|(name, _)| *name == lang) else {
+            return Err(TriversiError::UnknownLanguage(lang.to_owned()).into());
+        };
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Messages of the language of `LANG`, e.g., `ja` of `ja_JP.UTF-8`,
+    /// which are in English if the language is not built in.
+    pub fn from_env() -> Self {
+        env::var("LANG")
+            .ok()
+            .and_then(|lang| {
+                let lang = lang.split(['_', '.']).next().unwrap_or_default().to_owned();
+                LOCALES
+                    .iter()
+                    .any(|(name, _)| *name == lang)
+                    .then(|| Self::load(&lang).ok())
+                    .flatten()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// `template` where `{0}`, `{1}`, ... are replaced by the corresponding `args`,
+/// and braces not enclosing the number of an argument are kept as they are.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let arg = rest.find('}').and_then(|end| {
+            let index = rest[1..end].parse::<usize>().ok()?;
+            Some((args.get(index)?, end))
+        });
+        match arg {
+            Some((arg, end)) => {
+                text.push_str(&arg.to_string());
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_locales_translate_every_message() {
+        let fields = toml::Table::try_from(Messages::default()).unwrap();
+        for (lang, text) in LOCALES {
+            let translated: toml::Table = toml::from_str(text).unwrap();
+            let missing = fields
+                .keys()
+                .filter(|field| !translated.contains_key(*field))
+                .collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{}: {:?}", lang, missing);
+        }
+    }
+
+    #[test]
+    fn values_are_filled_by_their_numbers() {
+        assert_eq!(fill("{1} and {0}", &[&"a", &2]), "2 and a");
+        assert_eq!(fill("{0} {2} {x}", &[&"a"]), "a {2} {x}");
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::app::event_log::{GameEvent, Observer};
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Feedback given on an illegal selection, a skipped turn, and the end of the game.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Notification {
+    /// No feedback besides the message.
+    #[default]
+    Off,
+    /// Bell of the terminal.
+    Bell,
+    /// Border of the board flashing red for a moment.
+    Flash,
+}
+
+/// Time for which the border of the board flashes by the notification.
+const FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// Observer giving the feedback of `Notification` on skipped turns and the end of the game,
+/// which is taken by the terminal.
+#[derive(Debug, Default)]
+pub struct Notifier {
+    notification: Notification,
+    /// Whether the bell is to be rung by the terminal.
+    bell: bool,
+    /// Time until which the border of the board flashes.
+    flash_until: Option<Instant>,
+}
+
+impl Notifier {
+    pub fn new(notification: Notification) -> Self {
+        Self {
+            notification,
+            ..Self::default()
+        }
+    }
+
+    /// Rings the bell or flashes the border of the board by the notification setting.
+    pub fn alert(&mut self) {
+        match self.notification {
+            Notification::Off => (),
+            Notification::Bell => self.bell = true,
+            Notification::Flash => self.flash_until = Some(Instant::now() + FLASH_DURATION),
+        }
+    }
+
+    /// Whether the bell is to be rung, which is reset by this call.
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell)
+    }
+
+    /// Whether the border of the board flashes, which stops when its time has passed.
+    pub fn is_flashing(&self) -> bool {
+        self.flash_until
+            .is_some_and(|flash_until| Instant::now() < flash_until)
+    }
+}
+
+impl Observer for Notifier {
+    fn notify(&mut self, event: &GameEvent) {
+        if matches!(event, GameEvent::Skip { .. } | GameEvent::Finish { .. }) {
+            self.alert();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Player;
+
+    #[test]
+    fn skips_and_finishes_ring_the_bell() {
+        let mut notifier = Notifier::new(Notification::Bell);
+        let counts = vec![4, 4, 4];
+        notifier.notify(&GameEvent::Move {
+            turn: 1,
+            player: Player::Zero,
+            position: (0, 4),
+            flipped: vec![(1, 4)],
+            counts: counts.clone(),
+            next_player: Player::One,
+        });
+        assert!(!notifier.take_bell());
+        notifier.notify(&GameEvent::Skip {
+            turn: 1,
+            player: Player::One,
+            counts: counts.clone(),
+        });
+        assert!(notifier.take_bell());
+        assert!(!notifier.take_bell());
+        notifier.notify(&GameEvent::Finish {
+            turn: 1,
+            winner: None,
+            counts,
+        });
+        assert!(notifier.take_bell());
+        assert!(!notifier.is_flashing());
+    }
+}
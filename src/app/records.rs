@@ -0,0 +1,79 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Best scores of single games for each range of the board, which are kept across runs.
+
+use crate::error::TriversiError;
+use anyhow::{Context, Result};
+use getset::{CopyGetters, Getters};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, CopyGetters, Getters, Serialize, Deserialize)]
+pub struct Record {
+    /// Number of stones of a player at the end of a game.
+    #[getset(get_copy = "pub")]
+    count: u64,
+    /// Day when the record was set as `YYYY-MM-DD`.
+    #[getset(get = "pub")]
+    date: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Records {
+    /// Records keyed by the range of the board.
+    records: BTreeMap<usize, Record>,
+}
+
+impl Records {
+    /// `$XDG_DATA_HOME/triversi/records.json` or `$HOME/.local/share/triversi/records.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+            })
+            .map(|dir| dir.join("triversi").join("records.json"))
+    }
+
+    /// Loads records saved by `save`, where a missing file has no records.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path).map_err(TriversiError::io(path))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse records in {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(TriversiError::io(dir))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).map_err(TriversiError::io(path))?;
+        Ok(())
+    }
+
+    pub fn get(&self, range: usize) -> Option<&Record> {
+        self.records.get(&range)
+    }
+
+    /// Replaces the record of `range` if `count` beats it, which returns whether it is beaten.
+    pub fn update(&mut self, range: usize, count: u64, date: String) -> bool {
+        if self
+            .records
+            .get(&range)
+            .is_some_and(|record| record.count >= count)
+        {
+            return false;
+        }
+        self.records.insert(range, Record { count, date });
+        true
+    }
+}
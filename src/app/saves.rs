@@ -0,0 +1,125 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Games saved in named slots, which are JSON files in the directory of the slots.
+
+use crate::board::history::Record;
+use crate::config::Config;
+use crate::error::TriversiError;
+use anyhow::{Context, Result};
+use getset::{CopyGetters, Getters};
+use serde_derive::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest name of a slot.
+pub const MAX_SLOT_NAME_LEN: usize = 32;
+
+#[derive(Clone, Debug, CopyGetters, Getters, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// Date when the game was saved, as `YYYY-MM-DD`.
+    #[getset(get = "pub")]
+    date: String,
+    /// Configuration the game was started with, which is restored under the command line options.
+    #[getset(get = "pub")]
+    config: Config,
+    #[getset(get = "pub")]
+    record: Record,
+    /// Turn being played or browsed in the history when the game was saved.
+    #[getset(get_copy = "pub")]
+    turn: usize,
+}
+
+impl SavedGame {
+    pub fn new(date: String, config: Config, record: Record, turn: usize) -> Self {
+        Self {
+            date,
+            config,
+            record,
+            turn,
+        }
+    }
+
+    /// `$XDG_DATA_HOME/triversi/saves` or `$HOME/.local/share/triversi/saves`.
+    pub fn default_dir() -> Option<PathBuf> {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+            })
+            .map(|dir| dir.join("triversi").join("saves"))
+    }
+
+    /// Checks that `name` is made of at most `MAX_SLOT_NAME_LEN` letters, digits, `-`, and `_`,
+    /// which keeps the file of the slot inside the directory.
+    pub fn validate_name(name: &str) -> Result<(), TriversiError> {
+        if name.is_empty() || name.len() > MAX_SLOT_NAME_LEN || !name.chars().all(is_name_char) {
+            return Err(TriversiError::InvalidSlotName(name.to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{}.json", name))
+    }
+
+    pub fn load(dir: &Path, name: &str) -> Result<Self> {
+        Self::validate_name(name)?;
+        let path = Self::path(dir, name);
+        if !path.exists() {
+            return Err(
+                TriversiError::UnknownSlot(name.to_owned(), dir.display().to_string()).into(),
+            );
+        }
+        let text = fs::read_to_string(&path).map_err(TriversiError::io(&path))?;
+        let saved_game = serde_json::from_str::<Self>(&text)
+            .with_context(|| format!("failed to parse a saved game in {}", path.display()))?;
+        saved_game
+            .record
+            .validate()
+            .with_context(|| format!("invalid record in {}", path.display()))?;
+        Ok(saved_game)
+    }
+
+    /// Writes the game to the slot of `name` in `dir`, replacing the game saved there.
+    pub fn save(&self, dir: &Path, name: &str) -> Result<()> {
+        Self::validate_name(name)?;
+        fs::create_dir_all(dir).map_err(TriversiError::io(dir))?;
+        let path = Self::path(dir, name);
+        fs::write(&path, serde_json::to_string_pretty(self)?).map_err(TriversiError::io(&path))?;
+        Ok(())
+    }
+
+    /// Games saved in `dir` with the names of their slots in the order of names,
+    /// where a missing directory has no slots.
+    pub fn list(dir: &Path) -> Result<Vec<(String, Self)>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = fs::read_dir(dir)
+            .map_err(TriversiError::io(dir))?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_owned())
+            })
+            .filter(|name| Self::validate_name(name).is_ok())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| Ok((name.clone(), Self::load(dir, &name)?)))
+            .collect()
+    }
+}
+
+/// Whether `c` can be used in the name of a slot.
+pub fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
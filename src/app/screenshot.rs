@@ -0,0 +1,125 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Text of a screen rendered into a buffer instead of the terminal.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use tui::buffer::{Buffer, Cell};
+use tui::style::{Color, Modifier};
+use unicode_width::UnicodeWidthStr;
+
+/// Modifiers and their SGR parameters.
+const MODIFIER_CODES: &[(Modifier, u8)] = &[
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+
+/// Whether a screenshot at `path` is written with ANSI escape sequences of colors,
+/// which is chosen by the extension `.ans` or `.ansi`.
+pub fn is_ansi(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "ans" || extension == "ansi")
+}
+
+/// Rows of the symbols of `buffer`, where cells hidden by wide symbols are skipped
+/// and trailing spaces are removed.
+pub fn to_plain(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for row in rows(buffer) {
+        let line = row
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect::<String>();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Rows of the symbols of `buffer` with SGR sequences of their colors and modifiers,
+/// where the style is reset at the end of each row.
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for row in rows(buffer) {
+        let mut style = None;
+        for cell in row {
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                write!(text, "\x1b[{}m", sgr_parameters(cell)).unwrap();
+                style = Some(cell_style);
+            }
+            text.push_str(&cell.symbol);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// Cells shown in each row, skipping the cells covered by the preceding wide symbols.
+fn rows(buffer: &Buffer) -> Vec<Vec<&Cell>> {
+    buffer
+        .content()
+        .chunks(buffer.area.width.max(1) as usize)
+        .map(|cells| {
+            let mut skip = 0;
+            cells
+                .iter()
+                .filter(|cell| {
+                    let shown = skip == 0;
+                    skip = skip.max(cell.symbol.width()).saturating_sub(1);
+                    shown
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Parameters of the SGR sequence which resets the style and sets that of `cell`.
+fn sgr_parameters(cell: &Cell) -> String {
+    let mut parameters = vec!["0".to_owned()];
+    for (modifier, code) in MODIFIER_CODES {
+        if cell.modifier.contains(*modifier) {
+            parameters.push(code.to_string());
+        }
+    }
+    parameters.extend(color_parameter(cell.fg, 30));
+    parameters.extend(color_parameter(cell.bg, 40));
+    parameters.join(";")
+}
+
+/// Parameter of `color`, where `base` is 30 for the foreground and 40 for the background,
+/// or `None` for the default color.
+fn color_parameter(color: Color, base: u8) -> Option<String> {
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => base,
+        Color::Red => base + 1,
+        Color::Green => base + 2,
+        Color::Yellow => base + 3,
+        Color::Blue => base + 4,
+        Color::Magenta => base + 5,
+        Color::Cyan => base + 6,
+        Color::Gray => base + 7,
+        Color::DarkGray => base + 60,
+        Color::LightRed => base + 61,
+        Color::LightGreen => base + 62,
+        Color::LightYellow => base + 63,
+        Color::LightBlue => base + 64,
+        Color::LightMagenta => base + 65,
+        Color::LightCyan => base + 66,
+        Color::White => base + 67,
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+        Color::Indexed(i) => return Some(format!("{};5;{}", base + 8, i)),
+    };
+    Some(code.to_string())
+}
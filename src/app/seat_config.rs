@@ -0,0 +1,130 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::StrategyKind;
+use crate::board::{Player, PLAYERS};
+use crate::error::TriversiError;
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// Who selects positions of a player.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Controller {
+    Human,
+    Ai(StrategyKind),
+    /// Played on another terminal in a network game.
+    Remote,
+}
+
+impl Controller {
+    pub fn is_ai(&self) -> bool {
+        matches!(self, Controller::Ai(_))
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, Controller::Remote)
+    }
+}
+
+/// Controllers of the three seats, where all seats are human by default.
+#[derive(Clone, Debug)]
+pub struct SeatConfig {
+    controllers: HashMap<Player, Controller>,
+}
+
+impl Default for SeatConfig {
+    fn default() -> Self {
+        Self {
+            controllers: PLAYERS
+                .iter()
+                .map(|player| (*player, Controller::Human))
+                .collect(),
+        }
+    }
+}
+
+impl SeatConfig {
+    /// The human plays the seat `seat` (1, 2, or 3) and the others are played by `strategy_kind`.
+    pub fn try_with_human_seat(
+        seat: usize,
+        strategy_kind: StrategyKind,
+    ) -> Result<Self, TriversiError> {
+        let human = Player::from_seat(seat).ok_or(TriversiError::InvalidSeat(seat))?;
+        Ok(Self {
+            controllers: PLAYERS
+                .iter()
+                .map(|player| {
+                    if *player == human {
+                        (*player, Controller::Human)
+                    } else {
+                        (*player, Controller::Ai(strategy_kind))
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Every seat is played by `strategy_kind`.
+    pub fn with_ai(strategy_kind: StrategyKind) -> Self {
+        Self {
+            controllers: PLAYERS
+                .iter()
+                .map(|player| (*player, Controller::Ai(strategy_kind)))
+                .collect(),
+        }
+    }
+
+    /// The human plays `player` on this terminal and the others are played on other terminals.
+    pub fn with_local_player(player: Player) -> Self {
+        Self {
+            controllers: PLAYERS
+                .iter()
+                .map(|other| {
+                    if *other == player {
+                        (*other, Controller::Human)
+                    } else {
+                        (*other, Controller::Remote)
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn controller(&self, player: Player) -> Controller {
+        *self.controllers.get(&player).unwrap()
+    }
+
+    /// Players played on other terminals in `PLAYERS` order.
+    pub fn remote_players(&self) -> Vec<Player> {
+        PLAYERS
+            .iter()
+            .copied()
+            .filter(|player| self.controller(*player).is_remote())
+            .collect()
+    }
+}
+
+/// Parses controllers of the three seats like `human,greedy,remote` (delimiters are ',').
+impl TryFrom<String> for SeatConfig {
+    type Error = TriversiError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let controllers = s
+            .split(',')
+            .map(|controller| match controller.trim() {
+                "human" => Some(Controller::Human),
+                "remote" => Some(Controller::Remote),
+                strategy => StrategyKind::from_str(strategy, false)
+                    .ok()
+                    .map(Controller::Ai),
+            })
+            .collect::<Option<Vec<_>>>();
+        match controllers {
+            Some(controllers) if controllers.len() == PLAYERS.len() => Ok(Self {
+                controllers: PLAYERS.iter().copied().zip(controllers).collect(),
+            }),
+            _ => Err(TriversiError::InvalidStringForSeats(s)),
+        }
+    }
+}
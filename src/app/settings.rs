@@ -0,0 +1,113 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::ai::{SearchConfig, StrategyKind};
+use crate::app::key_binding::KeyBinding;
+use crate::app::{ColorConfig, Messages, Notification, SeatConfig};
+use crate::board::{Player, Tiebreak, Variant};
+use crate::config::Config;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Options of `System` given at startup.
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// Directory where plain text dumps of the board are written.
+    pub dump_dir: PathBuf,
+    /// File where statistics of moves are written at the end of the game.
+    pub stats_csv: Option<PathBuf>,
+    /// File or FIFO where events of the game are written as JSON lines.
+    pub event_log: Option<PathBuf>,
+    /// File where the game is archived as text at the end of the game.
+    pub export_game: Option<PathBuf>,
+    /// File where the screen is written when the game is finished, with colors if it is `.ans` or `.ansi`.
+    pub screenshot_on_finish: Option<PathBuf>,
+    /// File where the best scores for each range are kept, or `None` if they are not kept.
+    pub records: Option<PathBuf>,
+    /// Directory of the slots where games are saved by a key, or `None` if it cannot be determined.
+    pub saves_dir: Option<PathBuf>,
+    /// Configuration the game was started with, which is saved with the game.
+    pub config: Config,
+    pub color_config: ColorConfig,
+    pub messages: Messages,
+    pub key_binding: KeyBinding,
+    pub seat_config: SeatConfig,
+    /// Least time between moves of computer players shown on the terminal, or `None` to apply them at once.
+    pub ai_move_interval: Option<Duration>,
+    /// Strategy suggesting a position to human players.
+    pub suggestion_strategy: StrategyKind,
+    pub search_config: SearchConfig,
+    pub tiebreak: Tiebreak,
+    /// Player who moves first in every game.
+    pub first_player: Player,
+    pub variant: Variant,
+    /// Whether turns of players who cannot select any position are skipped without a key.
+    pub auto_skip: bool,
+    /// Whether moves after a turn selected in the history are kept as a branch instead of being discarded.
+    pub keep_branches: bool,
+    /// Number of turns after which the game is finished with the stones on the board, or `None` if there is no limit.
+    pub max_turns: Option<usize>,
+    /// Number of stones by which a position must flip more than the one selected by a human player
+    /// to be told after the move, or `None` if the coach is off.
+    pub coach_margin: Option<u64>,
+    /// Whether the legend of marks and names of players is shown at startup.
+    pub legend: bool,
+    /// Whether lines from a placed stone through the stones flipped by it are drawn at startup.
+    pub capture_lines: bool,
+    /// Whether the flips of the position under the cursor are listed in a side panel at startup.
+    pub flip_panel: bool,
+    /// Whether arrows point from the selectable position under the cursor toward the directions it captures.
+    pub capture_arrows: bool,
+    /// Whether the board is scrolled to keep the cursor inside the board block when the cursor is moved.
+    pub auto_scroll: bool,
+    /// Whether every move and skip is written to the message log in a sentence for screen readers.
+    pub announce: bool,
+    /// Whether the description of the board reads out every row.
+    pub describe_rows: bool,
+    pub notification: Notification,
+    /// Whether the debug panel is shown at startup.
+    pub debug: bool,
+    /// Seed from which all randomized components are derived.
+    pub seed: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            dump_dir: PathBuf::from("."),
+            stats_csv: None,
+            event_log: None,
+            export_game: None,
+            screenshot_on_finish: None,
+            records: None,
+            saves_dir: None,
+            config: Config::default(),
+            color_config: ColorConfig::default(),
+            messages: Messages::default(),
+            key_binding: KeyBinding::default(),
+            seat_config: SeatConfig::default(),
+            ai_move_interval: None,
+            suggestion_strategy: StrategyKind::default(),
+            search_config: SearchConfig::default(),
+            tiebreak: Tiebreak::default(),
+            first_player: Player::default(),
+            variant: Variant::default(),
+            auto_skip: false,
+            keep_branches: false,
+            max_turns: None,
+            coach_margin: None,
+            legend: false,
+            capture_lines: false,
+            flip_panel: false,
+            capture_arrows: true,
+            auto_scroll: true,
+            announce: false,
+            describe_rows: false,
+            notification: Notification::default(),
+            debug: false,
+            seed: 0,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use std::time::{Duration, Instant};
+
+/// Wall-clock time which can be paused.
+#[derive(Clone, Copy, Debug)]
+pub struct Stopwatch {
+    /// Time until the last pause.
+    elapsed: Duration,
+    /// When the stopwatch was started or resumed, or `None` while it is paused.
+    started_at: Option<Instant>,
+}
+
+impl Default for Stopwatch {
+    /// A running stopwatch.
+    fn default() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            started_at: Some(Instant::now()),
+        }
+    }
+}
+
+impl Stopwatch {
+    /// Resets the time to zero and starts the stopwatch.
+    pub fn restart(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(started_at) = self.started_at.take() {
+            self.elapsed += started_at.elapsed();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.started_at.is_none() {
+            self.started_at = Some(Instant::now());
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+            + self
+                .started_at
+                .map(|started_at| started_at.elapsed())
+                .unwrap_or_default()
+    }
+}
+
+/// `duration` in a short form like `4m12s`, where times under a minute are written to a tenth of a second.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{:.1}s", duration.as_secs_f64()),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m{:02}s", secs / 3600, secs / 60 % 60, secs % 60),
+    }
+}
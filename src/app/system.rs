@@ -3,29 +3,96 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use crate::app::board_display::BoardDisplay;
-use crate::app::key_binding;
-use crate::app::ColorConfig;
-use crate::board::{Availables, Board, History, Player, PLAYERS};
+use crate::app::ai_worker::AiWorker;
+use crate::app::annotator::{Annotation, Annotator, Progress, Quality};
+use crate::app::board_display::{BoardDisplay, CaptureArrows, CaptureLines, Fog, MoveDiff};
+use crate::app::description;
+use crate::app::event_log::{self, EventLog, GameEvent, Observer};
+use crate::app::key_binding::Action;
+use crate::app::match_state::MatchState;
+use crate::app::message_log::MessageLog;
+use crate::app::messages::fill;
+use crate::app::notification::Notifier;
+use crate::app::records::Records;
+use crate::app::saves::{self, SavedGame, MAX_SLOT_NAME_LEN};
+use crate::app::screenshot;
+use crate::app::stopwatch::{format_duration, Stopwatch};
+use crate::app::{Controller, Settings};
+use crate::board::archive;
+use crate::board::{
+    sorted_moves, to_fen, Archive, Availables, Board, Count, History, Move, Player, Position,
+    Scoring, Variant, DIRECTIONS, MIN_RANGE, PLAYERS, SWAP_TURN,
+};
 use crate::error::TriversiError;
-use getset::CopyGetters;
-use std::fmt::Write as _;
+use crate::net::{NetEvent, Network};
+use crate::puzzle::Puzzle;
+use crate::rng::{self, Component};
+use crate::util::validate_position;
+use clap::ValueEnum;
+use getset::{CopyGetters, Getters};
+use rand::rngs::StdRng;
+use std::cmp;
+use std::collections::VecDeque;
+use std::fmt::{Display, Write as _};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::TryRecvError;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use termion::event::Key;
-use tui::backend::Backend;
+use tui::backend::{Backend, TestBackend};
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
-use tui::terminal::Frame;
+use tui::terminal::{Frame, Terminal};
 use tui::text::{Span, Spans};
-#[cfg(debug_assertions)]
-use tui::widgets::Wrap;
-use tui::widgets::{Block, Borders, Paragraph};
+use tui::widgets::{Block, Borders, Paragraph, Wrap};
 use unicode_width::UnicodeWidthStr;
 
+/// Annotation of seats played by the computer in the player block.
+const AI_MARK: &str = "[AI]";
+
+/// Annotations of seats played on other terminals in the player block.
+const ONLINE_MARK: &str = "[NET]";
+const OFFLINE_MARK: &str = "[OFFLINE]";
+
+/// Annotation of the player moving first in the player block, which is shown until the first move.
+const FIRST_MARK: &str = "[1st]";
+
+/// Sizes of the blocks around the board.
+const GUIDANCE_BOX_HEIGHT: u16 = 4;
+const MESSAGE_BOX_HEIGHT: u16 = 3;
+/// Height of the line above the board telling the phase of the game and whose turn it is.
+const STATUS_STRIP_HEIGHT: u16 = 1;
+/// Height of the line of the status, players, and views, and the line of the message in the compact HUD.
+const COMPACT_HUD_HEIGHT: u16 = 2;
+const POSITION_BOX_WIDTH: u16 = 10;
+const SCROLL_BOX_WIDTH: u16 = 12;
+const ZOOM_BOX_WIDTH: u16 = 6;
+/// Width of the message block below which the terminal is regarded as too small.
+const MIN_MESSAGE_BOX_WIDTH: u16 = 10;
+
+/// Number of ranges listed above and below the selected one in the menu of ranges.
+const SIZE_MENU_SIDE: usize = 3;
+
+/// Frames of the spinner shown while the computer is thinking.
+const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Status {
     Play(Play),
+    MessageLog,
     AskInit,
     AskQuit,
+    /// Confirmation of the second player taking over the seat of the first in the swap variant.
+    AskSwap,
+    /// Name of the slot to save the game in, which is typed by the player.
+    AskSlot,
+    /// Confirmation of replacing the game saved in the slot.
+    AskOverwrite,
+    /// Result of the move selected in a puzzle.
+    PuzzleResult,
+    /// Range of the board chosen from a menu before the game starts.
+    SelectSize,
     Quit,
 }
 
@@ -37,424 +104,2747 @@ pub enum Play {
     Finished,
 }
 
-#[derive(CopyGetters)]
+/// Contents of the debug panel, which cycles in this order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DebugPanel {
+    #[default]
+    Off,
+    History,
+    Availables,
+}
+
+impl DebugPanel {
+    pub fn next(self) -> Self {
+        match self {
+            DebugPanel::Off => DebugPanel::History,
+            DebugPanel::History => DebugPanel::Availables,
+            DebugPanel::Availables => DebugPanel::Off,
+        }
+    }
+}
+
+/// Region the view is centered on by the key, which cycles in this order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ViewFocus {
+    #[default]
+    Center,
+    Home(Player),
+}
+
+impl ViewFocus {
+    pub fn next(self) -> Self {
+        match self {
+            ViewFocus::Center => ViewFocus::Home(Player::Zero),
+            ViewFocus::Home(Player::Zero) => ViewFocus::Home(Player::One),
+            ViewFocus::Home(Player::One) => ViewFocus::Home(Player::Two),
+            ViewFocus::Home(Player::Two) => ViewFocus::Center,
+        }
+    }
+}
+
+#[derive(CopyGetters, Getters)]
 pub struct System<D: BoardDisplay> {
+    #[getset(get_copy = "pub")]
     current_player: Player,
+    /// Player who moves first in the game.
+    first_player: Player,
+    #[getset(get_copy = "pub")]
     current_position: (usize, usize),
+    #[getset(get = "pub")]
     board: Board,
     board_display: D,
+    /// Region on which the view was centered last.
+    view_focus: ViewFocus,
+    /// Area of the board block in the last frame.
+    board_rect: Rect,
+    #[getset(get = "pub")]
     availables: Availables,
+    #[getset(get = "pub")]
     history: History,
+    #[getset(get = "pub")]
+    skips: Count,
+    /// Time the current player is spending on the turn,
+    /// which runs only while the turn is shown and is paused by the history and questions.
+    think_stopwatch: Stopwatch,
     #[getset(get_copy = "pub")]
     current_status: Status,
     previous_status: Status,
+    #[getset(get = "pub")]
     message: String,
     message_color: Color,
-    color_config: ColorConfig,
-    #[cfg(debug_assertions)]
+    /// Warning following the message in the warning color, e.g., a better move told by the coach.
+    #[getset(get = "pub")]
+    message_warning: String,
+    /// Whether the current position is suggested, which is reset by the next key.
+    is_suggested: bool,
+    /// Computation of the computer player whose turn it is.
+    ai_worker: Option<AiWorker>,
+    /// Whether computer players are paused by a key, which does not stop human players.
+    ai_paused: bool,
+    /// Time the last position selected by the computer was applied, which paces the moves.
+    last_ai_move: Instant,
+    /// Evaluation of recorded moves, whose result is kept until the record changes.
+    annotator: Option<Annotator>,
+    annotations: Option<Vec<Annotation>>,
+    network: Option<Network>,
+    /// Puzzle being solved, whose position is restored by initializing.
+    puzzle: Option<Puzzle>,
+    /// Board given by a code, which is restored by initializing instead of the initial board.
+    start_board: Option<Board>,
+    /// Results of the games of the match being played.
+    match_state: Option<MatchState>,
+    /// Best scores for each range, or `None` if they are not kept.
+    records: Option<Records>,
+    /// File where the unfinished match is saved at quitting.
+    saved_match: Option<PathBuf>,
+    /// Name of the slot being typed to save the game in.
+    slot_name: String,
+    /// Range selected in the menu of ranges.
+    size_selection: usize,
+    event_log: Option<EventLog>,
+    /// Receivers of every event besides the message, the notifier, the host, and the event log.
+    observers: Vec<Box<dyn Observer>>,
+    notifier: Notifier,
+    /// Moves received from the network which are applied when the game is shown.
+    network_moves: VecDeque<NetEvent>,
+    /// Number of ticks, which animates the spinner.
+    tick_count: usize,
+    /// Whether the message is a banner of auto-skipped players.
+    skip_banner: bool,
+    /// Players skipped automatically since the last move, who are shown in the banner.
+    auto_skipped: Vec<Player>,
+    /// Better move told by the coach for the move being emitted.
+    better_move: Option<((usize, usize), u64)>,
+    #[getset(get = "pub")]
+    message_log: MessageLog,
+    /// Number of the newest entries scrolled out of the message log overlay.
+    message_log_scroll: usize,
+    settings: Settings,
+    /// Whether the legend of marks and names of players is shown.
+    legend_visibility: bool,
+    compact_hud: bool,
+    /// Whether lines from a placed stone through the stones flipped by it are drawn.
+    capture_line_visibility: bool,
+    /// Whether the flips of the position under the cursor are listed in the side panel.
+    flip_panel_visibility: bool,
+    /// Whether the history shows the stones changed by the browsed move over the others dimmed,
+    /// which is kept while stepping through turns.
+    diff_visibility: bool,
+    /// Whether stones of the other players are hidden in the fog except those the current player can capture,
+    /// which changes only the display of the board.
+    fog_visibility: bool,
+    /// Capture lines of the last move on the current board.
+    last_capture: Option<CaptureLines>,
+    #[getset(get_copy = "pub")]
+    debug_panel: DebugPanel,
     debug_information: String,
 }
 
 impl<D: BoardDisplay> System<D> {
-    pub fn try_new(board: Board, board_display: D) -> Result<Self, TriversiError> {
+    pub fn try_new(
+        board: Board,
+        board_display: D,
+        settings: Settings,
+        observers: Vec<Box<dyn Observer>>,
+    ) -> Result<Self, TriversiError> {
         let mut availables = Availables::default();
         board.update_availables(&mut availables);
-        Ok(Self {
-            history: History::new(board.clone()),
+        let mut history = History::new(board.clone(), settings.first_player);
+        history.set_seed(settings.seed);
+        history.set_metadata(
+            settings.variant,
+            PLAYERS
+                .iter()
+                .map(|player| board_display.player_name(*player).to_owned())
+                .collect(),
+            PLAYERS
+                .iter()
+                .map(|player| board_display.player_mark(*player))
+                .collect(),
+        );
+        history.set_keeps_branches(settings.keep_branches);
+        let event_log = settings.event_log.as_deref().map(EventLog::open);
+        let records = settings.records.as_deref().map(Records::load);
+        let mut system = Self {
+            history,
+            skips: Count::default(),
+            think_stopwatch: Stopwatch::default(),
             board_display,
-            current_player: Player::default(),
+            view_focus: ViewFocus::default(),
+            board_rect: Rect::default(),
+            current_player: settings.first_player,
+            first_player: settings.first_player,
             current_position: board.initial_position(),
             board,
             message: String::new(),
             message_color: Color::Reset,
+            message_warning: String::new(),
+            is_suggested: false,
+            ai_worker: None,
+            ai_paused: false,
+            last_ai_move: Instant::now(),
+            annotator: None,
+            annotations: None,
+            network: None,
+            puzzle: None,
+            start_board: None,
+            match_state: None,
+            records: None,
+            saved_match: None,
+            slot_name: String::new(),
+            size_selection: MIN_RANGE,
+            event_log: None,
+            observers,
+            notifier: Notifier::new(settings.notification),
+            network_moves: VecDeque::new(),
+            tick_count: 0,
+            skip_banner: false,
+            auto_skipped: Vec::new(),
+            better_move: None,
+            message_log: MessageLog::default(),
+            message_log_scroll: 0,
             current_status: Status::Play(Play::Turn),
             previous_status: Status::Play(Play::Turn),
-            color_config: ColorConfig::default(),
+            legend_visibility: settings.legend,
+            compact_hud: false,
+            capture_line_visibility: settings.capture_lines,
+            flip_panel_visibility: settings.flip_panel,
+            diff_visibility: false,
+            fog_visibility: false,
+            last_capture: None,
+            debug_panel: if settings.debug {
+                DebugPanel::History
+            } else {
+                DebugPanel::Off
+            },
+            settings,
             availables,
-            #[cfg(debug_assertions)]
             debug_information: String::new(),
-        })
+        };
+        match event_log {
+            Some(Ok(event_log)) => system.event_log = Some(event_log),
+            Some(Err(err)) => system.warn_event_log(err),
+            None => (),
+        }
+        match records {
+            Some(Ok(records)) => system.records = Some(records),
+            Some(Err(err)) => {
+                system.message_color = system.settings.color_config.error_color();
+                let text = fill(
+                    &system.settings.messages.records_not_kept,
+                    &[&format!("{:#}", err)],
+                );
+                write!(system.message, " {}", text).unwrap();
+                system.log_message();
+            }
+            None => (),
+        }
+        system.emit_init_event();
+        system.start_ai_turn();
+        Ok(system)
     }
 
     fn init(&mut self) {
-        self.board.init();
-        self.current_player = Player::default();
+        match &self.puzzle {
+            Some(puzzle) => {
+                self.board = puzzle.board().clone();
+                self.current_player = puzzle.player();
+            }
+            None => {
+                match &self.start_board {
+                    Some(start_board) => self.board = start_board.clone(),
+                    None => self.board.init(),
+                }
+                self.current_player = self.first_player;
+            }
+        }
         self.clear_message();
         self.current_position = self.board.initial_position();
         self.current_status = Status::Play(Play::Turn);
         self.previous_status = Status::Play(Play::Turn);
         self.update_available_list();
-        self.history.init(self.board.clone());
+        self.history.init(self.board.clone(), self.current_player);
+        self.last_capture = None;
+        self.skips.reset();
+        self.think_stopwatch.restart();
+        self.message_log.clear();
+        self.ai_worker = None;
+        self.annotator = None;
+        self.annotations = None;
+        self.emit_init_event();
+        if let Some(puzzle) = &self.puzzle {
+            let title = puzzle.title().clone();
+            self.message_color = self.settings.color_config.player(self.current_player);
+            let messages = &self.settings.messages;
+            if !title.is_empty() {
+                write!(self.message, " {}", fill(&messages.puzzle_title, &[&title])).unwrap();
+            }
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &messages.puzzle_goal,
+                    &[&self.board_display.player_name(self.current_player)]
+                )
+            )
+            .unwrap();
+        }
+        if let Some(match_state) = &self.match_state {
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.game_number,
+                    &[
+                        &match_state.game_number(),
+                        &match_state.games(),
+                        &self.board_display.player_name(self.current_player)
+                    ]
+                )
+            )
+            .unwrap();
+        }
+    }
+
+    fn emit_init_event(&mut self) {
+        self.emit(GameEvent::Init {
+            range: self.board.range(),
+            counts: event_log::counts(&self.board),
+        });
+    }
+
+    fn emit_history_event(&mut self) {
+        self.emit(GameEvent::History {
+            turn: self.history.current_turn(),
+            counts: event_log::counts(&self.board),
+        });
+    }
+
+    /// Registers `observer` to be told every following event, which is first told an init event
+    /// of the current board so that it knows the board the events start from.
+    pub fn add_observer(&mut self, mut observer: impl Observer + 'static) {
+        observer.notify(&GameEvent::Init {
+            range: self.board.range(),
+            counts: event_log::counts(&self.board),
+        });
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Tells `event` to the message, the notifier, the host of a network game, and the observers,
+    /// announces it in the message log if enabled, and writes it to the event log,
+    /// which is closed with a warning if it fails.
+    fn emit(&mut self, event: GameEvent) {
+        self.write_event_message(&event);
+        self.notifier.notify(&event);
+        if let Some(Network::Host(host)) = &mut self.network {
+            host.notify(&event);
+        }
+        for observer in &mut self.observers {
+            observer.notify(&event);
+        }
+        if self.settings.announce {
+            if let Some(text) = description::announce(&event, &self.player_names()) {
+                self.message_log
+                    .push(self.history.current_turn(), Color::Reset, &text);
+            }
+        }
+        let Some(event_log) = &mut self.event_log else {
+            return;
+        };
+        if let Err(err) = event_log.write(&event) {
+            self.event_log = None;
+            self.warn_event_log(err);
+        }
+    }
+
+    fn warn_event_log(&mut self, err: io::Error) {
+        let path = self.settings.event_log.clone().unwrap_or_default();
+        self.clear_message();
+        self.message_color = self.settings.color_config.error_color();
+        write!(
+            self.message,
+            " {}",
+            fill(
+                &self.settings.messages.event_log_closed,
+                &[&path.display(), &err]
+            )
+        )
+        .unwrap();
+        self.log_message();
     }
 
     fn clear_message(&mut self) {
         self.message.clear();
         self.message_color = Color::Reset;
+        self.message_warning.clear();
+    }
+
+    /// Records the current message to the message log, followed by its warning in its own line.
+    fn log_message(&mut self) {
+        self.message_log.push(
+            self.history.current_turn(),
+            self.message_color,
+            self.message.trim_start(),
+        );
+        if !self.message_warning.is_empty() {
+            self.message_log.push(
+                self.history.current_turn(),
+                self.settings.color_config.warning_color(),
+                self.message_warning.trim_start(),
+            );
+        }
     }
 
     fn update_status(&mut self, status: Status) {
         self.previous_status = self.current_status;
         self.current_status = status;
+        match status {
+            Status::Play(Play::Turn | Play::Skipped) => self.think_stopwatch.resume(),
+            _ => self.think_stopwatch.pause(),
+        }
     }
 
     fn update_available_list(&mut self) {
         self.board.update_availables(&mut self.availables);
     }
 
-    fn set_player(&mut self) {
-        for position in self
-            .availables
-            .get(&self.current_player)
-            .unwrap()
-            .get(&self.current_position)
-            .unwrap()
-        {
+    /// Places a stone of the current player at the current position and flips stones,
+    /// or returns an error without changing the board if the position is not selectable.
+    fn set_player(&mut self) -> Result<(), TriversiError> {
+        let Some(positions) = self.availables[self.current_player].get(&self.current_position)
+        else {
+            return Err(TriversiError::IllegalMove {
+                player: self
+                    .board_display
+                    .player_name(self.current_player)
+                    .to_owned(),
+                position: self.current_position,
+            });
+        };
+        for position in positions {
             self.board.set_player(*position, Some(self.current_player));
         }
         self.update_available_list();
+        Ok(())
+    }
+
+    /// Shows an error of an assumption broken inside the game in the message instead of panicking.
+    fn show_error(&mut self, err: TriversiError) {
+        self.clear_message();
+        self.message_color = self.settings.color_config.error_color();
+        write!(
+            self.message,
+            " {}",
+            fill(&self.settings.messages.error, &[&err])
+        )
+        .unwrap();
+        self.log_message();
     }
 
     pub fn transition(&mut self, key: Key) {
+        // Quits at once from any screen, e.g., to stop a demo or a scripted run.
+        if self.settings.key_binding.action(key) == Some(Action::ForceQuit) {
+            self.update_status(Status::Quit);
+            return;
+        }
+        self.is_suggested = false;
+        if self.skip_banner {
+            self.skip_banner = false;
+            self.clear_message();
+        }
         match self.current_status {
-            Status::Play(play) => self.play(key, play),
+            Status::Play(play) => {
+                let position = self.current_position;
+                self.play(key, play);
+                if self.settings.auto_scroll && self.current_position != position {
+                    self.board_display.scroll_into_view(
+                        &self.board,
+                        self.current_position,
+                        self.board_rect,
+                    );
+                }
+            }
+            Status::MessageLog => self.browse_message_log(key),
             Status::AskInit => self.ask_init(key),
             Status::AskQuit => self.ask_quit(key),
+            Status::AskSwap => self.ask_swap(key),
+            Status::AskSlot => self.ask_slot(key),
+            Status::AskOverwrite => self.ask_overwrite(key),
+            Status::PuzzleResult => self.puzzle_result(key),
+            Status::SelectSize => self.choose_size(key),
             Status::Quit => unreachable!(),
         }
+        self.start_ai_turn();
     }
 
-    pub fn ui<B: Backend>(&mut self, frame: &mut Frame<B>) {
-        match self.current_status {
-            Status::Play(play) => self.ui_play(frame, play),
-            Status::AskInit => self.ui_ask_init(frame),
-            Status::AskQuit => self.ui_ask_quit(frame),
-            Status::Quit => unreachable!(),
-        }
+    /// Advances the spinner and applies the position selected by the computer if it is ready.
+    pub fn tick(&mut self) {
+        self.tick_count = self.tick_count.wrapping_add(1);
+        self.poll_network();
+        self.poll_ai(false);
+        self.poll_annotator();
     }
 
-    fn play(&mut self, key: Key, play: Play) {
-        match play {
-            Play::Turn => match key {
-                key_binding::key::QUIT => self.update_status(Status::AskQuit),
-                key_binding::key::INIT => self.update_status(Status::AskInit),
-                key_binding::key::FRAME_TOGGLE => self.board_display.toggle_frame_visibility(),
-                key_binding::key::MOVE_LEFT => {
-                    self.board.move_position_left(&mut self.current_position)
-                }
-                key_binding::key::MOVE_RIGHT => {
-                    self.board.move_position_right(&mut self.current_position)
-                }
-                key_binding::key::MOVE_UP => {
-                    self.board.move_position_up(&mut self.current_position)
-                }
-                key_binding::key::MOVE_DOWN => {
-                    self.board.move_position_down(&mut self.current_position)
-                }
-                key_binding::key::SCROLL_LEFT => self.board_display.scroll_left(),
-                key_binding::key::SCROLL_RIGHT => self.board_display.scroll_right(),
-                key_binding::key::SCROLL_UP => self.board_display.scroll_up(),
-                key_binding::key::SCROLL_DOWN => self.board_display.scroll_down(),
-                key_binding::key::SCROLL_RESET => self.board_display.scroll_reset(),
-                key_binding::key::ZOOM_IN => self.board_display.zoom_in(),
-                key_binding::key::ZOOM_OUT => self.board_display.zoom_out(),
-                key_binding::key::INTO_HISTORY => self.update_status(Status::Play(Play::History)),
-                key_binding::key::SELECT => self.select_in_play_turn(),
-                _ => (),
-            },
-            Play::History => match key {
-                key_binding::key::QUIT => self.update_status(Status::AskQuit),
-                key_binding::key::INIT => self.update_status(Status::AskInit),
-                key_binding::key::PREV_HISTORY | key_binding::key::NEXT_HISTORY => {
-                    self.history_move(key)
-                }
-                key_binding::key::SCROLL_LEFT => self.board_display.scroll_left(),
-                key_binding::key::SCROLL_RIGHT => self.board_display.scroll_right(),
-                key_binding::key::SCROLL_UP => self.board_display.scroll_up(),
-                key_binding::key::SCROLL_DOWN => self.board_display.scroll_down(),
-                key_binding::key::SCROLL_RESET => self.board_display.scroll_reset(),
-                key_binding::key::ZOOM_IN => self.board_display.zoom_in(),
-                key_binding::key::ZOOM_OUT => self.board_display.zoom_out(),
-                key_binding::key::SELECT => self.update_status(Status::Play(Play::Turn)),
-                _ => (),
-            },
-            Play::Skipped => match key {
-                key_binding::key::QUIT => self.update_status(Status::AskQuit),
-                key_binding::key::INIT => self.update_status(Status::AskInit),
-                key_binding::key::FRAME_TOGGLE => self.board_display.toggle_frame_visibility(),
-                key_binding::key::MOVE_LEFT => {
-                    self.board.move_position_left(&mut self.current_position)
-                }
-                key_binding::key::MOVE_RIGHT => {
-                    self.board.move_position_right(&mut self.current_position)
-                }
-                key_binding::key::MOVE_UP => {
-                    self.board.move_position_up(&mut self.current_position)
-                }
-                key_binding::key::MOVE_DOWN => {
-                    self.board.move_position_down(&mut self.current_position)
-                }
-                key_binding::key::SCROLL_LEFT => self.board_display.scroll_left(),
-                key_binding::key::SCROLL_RIGHT => self.board_display.scroll_right(),
-                key_binding::key::SCROLL_UP => self.board_display.scroll_up(),
-                key_binding::key::SCROLL_DOWN => self.board_display.scroll_down(),
-                key_binding::key::SCROLL_RESET => self.board_display.scroll_reset(),
-                key_binding::key::ZOOM_IN => self.board_display.zoom_in(),
-                key_binding::key::ZOOM_OUT => self.board_display.zoom_out(),
-                key_binding::key::INTO_HISTORY => self.update_status(Status::Play(Play::History)),
-                key_binding::key::SELECT => self.select_in_play_skip(),
-                _ => (),
-            },
-            Play::Finished => match key {
-                key_binding::key::QUIT => self.update_status(Status::AskQuit),
-                key_binding::key::INIT => self.update_status(Status::AskInit),
-                key_binding::key::FRAME_TOGGLE => self.board_display.toggle_frame_visibility(),
-                key_binding::key::MOVE_LEFT => {
-                    self.board.move_position_left(&mut self.current_position)
-                }
-                key_binding::key::MOVE_RIGHT => {
-                    self.board.move_position_right(&mut self.current_position)
-                }
-                key_binding::key::MOVE_UP => {
-                    self.board.move_position_up(&mut self.current_position)
-                }
-                key_binding::key::MOVE_DOWN => {
-                    self.board.move_position_down(&mut self.current_position)
+    /// Whether the bell is to be rung, which is reset by this call.
+    pub fn take_bell(&mut self) -> bool {
+        self.notifier.take_bell()
+    }
+
+    /// Blocks until a human player is to play or the game is finished.
+    pub fn wait_for_ai(&mut self) {
+        self.poll_ai(true);
+    }
+
+    /// Starts computation if the current player is played by the computer.
+    /// Skipped turns of computer players are acknowledged at once.
+    fn start_ai_turn(&mut self) {
+        if self.is_paused() || self.ai_paused {
+            return;
+        }
+        while let Controller::Ai(strategy_kind) = self.controller(self.current_player) {
+            match self.current_status {
+                Status::Play(Play::Turn) => {
+                    if self.ai_worker.is_none() {
+                        self.ai_worker = Some(AiWorker::spawn(
+                            strategy_kind,
+                            self.settings.search_config,
+                            self.rng(Component::Ai),
+                            self.board.clone(),
+                            self.availables.clone(),
+                            self.current_player,
+                        ));
+                    }
+                    break;
                 }
-                key_binding::key::SCROLL_LEFT => self.board_display.scroll_left(),
-                key_binding::key::SCROLL_RIGHT => self.board_display.scroll_right(),
-                key_binding::key::SCROLL_UP => self.board_display.scroll_up(),
-                key_binding::key::SCROLL_DOWN => self.board_display.scroll_down(),
-                key_binding::key::SCROLL_RESET => self.board_display.scroll_reset(),
-                key_binding::key::ZOOM_IN => self.board_display.zoom_in(),
-                key_binding::key::ZOOM_OUT => self.board_display.zoom_out(),
-                key_binding::key::INTO_HISTORY => self.update_status(Status::Play(Play::History)),
-                _ => (),
-            },
+                Status::Play(Play::Skipped) => self.select_in_play_skip(),
+                _ => break,
+            }
         }
     }
 
-    fn select_in_play_turn(&mut self) {
-        if self
-            .availables
-            .get(&self.current_player)
-            .unwrap()
-            .contains_key(&self.current_position)
-        {
-            self.set_player();
-            if self
-                .availables
-                .values()
-                .all(|available| available.is_empty())
+    /// Applies positions selected by the computer, waiting for them if `wait` is true.
+    /// Results are kept while another screen, e.g., the quit dialog, is shown,
+    /// and until the interval of moves has passed since the last one.
+    fn poll_ai(&mut self, wait: bool) {
+        while self.current_status == Status::Play(Play::Turn) {
+            let Some(ai_worker) = &self.ai_worker else {
+                break;
+            };
+            if !wait
+                && (self.ai_paused
+                    || self
+                        .settings
+                        .ai_move_interval
+                        .is_some_and(|interval| self.last_ai_move.elapsed() < interval))
             {
-                self.history.push(
-                    (self.current_player, self.current_position),
-                    self.board.clone(),
-                );
-                self.update_status(Status::Play(Play::Finished));
-                self.clear_message();
-                self.board.count();
-                write!(self.message, " Game is finished! Final Score is").unwrap();
-                let mut player_iter = PLAYERS.iter().peekable();
-                while let Some(player) = player_iter.next() {
-                    if player_iter.peek().is_none() {
-                        write!(self.message, " and").unwrap();
-                    }
-                    write!(
-                        self.message,
-                        " {} = {}",
-                        self.board_display.player_name(*player),
-                        self.board.count().get(player).unwrap(),
-                    )
-                    .unwrap();
-                    if player_iter.peek().is_none() {
-                        write!(self.message, ".").unwrap();
-                    } else {
-                        write!(self.message, ",").unwrap();
-                    }
-                }
+                break;
+            }
+            let result = if wait {
+                ai_worker.wait().ok()
             } else {
-                self.history.push(
-                    (self.current_player, self.current_position),
-                    self.board.clone(),
-                );
-                self.current_player.advance();
-                self.clear_message();
-                if self
-                    .availables
-                    .get(&self.current_player)
-                    .unwrap()
-                    .is_empty()
-                {
-                    self.update_status(Status::Play(Play::Skipped));
-                    self.message_color = Color::Red;
-                    write!(self.message, " Player-{}: Your turn is skipped, you cannot select any position. Pless [{}].",
-                        self.board_display.player_name(self.current_player),
-                        key_binding::change_key_to_str(key_binding::key::SELECT)
-                    ).unwrap();
+                match ai_worker.try_result() {
+                    Ok(result) => Some(result),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => None,
                 }
+            };
+            self.ai_worker = None;
+            match result.flatten() {
+                Some(position) => {
+                    self.current_position = position;
+                    self.select_in_play_turn();
+                    self.last_ai_move = Instant::now();
+                }
+                None => break,
             }
-        } else {
-            self.clear_message();
-            self.message_color = Color::Red;
-            write!(
-                self.message,
-                " Player-{}: You cannot select ({}, {}).",
-                self.board_display.player_name(self.current_player),
-                self.current_position.0,
-                self.current_position.1
-            )
-            .unwrap();
+            self.start_ai_turn();
         }
     }
 
-    fn select_in_play_skip(&mut self) {
+    /// Whether the current player is played by the human on this terminal.
+    fn is_local_turn(&self) -> bool {
+        self.controller(self.current_player) == Controller::Human
+    }
+
+    /// Controller of the seat of `player`, where the first two seats are exchanged after the swap.
+    fn controller(&self, player: Player) -> Controller {
+        let (first, second) = self.swap_pair();
+        let seat = match player {
+            _ if !self.history.record().swapped() => player,
+            _ if player == first => second,
+            _ if player == second => first,
+            _ => player,
+        };
+        self.settings.seat_config.controller(seat)
+    }
+
+    /// Player moving first and the player next to it, who may swap their seats in the swap variant.
+    fn swap_pair(&self) -> (Player, Player) {
+        let mut second = self.first_player;
+        second.advance();
+        (self.first_player, second)
+    }
+
+    /// Whether the second player may take over the seat of the first now.
+    fn can_swap(&self) -> bool {
+        self.settings.variant == Variant::Swap
+            && self.puzzle.is_none()
+            && self.network.is_none()
+            && !self.history.record().swapped()
+            && self.history.current_turn() == SWAP_TURN
+            && self.history.branch().len() == SWAP_TURN
+            && self.current_player == self.swap_pair().1
+            && self.current_status == Status::Play(Play::Turn)
+    }
+
+    /// Lets the player of the second seat play the first seat with its stones, and vice versa,
+    /// where names, marks, and colors stay with the seats.
+    fn swap_seats(&mut self) {
+        let (first, second) = self.swap_pair();
+        self.history.set_swapped();
+        self.ai_worker = None;
+        self.think_stopwatch.restart();
         self.clear_message();
-        self.current_player.advance();
-        if self
-            .availables
-            .get(&self.current_player)
-            .unwrap()
-            .is_empty()
-        {
-            self.update_status(Status::Play(Play::Skipped));
-            self.message_color = Color::Red;
-            write!(
-                self.message,
-                " Player-{}: Your turn is skipped, you cannot select any position. Pless [{}].",
-                self.board_display.player_name(self.current_player),
-                key_binding::change_key_to_str(key_binding::key::SELECT)
+        self.message_color = self.settings.color_config.player(first);
+        write!(
+            self.message,
+            " {}",
+            fill(
+                &self.settings.messages.seats_swapped,
+                &[
+                    &self.board_display.player_name(first),
+                    &self.board_display.player_name(second)
+                ]
             )
-            .unwrap();
-        } else {
-            self.update_status(Status::Play(Play::Turn));
+        )
+        .unwrap();
+        self.log_message();
+    }
+
+    /// Swaps the seats as recorded in a replayed game, which is ignored before the first move.
+    pub fn restore_swap(&mut self) {
+        if self.history.branch().len() >= SWAP_TURN {
+            self.history.set_swapped();
+            self.ai_worker = None;
+            self.start_ai_turn();
         }
     }
 
-    fn history_move(&mut self, key: Key) {
-        if key == key_binding::key::PREV_HISTORY {
-            self.history.go_prev();
-        } else {
-            self.history.go_next();
+    /// Transits by `keys` in order, e.g., to play a game without a terminal.
+    /// Keys after quitting are ignored.
+    /// Moves of the computer are waited for before each key.
+    pub fn apply_key_sequence(&mut self, keys: &[Key]) {
+        for key in keys {
+            self.wait_for_ai();
+            if self.current_status == Status::Quit {
+                break;
+            }
+            self.transition(*key);
         }
-        self.board = self.history.board().clone();
-        if self.history.past_player().is_some() {
-            self.current_player = self.history.past_player().unwrap();
+        self.wait_for_ai();
+    }
+
+    /// Position of the cursor.
+    pub fn cursor(&self) -> (usize, usize) {
+        self.current_position
+    }
+
+    /// Moves the cursor to `position` without move keys, scrolling it into view as the keys do.
+    pub fn set_cursor(&mut self, position: (usize, usize)) -> Result<(), TriversiError> {
+        validate_position(position, self.board.range())?;
+        self.current_position = position;
+        if self.settings.auto_scroll {
+            self.board_display
+                .scroll_into_view(&self.board, position, self.board_rect);
         }
-        if self.history.past_position().is_some() {
-            self.current_position = self.history.past_position().unwrap();
+        Ok(())
+    }
+
+    /// Applies `moves` with their line numbers in order regardless of the controllers of seats,
+    /// and then lets the computer play if it is its turn.
+    pub fn replay(&mut self, moves: &[(usize, Move)]) -> Result<(), TriversiError> {
+        for (line_number, player_move) in moves {
+            self.apply_move(*player_move).map_err(|error| {
+                TriversiError::IllegalMoveLine(
+                    *line_number,
+                    player_move.to_string(),
+                    Box::new(error),
+                )
+            })?;
         }
-        self.update_available_list();
+        self.start_ai_turn();
+        Ok(())
     }
 
-    fn ask_quit(&mut self, key: Key) {
-        match key {
-            Key::Char('Y') => self.update_status(Status::Quit),
-            _ => self.update_status(self.previous_status),
+    /// Replaces the times spent on the replayed moves by those recorded in the game.
+    pub fn restore_think_times(&mut self, think_times: &[u64]) {
+        self.history.restore_think_times(think_times);
+    }
+
+    /// Applies a move of the current player, or returns the error of the illegal move.
+    fn apply_move(&mut self, player_move: Move) -> Result<(), TriversiError> {
+        self.ai_worker = None;
+        let name = self
+            .board_display
+            .player_name(self.current_player)
+            .to_owned();
+        match (self.current_status, player_move) {
+            (Status::Play(Play::Turn), Move::Put(position)) => {
+                if self.availables[self.current_player].contains_key(&position) {
+                    self.current_position = position;
+                    self.select_in_play_turn();
+                    Ok(())
+                } else if let Some(occupant) = self.board.player(position) {
+                    Err(TriversiError::PositionOccupied(
+                        position.0,
+                        position.1,
+                        self.board_display.player_name(occupant).to_owned(),
+                    ))
+                } else {
+                    Err(TriversiError::IllegalMove {
+                        player: name,
+                        position,
+                    })
+                }
+            }
+            (Status::Play(Play::Skipped), Move::Pass) => {
+                self.select_in_play_skip();
+                Ok(())
+            }
+            (Status::Play(Play::Turn), Move::Pass) => Err(TriversiError::IllegalPass(name)),
+            (Status::Play(Play::Skipped), Move::Put(_)) => Err(TriversiError::MustPass(name)),
+            _ => Err(TriversiError::GameFinished),
         }
     }
 
-    fn ask_init(&mut self, key: Key) {
-        match key {
-            Key::Char('Y') => self.init(),
-            _ => self.update_status(self.previous_status),
+    /// Plays a network game as the host or a client.
+    /// Seats played on other terminals are `Controller::Remote` in the seat configuration.
+    pub fn set_network(&mut self, network: Network) {
+        self.ai_worker = None;
+        self.network = Some(network);
+        if self.is_paused() {
+            self.write_paused_message();
         }
+        self.start_ai_turn();
     }
 
-    fn ui_play<B: Backend>(&mut self, frame: &mut Frame<B>, play: Play) {
-        let guidance_box_height = 4;
-        let message_box_height = 3;
-        let player_box_width = 6 + PLAYERS
-            .iter()
-            .map(|player| self.board_display.player_name(*player).width_cjk())
-            .sum::<usize>() as u16;
-        let position_box_width = 10;
-        let scroll_box_width = 10;
-        let zoom_box_width = 6;
-        let debug_box_width = if cfg!(debug_assertions) {
-            frame.size().width / 2
-        } else {
-            0
+    /// Plays the next game of `match_state`.
+    pub fn set_match(&mut self, match_state: MatchState) {
+        self.first_player = match_state.first_player();
+        self.match_state = Some(match_state);
+        self.init();
+        self.start_ai_turn();
+    }
+
+    /// Whether a match is played and has games left.
+    fn is_match_continuing(&self) -> bool {
+        self.match_state
+            .as_ref()
+            .is_some_and(|match_state| !match_state.is_over())
+    }
+
+    fn next_game(&mut self) {
+        if let Some(match_state) = &self.match_state {
+            self.first_player = match_state.first_player();
+        }
+        self.init();
+    }
+
+    /// Records the result of the finished game in the match and writes what follows to the message.
+    fn finish_match_game(&mut self) {
+        let points =
+            self.board
+                .match_points(self.settings.tiebreak, &self.skips, self.first_player);
+        let stones = self.board.scores().into_array();
+        let Some(match_state) = &mut self.match_state else {
+            return;
         };
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Length(guidance_box_height),
-                    Constraint::Length(message_box_height),
-                    Constraint::Length(
-                        frame.size().height - guidance_box_height - message_box_height,
-                    ),
-                ]
-                .as_ref(),
-            )
-            .split(frame.size());
-        let chunks_1 = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                [
-                    Constraint::Length(player_box_width),
-                    Constraint::Length(position_box_width),
-                    Constraint::Length(scroll_box_width),
-                    Constraint::Length(zoom_box_width),
-                    Constraint::Length(
-                        frame.size().width
-                            - player_box_width
-                            - position_box_width
-                            - scroll_box_width
-                            - zoom_box_width,
-                    ),
-                ]
-                .as_ref(),
-            )
-            .split(chunks[1]);
-        let chunks_2 = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                [
-                    Constraint::Length(frame.size().width - debug_box_width),
-                    Constraint::Length(debug_box_width),
-                ]
-                .as_ref(),
+        match_state.finish_game(points.into_array(), stones);
+        if !match_state.is_over() {
+            let game_number = match_state.game_number();
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.next_game,
+                    &[
+                        &self.settings.key_binding.key_str(Action::Select),
+                        &game_number
+                    ]
+                )
             )
-            .split(chunks[2]);
-        let guidance = if play == Play::History {
-            key_binding::make_guidance_in_history()
-        } else {
-            key_binding::make_guidance_in_turn()
+            .unwrap();
+            return;
+        }
+        let names = match_state
+            .leaders()
+            .iter()
+            .map(|player| self.board_display.player_name(*player).to_owned())
+            .collect::<Vec<_>>();
+        let messages = &self.settings.messages;
+        let text = match names.as_slice() {
+            [winner] => fill(&messages.match_won, &[winner]),
+            [first, second] => fill(&messages.match_drawn_between, &[first, second]),
+            _ => messages.match_drawn.clone(),
         };
-        self.render_guidance_block(frame, chunks[0], guidance);
-        self.render_player_block(frame, chunks_1[0], play);
-        self.render_position_block(frame, chunks_1[1]);
-        self.board_display.render_scroll_block(frame, chunks_1[2]);
-        self.board_display.render_zoom_block(frame, chunks_1[3]);
-        self.render_message_block(frame, chunks_1[4]);
-        self.board_display.render_board_block(
-            frame,
-            chunks_2[0],
-            &self.board,
-            self.color_config,
-            play,
+        write!(self.message, " {}", text).unwrap();
+    }
+
+    /// Standings of the match and the file where it is saved, which are printed at exit.
+    pub fn match_summary(&self) -> Option<String> {
+        let match_state = self.match_state.as_ref()?;
+        let mut summary = match_state.standings(&self.player_names());
+        if let Some(path) = &self.saved_match {
+            writeln!(
+                summary,
+                "{}",
+                fill(&self.settings.messages.match_saved, &[&path.display()])
+            )
+            .unwrap();
+        }
+        Some(summary)
+    }
+
+    fn player_names(&self) -> Vec<&str> {
+        PLAYERS
+            .iter()
+            .map(|player| self.board_display.player_name(*player))
+            .collect()
+    }
+
+    /// Saves the unfinished match to the dump directory, or writes the error to the message.
+    fn save_match(&mut self) -> bool {
+        let Some(match_state) = &self.match_state else {
+            return false;
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let path = self
+            .settings
+            .dump_dir
+            .join(format!("triversi-match-{}.json", timestamp));
+        match match_state.save(&path) {
+            Ok(()) => {
+                self.saved_match = Some(path);
+                true
+            }
+            Err(err) => {
+                self.clear_message();
+                self.message_color = self.settings.color_config.error_color();
+                let text = fill(
+                    &self.settings.messages.match_not_saved,
+                    &[&format!("{:#}", err)],
+                );
+                write!(self.message, " {}", text).unwrap();
+                self.log_message();
+                false
+            }
+        }
+    }
+
+    /// Starts solving `puzzle` from its position.
+    pub fn set_puzzle(&mut self, puzzle: Puzzle) {
+        self.puzzle = Some(puzzle);
+        self.init();
+    }
+
+    /// Starts the game from `board` instead of the initial board.
+    pub fn set_start_board(&mut self, board: Board) {
+        self.start_board = Some(board);
+        self.init();
+    }
+
+    /// Shows the menu of ranges before the game starts, where the range of the board is selected first.
+    pub fn select_size(&mut self) {
+        self.ai_worker = None;
+        self.size_selection = self.board.range();
+        self.update_status(Status::SelectSize);
+    }
+
+    /// Moves the selection of the menu of ranges or starts the game at the selected range.
+    fn choose_size(&mut self, key: Key) {
+        match self.settings.key_binding.action(key) {
+            Some(Action::MoveUp | Action::MoveLeft) => {
+                if let Some(range) = (MIN_RANGE..self.size_selection)
+                    .rev()
+                    .find(|range| Board::validate_range(*range).is_ok())
+                {
+                    self.size_selection = range;
+                }
+            }
+            Some(Action::MoveDown | Action::MoveRight) => {
+                self.size_selection = (self.size_selection + 1..)
+                    .find(|range| Board::validate_range(*range).is_ok())
+                    .unwrap();
+            }
+            Some(Action::Select) => self.start_at_selected_size(),
+            Some(Action::Quit) => self.update_status(Status::AskQuit),
+            _ => (),
+        }
+    }
+
+    /// Starts the game on a board of the selected range with the rules of the current board,
+    /// or shows the error if the handicap does not fit the board.
+    fn start_at_selected_size(&mut self) {
+        let board = Board::try_new(self.size_selection).and_then(|mut board| {
+            board.set_capture_rules(self.board.capture_rules().clone());
+            board.set_scoring(self.board.scoring());
+            board.try_set_handicap(self.board.handicap(), self.first_player)?;
+            Ok(board)
+        });
+        match board {
+            Ok(board) => {
+                self.board = board;
+                self.board_display.scroll_reset();
+                self.init();
+            }
+            Err(err) => self.show_error(err),
+        }
+    }
+
+    /// Stops or restarts applying positions selected by the computer.
+    fn toggle_ai_pause(&mut self) {
+        self.ai_paused = !self.ai_paused;
+        self.clear_message();
+        if self.ai_paused {
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.ai_paused,
+                    &[&self.settings.key_binding.key_str(Action::Pause)]
+                )
+            )
+            .unwrap();
+        } else {
+            write!(self.message, " {}", self.settings.messages.ai_resumed).unwrap();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.network.as_ref().is_some_and(Network::is_paused)
+    }
+
+    /// Handles events of the network and applies received moves while the game is shown.
+    fn poll_network(&mut self) {
+        while let Some(event) = self.network.as_mut().and_then(Network::poll) {
+            match event {
+                NetEvent::Joined(player) => {
+                    self.clear_message();
+                    write!(
+                        self.message,
+                        " {}",
+                        fill(
+                            &self.settings.messages.joined,
+                            &[&self.board_display.player_name(player)]
+                        )
+                    )
+                    .unwrap();
+                    self.log_message();
+                }
+                NetEvent::Left(player) => {
+                    self.clear_message();
+                    self.message_color = self.settings.color_config.error_color();
+                    write!(
+                        self.message,
+                        " {}",
+                        fill(
+                            &self.settings.messages.left,
+                            &[&self.board_display.player_name(player)]
+                        )
+                    )
+                    .unwrap();
+                    self.log_message();
+                }
+                NetEvent::Lost => {
+                    self.clear_message();
+                    self.message_color = self.settings.color_config.error_color();
+                    write!(self.message, " {}", self.settings.messages.connection_lost).unwrap();
+                    self.log_message();
+                }
+                event => self.network_moves.push_back(event),
+            }
+        }
+        while let Status::Play(Play::Turn | Play::Skipped) = self.current_status {
+            match self.network_moves.pop_front() {
+                Some(NetEvent::Requested(player, player_move)) => {
+                    // Illegal moves and moves out of turn are ignored.
+                    if !self.is_paused() && player == self.current_player {
+                        let _ = self.apply_move(player_move);
+                    }
+                }
+                Some(NetEvent::Applied(applied_move)) => {
+                    if applied_move.player != self.current_player
+                        || self.apply_move(applied_move.player_move).is_err()
+                        || applied_move.next_player != self.current_player
+                    {
+                        self.clear_message();
+                        self.message_color = self.settings.color_config.error_color();
+                        write!(self.message, " {}", self.settings.messages.out_of_sync).unwrap();
+                        self.log_message();
+                    }
+                }
+                _ => break,
+            }
+        }
+        self.start_ai_turn();
+    }
+
+    /// Selects the current position, or acknowledges the skipped turn, by a key of the local player.
+    /// Clients send the move to the host, which applies it and sends it back.
+    fn select_by_key(&mut self) {
+        if self.is_paused() {
+            self.write_paused_message();
+            return;
+        }
+        let is_skipped = self.current_status == Status::Play(Play::Skipped);
+        if let Some(Network::Client(client)) = &mut self.network {
+            let player_move = if is_skipped {
+                Move::Pass
+            } else if self.availables[self.current_player].contains_key(&self.current_position) {
+                Move::Put(self.current_position)
+            } else {
+                // Shows that the position cannot be selected.
+                self.select_in_play_turn();
+                return;
+            };
+            if client.send_move(player_move).is_err() {
+                self.clear_message();
+                self.message_color = self.settings.color_config.error_color();
+                write!(self.message, " {}", self.settings.messages.move_not_sent).unwrap();
+                self.log_message();
+            }
+        } else if is_skipped {
+            self.select_in_play_skip();
+        } else {
+            self.select_in_play_turn();
+        }
+    }
+
+    fn write_paused_message(&mut self) {
+        let offline_names = PLAYERS
+            .iter()
+            .filter(|player| {
+                self.network
+                    .as_ref()
+                    .is_some_and(|network| network.is_offline(**player))
+            })
+            .map(|player| {
+                fill(
+                    &self.settings.messages.player,
+                    &[&self.board_display.player_name(*player)],
+                )
+            })
+            .collect::<Vec<_>>();
+        self.clear_message();
+        self.message_color = self.settings.color_config.error_color();
+        let messages = &self.settings.messages;
+        if offline_names.is_empty() {
+            write!(self.message, " {}", messages.game_paused).unwrap();
+        } else {
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &messages.game_paused_waiting,
+                    &[&offline_names.join(&messages.and_separator)]
+                )
+            )
+            .unwrap();
+        }
+    }
+
+    pub fn ui<B: Backend>(&mut self, frame: &mut Frame<B>) {
+        let (min_width, min_height) = self.min_terminal_size();
+        if frame.size().width < min_width || frame.size().height < min_height {
+            self.ui_too_small(frame, min_width, min_height);
+            return;
+        }
+        match self.current_status {
+            Status::Play(play) => self.ui_play(frame, play),
+            Status::MessageLog => self.ui_message_log(frame),
+            Status::AskInit => self.ui_ask_init(frame),
+            Status::AskSwap => self.ui_ask_swap(frame),
+            Status::AskQuit => self.ui_ask_quit(frame),
+            Status::AskSlot => self.ui_ask_slot(frame),
+            Status::AskOverwrite => self.ui_ask_overwrite(frame),
+            Status::PuzzleResult => self.ui_play(frame, Play::Finished),
+            Status::SelectSize => self.ui_select_size(frame),
+            Status::Quit => unreachable!(),
+        }
+    }
+
+    fn play(&mut self, key: Key, play: Play) {
+        let action = self.settings.key_binding.action(key);
+        // Restarting and rewinding the game are not shared with other terminals.
+        if self.network.is_some()
+            && matches!(
+                action,
+                Some(
+                    Action::Init
+                        | Action::IntoHistory
+                        | Action::Undo
+                        | Action::Redo
+                        | Action::Pause
+                )
+            )
+        {
+            self.clear_message();
+            self.message_color = self.settings.color_config.error_color();
+            write!(self.message, " {}", self.settings.messages.not_in_network).unwrap();
+            return;
+        }
+        if action.is_some_and(|action| self.view(action, play)) {
+            return;
+        }
+        match play {
+            Play::Turn => match action {
+                Some(Action::Quit) => self.update_status(Status::AskQuit),
+                Some(Action::Init) => self.update_status(Status::AskInit),
+                Some(Action::Pause) => self.toggle_ai_pause(),
+                Some(Action::Save) => self.ask_slot_name(),
+                Some(Action::MoveLeft) => self.board.move_position_left(&mut self.current_position),
+                Some(Action::MoveRight) => {
+                    self.board.move_position_right(&mut self.current_position)
+                }
+                Some(Action::MoveUp) => self.move_cursor_up(),
+                Some(Action::MoveDown) => self.move_cursor_down(),
+                Some(Action::IntoHistory) => self.enter_history(),
+                Some(Action::Undo) if self.puzzle.is_none() => self.undo(),
+                Some(Action::Redo) if self.puzzle.is_none() => self.redo(),
+                Some(Action::Select) if self.puzzle.is_some() => self.select_in_puzzle(),
+                Some(Action::Select) if self.is_local_turn() => self.select_by_key(),
+                Some(Action::Suggest) if self.is_local_turn() => self.suggest(),
+                Some(Action::Swap) if self.is_local_turn() && self.can_swap() => {
+                    self.update_status(Status::AskSwap)
+                }
+                _ => (),
+            },
+            Play::History => match action {
+                Some(Action::Quit) => self.update_status(Status::AskQuit),
+                Some(Action::Init) => self.update_status(Status::AskInit),
+                Some(action @ (Action::PrevHistory | Action::NextHistory)) => {
+                    self.history_move(action)
+                }
+                Some(Action::SwitchBranch) => self.switch_branch(),
+                Some(Action::Annotate) => self.annotate(),
+                Some(Action::Save) => self.ask_slot_name(),
+                Some(Action::Select) => self.leave_history(),
+                _ => (),
+            },
+            Play::Skipped => match action {
+                Some(Action::Quit) => self.update_status(Status::AskQuit),
+                Some(Action::Init) => self.update_status(Status::AskInit),
+                Some(Action::Pause) => self.toggle_ai_pause(),
+                Some(Action::Save) => self.ask_slot_name(),
+                Some(Action::MoveLeft) => self.board.move_position_left(&mut self.current_position),
+                Some(Action::MoveRight) => {
+                    self.board.move_position_right(&mut self.current_position)
+                }
+                Some(Action::MoveUp) => self.move_cursor_up(),
+                Some(Action::MoveDown) => self.move_cursor_down(),
+                Some(Action::IntoHistory) => self.enter_history(),
+                Some(Action::Select) if self.is_local_turn() => self.select_by_key(),
+                _ => (),
+            },
+            Play::Finished => match action {
+                Some(Action::Quit) => self.update_status(Status::AskQuit),
+                Some(Action::Init) => self.update_status(Status::AskInit),
+                Some(Action::Pause) => self.toggle_ai_pause(),
+                Some(Action::Save) => self.ask_slot_name(),
+                Some(Action::Export) => self.export_game_by_key(),
+                Some(Action::Select) if self.is_match_continuing() => self.next_game(),
+                Some(Action::MoveLeft) => self.board.move_position_left(&mut self.current_position),
+                Some(Action::MoveRight) => {
+                    self.board.move_position_right(&mut self.current_position)
+                }
+                Some(Action::MoveUp) => self.move_cursor_up(),
+                Some(Action::MoveDown) => self.move_cursor_down(),
+                Some(Action::IntoHistory) => self.enter_history(),
+                Some(Action::Undo) if self.puzzle.is_none() && self.match_state.is_none() => {
+                    self.undo()
+                }
+                _ => (),
+            },
+        }
+    }
+
+    /// Handles an action which changes only the view of the board or shows it, not the game,
+    /// and returns whether `action` is such one.
+    fn view(&mut self, action: Action, play: Play) -> bool {
+        match action {
+            Action::FrameToggle => self.board_display.toggle_frame_visibility(),
+            Action::HomeToggle => self.board_display.toggle_home_region_visibility(),
+            Action::LegendToggle => self.legend_visibility = !self.legend_visibility,
+            Action::HudToggle => self.compact_hud = !self.compact_hud,
+            Action::Rotate => self.rotate_board(),
+            Action::CaptureLinesToggle => {
+                self.capture_line_visibility = !self.capture_line_visibility
+            }
+            Action::FlipsToggle => self.flip_panel_visibility = !self.flip_panel_visibility,
+            Action::DiffToggle => self.diff_visibility = !self.diff_visibility,
+            Action::FogToggle => self.fog_visibility = !self.fog_visibility,
+            Action::Dump => self.dump_board(),
+            Action::Code => self.copy_code(),
+            Action::MessageLog => self.open_message_log(),
+            Action::Describe => self.describe(play),
+            Action::DebugToggle => self.debug_panel = self.debug_panel.next(),
+            Action::ScrollLeft => self.board_display.scroll_left(),
+            Action::ScrollRight => self.board_display.scroll_right(),
+            Action::ScrollUp => self.board_display.scroll_up(),
+            Action::ScrollDown => self.board_display.scroll_down(),
+            Action::ScrollReset => self.board_display.scroll_reset(),
+            Action::CenterView => self.center_view(),
+            Action::CenterCursor => self.center_cursor(),
+            Action::ScrollPageUp
+            | Action::ScrollPageDown
+            | Action::ScrollPageLeft
+            | Action::ScrollPageRight => self.scroll_page(action),
+            Action::ZoomIn => self.board_display.zoom_in(),
+            Action::ZoomOut => self.board_display.zoom_out(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Places a stone at the current position and shows whether it solves the puzzle.
+    fn select_in_puzzle(&mut self) {
+        let position = self.current_position;
+        if !self.availables[self.current_player].contains_key(&position) {
+            // Shows that the position cannot be selected.
+            self.select_in_play_turn();
+            return;
+        }
+        let is_solved = self
+            .puzzle
+            .as_ref()
+            .is_some_and(|puzzle| puzzle.is_solved_by(position));
+        if let Err(err) = self.set_player() {
+            self.show_error(err);
+            return;
+        }
+        self.update_status(Status::PuzzleResult);
+        self.clear_message();
+        if is_solved {
+            self.message_color = self.settings.color_config.player(self.current_player);
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.puzzle_solved,
+                    &[&position.0, &position.1]
+                )
+            )
+            .unwrap();
+        } else {
+            self.message_color = self.settings.color_config.error_color();
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.puzzle_failed,
+                    &[
+                        &position.0,
+                        &position.1,
+                        &self.settings.key_binding.key_str(Action::Init),
+                        &self.settings.key_binding.key_str(Action::Reveal)
+                    ]
+                )
+            )
+            .unwrap();
+        }
+        self.log_message();
+    }
+
+    fn puzzle_result(&mut self, key: Key) {
+        let action = self.settings.key_binding.action(key);
+        if action.is_some_and(|action| self.view(action, Play::Finished)) {
+            return;
+        }
+        match action {
+            Some(Action::Quit) => self.update_status(Status::AskQuit),
+            Some(Action::Init) => self.init(),
+            Some(Action::Reveal) => self.reveal_solution(),
+            _ => (),
+        }
+    }
+
+    /// Restores the position of the puzzle and points at the solution.
+    fn reveal_solution(&mut self) {
+        let Some(puzzle) = &self.puzzle else {
+            return;
+        };
+        let solution = puzzle.solutions()[0];
+        let others = puzzle
+            .accepted()
+            .iter()
+            .filter(|position| **position != solution)
+            .map(|position| format!("({}, {})", position.0, position.1))
+            .collect::<Vec<_>>();
+        self.board = puzzle.board().clone();
+        self.update_available_list();
+        self.current_position = solution;
+        self.is_suggested = true;
+        self.clear_message();
+        self.message_color = self.settings.color_config.player(self.current_player);
+        let messages = &self.settings.messages;
+        let text = if others.is_empty() {
+            fill(&messages.solution, &[&solution.0, &solution.1])
+        } else {
+            fill(
+                &messages.solution_with_others,
+                &[
+                    &solution.0,
+                    &solution.1,
+                    &others.join(&messages.list_separator),
+                ],
+            )
+        };
+        write!(self.message, " {}", text).unwrap();
+        self.log_message();
+    }
+
+    fn select_in_play_turn(&mut self) {
+        if let Some(positions) = self.availables[self.current_player].get(&self.current_position) {
+            // The selected position itself is included in the positions.
+            let flips = positions.len() as u64 - 1;
+            let player = self.current_player;
+            let position = self.current_position;
+            let flipped = positions
+                .iter()
+                .copied()
+                .filter(|flipped| *flipped != position)
+                .collect::<Vec<_>>();
+            let better_move = self.better_move(flips);
+            self.last_capture = Some(CaptureLines {
+                player,
+                lines: self.board.capture_lines(position, positions),
+            });
+            if let Err(err) = self.set_player() {
+                self.show_error(err);
+                return;
+            }
+            debug_assert_eq!(*self.board.count(), Count::from_board(&self.board));
+            self.history.push(
+                (self.current_player, self.current_position),
+                flips,
+                self.think_stopwatch.elapsed(),
+                self.board.clone(),
+            );
+            self.annotator = None;
+            self.annotations = None;
+            self.think_stopwatch.restart();
+            // Whichever of the end of moves and the limit of turns comes first finishes the game.
+            let is_finished = self.is_over() || self.turn_limit().is_some();
+            let skipped = if is_finished {
+                self.update_status(Status::Play(Play::Finished));
+                Vec::new()
+            } else {
+                self.advance_turn()
+            };
+            self.better_move = better_move;
+            self.emit(GameEvent::Move {
+                turn: self.history.current_turn(),
+                player,
+                position,
+                flipped,
+                counts: event_log::counts(&self.board),
+                next_player: self.current_player,
+            });
+            for skipped_player in skipped {
+                self.emit_skip_event(skipped_player);
+            }
+            if is_finished {
+                self.emit(GameEvent::Finish {
+                    turn: self.history.current_turn(),
+                    winner: self.board.winner(
+                        self.settings.tiebreak,
+                        &self.skips,
+                        self.first_player,
+                    ),
+                    counts: event_log::counts(&self.board),
+                });
+                self.write_stats_csv();
+                if let Some(path) = self.settings.export_game.clone() {
+                    self.export_game(&path);
+                }
+                if let Some(path) = self.settings.screenshot_on_finish.clone() {
+                    self.write_screenshot(&path);
+                }
+            } else if self.can_swap() {
+                write!(
+                    self.message,
+                    " {}",
+                    fill(
+                        &self.settings.messages.swap_offer,
+                        &[
+                            &self.board_display.player_name(self.current_player),
+                            &self.board_display.player_name(player),
+                            &self.settings.key_binding.key_str(Action::Swap)
+                        ]
+                    )
+                )
+                .unwrap();
+            }
+        } else {
+            self.clear_message();
+            self.message_color = self.settings.color_config.error_color();
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.cannot_select,
+                    &[
+                        &self.board_display.player_name(self.current_player),
+                        &self.current_position.0,
+                        &self.current_position.1
+                    ]
+                )
+            )
+            .unwrap();
+            self.log_message();
+            self.notifier.alert();
+        }
+    }
+
+    /// Whether no player can select any position.
+    fn is_over(&self) -> bool {
+        self.availables
+            .values()
+            .all(|available| available.is_empty())
+    }
+
+    /// Limit of turns if the game has reached it.
+    fn turn_limit(&self) -> Option<usize> {
+        self.settings
+            .max_turns
+            .filter(|max_turns| self.history.current_turn() >= *max_turns)
+    }
+
+    /// Passes the turn to the next player, who is skipped with the following players
+    /// if turns are skipped automatically, and returns the players who cannot select any position.
+    /// The turn of a skipped player is kept until the pass without the automatic skip.
+    fn advance_turn(&mut self) -> Vec<Player> {
+        let mut skipped = Vec::new();
+        self.current_player.advance();
+        while self.availables[self.current_player].is_empty() {
+            self.skips.increment(self.current_player);
+            skipped.push(self.current_player);
+            if !self.settings.auto_skip {
+                break;
+            }
+            self.current_player.advance();
+        }
+        let play = if self.availables[self.current_player].is_empty() {
+            Play::Skipped
+        } else {
+            Play::Turn
+        };
+        self.update_status(Status::Play(play));
+        skipped
+    }
+
+    /// Writes the message of `event`, which is told to the message before the other observers.
+    fn write_event_message(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::Move {
+                player, flipped, ..
+            } => {
+                self.clear_message();
+                self.auto_skipped.clear();
+                self.message_color = self.settings.color_config.player(*player);
+                write!(
+                    self.message,
+                    " {}",
+                    fill(
+                        &self.settings.messages.flipped,
+                        &[&self.board_display.player_name(*player), &flipped.len()]
+                    )
+                )
+                .unwrap();
+                if let Some(((x, y), more)) = self.better_move.take() {
+                    self.message_warning =
+                        format!(" {}", fill(&self.settings.messages.coach, &[&x, &y, &more]));
+                }
+                self.log_message();
+            }
+            GameEvent::Skip { turn, player, .. } if self.settings.auto_skip => {
+                self.skip_banner = true;
+                self.auto_skipped.push(*player);
+                let name = |player: &Player| {
+                    fill(
+                        &self.settings.messages.player,
+                        &[&self.board_display.player_name(*player)],
+                    )
+                };
+                let names = self.auto_skipped.iter().map(name).collect::<Vec<_>>();
+                let auto_skipped = &self.settings.messages.auto_skipped;
+                let text = fill(auto_skipped, &[&name(player)]);
+                let banner = fill(
+                    auto_skipped,
+                    &[&names.join(&self.settings.messages.and_separator)],
+                );
+                self.clear_message();
+                self.message_color = self.settings.color_config.error_color();
+                write!(self.message, " {}", banner).unwrap();
+                self.message_log.push(*turn, self.message_color, &text);
+            }
+            GameEvent::Skip { player, .. } => {
+                self.clear_message();
+                self.message_color = self.settings.color_config.error_color();
+                write!(
+                    self.message,
+                    " {}",
+                    fill(
+                        &self.settings.messages.skipped,
+                        &[
+                            &self.board_display.player_name(*player),
+                            &self.settings.key_binding.key_str(Action::Select)
+                        ]
+                    )
+                )
+                .unwrap();
+                self.log_message();
+            }
+            GameEvent::Pass { .. } => self.clear_message(),
+            GameEvent::Finish { .. } => self.write_finish_message(),
+            GameEvent::Init { .. } | GameEvent::History { .. } => (),
+        }
+    }
+
+    /// Writes the scores, the flips, the thinking times, the winner, the record, and the match
+    /// of the finished game to the message.
+    fn write_finish_message(&mut self) {
+        self.clear_message();
+        let scores = self.format_scores();
+        let flips = self.format_player_values(&self.history.flip_totals());
+        let messages = &self.settings.messages;
+        let text = match (self.is_over(), self.turn_limit()) {
+            (false, Some(max_turns)) => fill(
+                &messages.game_finished_by_limit,
+                &[&max_turns, &scores, &flips],
+            ),
+            _ => fill(&messages.game_finished, &[&scores, &flips]),
+        };
+        write!(self.message, " {}", text).unwrap();
+        let think_totals = self.format_think_totals();
+        write!(
+            self.message,
+            " {}",
+            fill(&self.settings.messages.think_totals, &[&think_totals])
+        )
+        .unwrap();
+        self.write_winner();
+        self.update_record();
+        self.finish_match_game();
+        self.log_message();
+    }
+
+    /// Position flipping more stones than `flips` of the current human player by the margin of the coach
+    /// and the difference, or `None` if the coach is off.
+    fn better_move(&self, flips: u64) -> Option<((usize, usize), u64)> {
+        let margin = self.settings.coach_margin?;
+        if !self.is_local_turn() {
+            return None;
+        }
+        let (position, max_flips) = self.availables.max_flip_move(self.current_player)?;
+        (max_flips >= flips + margin.max(1)).then_some((position, max_flips - flips))
+    }
+
+    /// Generator of `component` at the current turn, so that replaying the same moves draws the same numbers.
+    fn rng(&self, component: Component) -> StdRng {
+        rng::child(
+            self.settings.seed,
+            component,
+            self.history.current_turn() as u64,
+        )
+    }
+
+    /// Moves the current position to the position selected by the suggestion strategy without selecting it.
+    fn suggest(&mut self) {
+        self.clear_message();
+        let rng = self.rng(Component::Suggestion);
+        match self
+            .settings
+            .suggestion_strategy
+            .strategy(rng, self.settings.search_config)
+            .select(&self.board, &self.availables, self.current_player)
+        {
+            Some(position) => {
+                self.current_position = position;
+                self.is_suggested = true;
+                self.message_color = self.settings.color_config.player(self.current_player);
+                write!(
+                    self.message,
+                    " {}",
+                    fill(
+                        &self.settings.messages.suggested,
+                        &[
+                            &position.0,
+                            &position.1,
+                            &self.board.flips(self.current_player, position).len()
+                        ]
+                    )
+                )
+                .unwrap();
+            }
+            None => {
+                self.message_color = self.settings.color_config.error_color();
+                write!(self.message, " {}", self.settings.messages.no_suggestion).unwrap();
+            }
+        }
+        self.log_message();
+    }
+
+    /// Formats values of players like `A = 1, B = 2 and C = 3`.
+    /// Final scores, with the breakdown into stones and territories under the territory scoring.
+    fn format_scores(&self) -> String {
+        if self.board.scoring() == Scoring::Stones {
+            return self.format_player_values(self.board.count());
+        }
+        let scores = self.board.scores();
+        let territories = self.board.territories();
+        let breakdowns = PLAYERS
+            .iter()
+            .map(|player| {
+                fill(
+                    &self.settings.messages.score_breakdown,
+                    &[
+                        &self.board_display.player_name(*player),
+                        &scores[*player],
+                        &self.board.count()[*player],
+                        &territories[*player],
+                    ],
+                )
+            })
+            .collect::<Vec<_>>();
+        self.join_player_values(&breakdowns)
+    }
+
+    /// Total time each player spent on the moves, e.g., `Cyan = 4m12s`.
+    fn format_think_totals(&self) -> String {
+        let think_totals = self.history.think_totals();
+        let totals = think_totals
+            .iter()
+            .map(|(player, think_total)| {
+                format!(
+                    "{} = {}",
+                    self.board_display.player_name(player),
+                    format_duration(*think_total)
+                )
+            })
+            .collect::<Vec<_>>();
+        self.join_player_values(&totals)
+    }
+
+    fn format_player_values(&self, count: &Count) -> String {
+        let values = PLAYERS
+            .iter()
+            .map(|player| {
+                format!(
+                    "{} = {}",
+                    self.board_display.player_name(*player),
+                    count[*player]
+                )
+            })
+            .collect::<Vec<_>>();
+        self.join_player_values(&values)
+    }
+
+    /// Values of players joined by `list_separator` and `and_separator` before the last.
+    fn join_player_values(&self, values: &[String]) -> String {
+        let messages = &self.settings.messages;
+        let (last, rest) = values.split_last().unwrap();
+        format!(
+            "{}{}{}",
+            rest.join(&messages.list_separator),
+            messages.and_separator,
+            last
+        )
+    }
+
+    fn select_in_play_skip(&mut self) {
+        self.think_stopwatch.restart();
+        let player = self.current_player;
+        let skipped = self.advance_turn();
+        self.emit(GameEvent::Pass {
+            turn: self.history.current_turn(),
+            player,
+            counts: event_log::counts(&self.board),
+            next_player: self.current_player,
+        });
+        for skipped_player in skipped {
+            self.emit_skip_event(skipped_player);
+        }
+    }
+
+    fn emit_skip_event(&mut self, player: Player) {
+        self.emit(GameEvent::Skip {
+            turn: self.history.current_turn(),
+            player,
+            counts: event_log::counts(&self.board),
+        });
+    }
+
+    /// Writes the winner resolved by the tiebreak rule to the message.
+    fn write_winner(&mut self) {
+        let tiebreak = self.settings.tiebreak;
+        let ranking = self.board.ranking(tiebreak, &self.skips, self.first_player);
+        let messages = &self.settings.messages;
+        let text = match self.board.winner(tiebreak, &self.skips, self.first_player) {
+            Some(winner) if ranking[0].1 == ranking[1].1 => fill(
+                &messages.winner_by_tiebreak,
+                &[
+                    &self.board_display.player_name(winner),
+                    &tiebreak.to_possible_value().unwrap().get_name(),
+                ],
+            ),
+            Some(winner) => fill(&messages.winner, &[&self.board_display.player_name(winner)]),
+            None => messages.drawn.clone(),
+        };
+        write!(self.message, " {}", text).unwrap();
+    }
+
+    /// Scrolls the board to the next region of `ViewFocus` at the current zoom.
+    fn center_view(&mut self) {
+        self.view_focus = self.view_focus.next();
+        let position = match self.view_focus {
+            ViewFocus::Center => self.board.center(),
+            ViewFocus::Home(player) => self.board.home_center(player),
+        };
+        self.board_display
+            .center_on(&self.board, position, self.board_rect);
+    }
+
+    /// Scrolls the board so that the cursor is at the middle of the board block.
+    fn center_cursor(&mut self) {
+        self.board_display
+            .center_on(&self.board, self.current_position, self.board_rect);
+    }
+
+    /// Scrolls the board by a page in the direction of `action`.
+    fn scroll_page(&mut self, action: Action) {
+        let pages = match action {
+            Action::ScrollPageUp => (0, -1),
+            Action::ScrollPageDown => (0, 1),
+            Action::ScrollPageLeft => (-1, 0),
+            _ => (1, 0),
+        };
+        self.board_display
+            .scroll_page(&self.board, pages, self.board_rect);
+    }
+
+    fn enter_history(&mut self) {
+        self.ai_worker = None;
+        self.update_status(Status::Play(Play::History));
+    }
+
+    fn leave_history(&mut self) {
+        self.update_status(Status::Play(Play::Turn));
+    }
+
+    /// Starts browsing the history at `turn`, or at the last move if it is `None`.
+    pub fn view_turn(&mut self, turn: Option<usize>) -> Result<(), TriversiError> {
+        let len = self.history.branch().len();
+        let turn = turn.unwrap_or(len);
+        if !self.history.go_to(turn) {
+            return Err(TriversiError::TurnBeyondGame(turn, len));
+        }
+        self.enter_history();
+        self.clear_message();
+        self.show_history_board();
+        self.emit_history_event();
+        write!(
+            self.message,
+            " {}",
+            fill(
+                &self.settings.messages.browsing_turn,
+                &[
+                    &turn,
+                    &len,
+                    &self.settings.key_binding.key_str(Action::Select)
+                ]
+            )
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    fn history_move(&mut self, action: Action) {
+        if action == Action::PrevHistory {
+            self.history.go_prev();
+        } else {
+            self.history.go_next();
+        }
+        self.clear_message();
+        self.show_history_board();
+        self.emit_history_event();
+        self.write_annotation_message();
+    }
+
+    /// Takes back moves until a human player on this terminal is to move,
+    /// where the taken-back moves are kept to be redone.
+    fn undo(&mut self) {
+        self.clear_message();
+        if !self.history.can_undo() {
+            self.message_color = self.settings.color_config.error_color();
+            write!(self.message, " {}", self.settings.messages.nothing_to_undo).unwrap();
+            return;
+        }
+        self.ai_worker = None;
+        loop {
+            self.history.go_prev();
+            self.show_history_board();
+            if self.is_local_turn() || !self.history.can_undo() {
+                break;
+            }
+        }
+        self.skips = self.history.skips();
+        self.think_stopwatch.restart();
+        self.update_status(Status::Play(Play::Turn));
+        self.emit_history_event();
+        write!(
+            self.message,
+            " {}",
+            fill(
+                &self.settings.messages.undone,
+                &[
+                    &(self.history.current_turn() + 1),
+                    &(self.history.branch().len() - self.history.current_turn()),
+                    &self.settings.key_binding.key_str(Action::Redo)
+                ]
+            )
+        )
+        .unwrap();
+    }
+
+    /// Plays the undone moves again until a human player on this terminal is to move.
+    fn redo(&mut self) {
+        if !self.history.can_redo() {
+            self.clear_message();
+            self.message_color = self.settings.color_config.error_color();
+            write!(self.message, " {}", self.settings.messages.nothing_to_redo).unwrap();
+            return;
+        }
+        self.ai_worker = None;
+        loop {
+            self.show_history_board();
+            self.select_in_play_turn();
+            if self.current_status != Status::Play(Play::Turn)
+                || self.is_local_turn()
+                || !self.history.can_redo()
+            {
+                break;
+            }
+        }
+    }
+
+    /// Switches to the next branch of the history, where the turn is kept if the branch reaches it.
+    fn switch_branch(&mut self) {
+        let branches = self.history.branches();
+        let current_id = self.history.branch().id();
+        let next_id = branches
+            .iter()
+            .map(|branch| branch.id())
+            .filter(|id| *id > current_id)
+            .min()
+            .unwrap_or_else(|| branches[0].id());
+        self.history.switch_branch(next_id);
+        self.annotator = None;
+        self.annotations = None;
+        self.clear_message();
+        self.show_history_board();
+        self.emit_history_event();
+        let branch = self.history.branch();
+        let number = self
+            .history
+            .branches()
+            .iter()
+            .position(|other| other.id() == branch.id())
+            .unwrap()
+            + 1;
+        let values: [&dyn Display; 5] = [
+            branch.name(),
+            &number,
+            &self.history.branches().len(),
+            &branch.len(),
+            &(branch.fork_turn() + 1),
+        ];
+        let messages = &self.settings.messages;
+        let text = if branch.id() == 0 {
+            fill(&messages.branch, &values)
+        } else {
+            fill(&messages.branch_diverging, &values)
+        };
+        write!(self.message, " {}", text).unwrap();
+    }
+
+    /// Shows the board of the history at the current turn.
+    fn show_history_board(&mut self) {
+        self.board = self.history.board().clone();
+        self.last_capture = None;
+        if self.history.past_player().is_some() {
+            self.current_player = self.history.past_player().unwrap();
+        }
+        if self.history.past_position().is_some() {
+            self.current_position = self.history.past_position().unwrap();
+        }
+        self.update_available_list();
+    }
+
+    /// Starts evaluating recorded moves on a background thread unless they are annotated.
+    fn annotate(&mut self) {
+        if self.annotations.is_some() || self.annotator.is_some() {
+            return;
+        }
+        let moves = self.history.moves_with_boards();
+        self.clear_message();
+        write!(
+            self.message,
+            " {}",
+            fill(&self.settings.messages.annotating, &[&0, &moves.len()])
+        )
+        .unwrap();
+        self.annotator = Some(Annotator::spawn(
+            moves,
+            self.settings.suggestion_strategy,
+            self.settings.search_config,
+            self.rng(Component::Suggestion),
+        ));
+    }
+
+    fn poll_annotator(&mut self) {
+        let Some(annotator) = &self.annotator else {
+            return;
+        };
+        let in_history = self.current_status == Status::Play(Play::History);
+        match annotator.try_progress() {
+            Ok(Progress::Annotating(done, total)) => {
+                if in_history {
+                    self.clear_message();
+                    write!(
+                        self.message,
+                        " {}",
+                        fill(&self.settings.messages.annotating, &[&done, &total])
+                    )
+                    .unwrap();
+                }
+            }
+            Ok(Progress::Finished(annotations)) => {
+                self.annotator = None;
+                let count = |quality| {
+                    annotations
+                        .iter()
+                        .filter(|annotation| annotation.quality() == quality)
+                        .count()
+                };
+                let counts = [
+                    Quality::Best,
+                    Quality::Good,
+                    Quality::Inaccuracy,
+                    Quality::Blunder,
+                ]
+                .map(count);
+                self.annotations = Some(annotations);
+                if in_history {
+                    self.clear_message();
+                    write!(
+                        self.message,
+                        " {}",
+                        fill(
+                            &self.settings.messages.annotated,
+                            &[&counts[0], &counts[1], &counts[2], &counts[3]]
+                        )
+                    )
+                    .unwrap();
+                    self.log_message();
+                }
+            }
+            Err(TryRecvError::Empty) => (),
+            Err(TryRecvError::Disconnected) => self.annotator = None,
+        }
+    }
+
+    /// Annotation of the move selected next from the board shown in the history.
+    fn current_annotation(&self) -> Option<Annotation> {
+        self.annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(self.history.current_turn()))
+            .copied()
+    }
+
+    /// Writes the annotation of the move selected next in the history if it is not good.
+    fn write_annotation_message(&mut self) {
+        let (Some(annotation), Some(player)) =
+            (self.current_annotation(), self.history.past_player())
+        else {
+            return;
+        };
+        if annotation.mark().is_empty() {
+            return;
+        }
+        self.message_color = self.settings.color_config.error_color();
+        let messages = &self.settings.messages;
+        let (x, y) = annotation.best().unzip();
+        let values: [&dyn Display; 7] = [
+            &(self.history.current_turn() + 1),
+            &self.board_display.player_name(player),
+            &self.quality_label(annotation.quality()),
+            &annotation.mark(),
+            &annotation.delta(),
+            &x.unwrap_or_default(),
+            &y.unwrap_or_default(),
+        ];
+        let text = match annotation.best() {
+            Some(_) => fill(&messages.annotation_with_best, &values),
+            None => fill(&messages.annotation, &values),
+        };
+        write!(self.message, " {}", text).unwrap();
+    }
+
+    /// Label of `quality` in the language of the messages.
+    fn quality_label(&self, quality: Quality) -> &str {
+        let messages = &self.settings.messages;
+        match quality {
+            Quality::Best => &messages.quality_best,
+            Quality::Good => &messages.quality_good,
+            Quality::Inaccuracy => &messages.quality_inaccuracy,
+            Quality::Blunder => &messages.quality_blunder,
+        }
+    }
+
+    /// Writes the code of the board to the message, which is given to `--from-code` to start from the board.
+    fn copy_code(&mut self) {
+        self.clear_message();
+        write!(
+            self.message,
+            " {}",
+            fill(&self.settings.messages.code, &[&self.board.to_code()])
+        )
+        .unwrap();
+        self.log_message();
+    }
+
+    /// Writes the board, the scores, and the turn number to a plain text file in `dump_dir`.
+    fn dump_board(&mut self) {
+        let mut text = format!("Turn {}\n\n{}\n", self.history.current_turn(), self.board);
+        for (i_player, player) in PLAYERS.iter().enumerate() {
+            writeln!(
+                text,
+                "{}: {} = {}",
+                i_player + 1,
+                self.board_display.player_name(*player),
+                self.board.count()[*player]
+            )
+            .unwrap();
+        }
+        writeln!(text, "\n{}", to_fen(&self.board, self.current_player)).unwrap();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let path = self
+            .settings
+            .dump_dir
+            .join(format!("triversi-dump-{}.txt", timestamp));
+        self.clear_message();
+        match fs::write(&path, text) {
+            Ok(()) => write!(
+                self.message,
+                " {}",
+                fill(&self.settings.messages.dumped, &[&path.display()])
+            )
+            .unwrap(),
+            Err(err) => {
+                self.message_color = self.settings.color_config.error_color();
+                write!(
+                    self.message,
+                    " {}",
+                    fill(
+                        &self.settings.messages.dump_failed,
+                        &[&path.display(), &err]
+                    )
+                )
+                .unwrap();
+            }
+        }
+        self.log_message();
+    }
+
+    /// Archives the game to `export_game`, or to a new file in `dump_dir` if it is not given.
+    fn export_game_by_key(&mut self) {
+        let path = self.settings.export_game.clone().unwrap_or_else(|| {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+            self.settings
+                .dump_dir
+                .join(format!("triversi-game-{}.tvr", timestamp))
+        });
+        if self.export_game(&path) {
+            self.clear_message();
+            write!(
+                self.message,
+                " {}",
+                fill(&self.settings.messages.exported, &[&path.display()])
+            )
+            .unwrap();
+            self.log_message();
+        }
+    }
+
+    /// Archives the game to `path`, or writes the error to the message.
+    fn export_game(&mut self, path: &Path) -> bool {
+        let record = self.history.record();
+        let archive = Archive {
+            date: archive::today(),
+            version: record.version().clone(),
+            timestamp: record.timestamp(),
+            range: record.range(),
+            variant: record.variant(),
+            seed: *record.seed(),
+            handicap: record.handicap(),
+            first_player: record.first_player(),
+            swapped: record.swapped(),
+            player_names: PLAYERS
+                .iter()
+                .map(|player| self.board_display.player_name(*player).to_owned())
+                .collect(),
+            player_marks: record.player_marks().clone(),
+            score: self.board.count().values().copied().collect(),
+            moves: record
+                .moves()
+                .into_iter()
+                .enumerate()
+                .map(|(i_move, player_move)| (i_move + 1, player_move))
+                .collect(),
+        };
+        match fs::write(path, archive.to_string()) {
+            Ok(()) => true,
+            Err(err) => {
+                self.clear_message();
+                self.message_color = self.settings.color_config.error_color();
+                write!(
+                    self.message,
+                    " {}",
+                    fill(
+                        &self.settings.messages.export_failed,
+                        &[&path.display(), &err]
+                    )
+                )
+                .unwrap();
+                self.log_message();
+                false
+            }
+        }
+    }
+
+    /// Renders the screen at the size of the terminal into a buffer and writes it to `path`,
+    /// or writes the error to the message.
+    fn write_screenshot(&mut self, path: &Path) {
+        let (width, height) = termion::terminal_size().unwrap_or_else(|_| self.min_terminal_size());
+        let result = Terminal::new(TestBackend::new(width, height))
+            .and_then(|mut terminal| {
+                terminal.draw(|frame| self.ui(frame))?;
+                let buffer = terminal.backend().buffer();
+                Ok(if screenshot::is_ansi(path) {
+                    screenshot::to_ansi(buffer)
+                } else {
+                    screenshot::to_plain(buffer)
+                })
+            })
+            .and_then(|text| fs::write(path, text));
+        if let Err(err) = result {
+            self.message_color = self.settings.color_config.error_color();
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.screenshot_failed,
+                    &[&path.display(), &err]
+                )
+            )
+            .unwrap();
+            self.log_message();
+        }
+    }
+
+    /// Updates the best score of the range with the largest count of human players,
+    /// which is told in the message when it is beaten. Puzzles are not recorded.
+    fn update_record(&mut self) {
+        if self.puzzle.is_some() {
+            return;
+        }
+        let count = self.board.count();
+        let Some(best) = PLAYERS
+            .iter()
+            .filter(|player| self.controller(**player) == Controller::Human)
+            .map(|player| count[*player])
+            .max()
+        else {
+            return;
+        };
+        let (Some(records), Some(path)) = (&mut self.records, &self.settings.records) else {
+            return;
+        };
+        let range = self.board.range();
+        if !records.update(range, best, archive::today()) {
+            return;
+        }
+        match records.save(path) {
+            Ok(()) => write!(
+                self.message,
+                " {}",
+                fill(&self.settings.messages.new_record, &[&best, &range])
+            )
+            .unwrap(),
+            Err(err) => write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.record_not_saved,
+                    &[&format!("{:#}", err)]
+                )
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Writes statistics of moves to `stats_csv` if it is given.
+    fn write_stats_csv(&mut self) {
+        let Some(path) = self.settings.stats_csv.clone() else {
+            return;
+        };
+        let mut text = String::from("turn,player,position,think_ms,flips,resulting_count\n");
+        for stats in self.history.move_stats() {
+            writeln!(
+                text,
+                "{},{},\"({}, {})\",{},{},{}",
+                stats.turn(),
+                self.board_display.player_name(stats.player()),
+                stats.position().0,
+                stats.position().1,
+                stats.think_time().as_millis(),
+                stats.flips(),
+                stats.count(),
+            )
+            .unwrap();
+        }
+        if let Err(err) = fs::write(&path, text) {
+            self.clear_message();
+            self.message_color = self.settings.color_config.error_color();
+            write!(
+                self.message,
+                " {}",
+                fill(
+                    &self.settings.messages.stats_failed,
+                    &[&path.display(), &err]
+                )
+            )
+            .unwrap();
+            self.log_message();
+        }
+    }
+
+    /// Moves the cursor up on the screen, which is down on the board turned upside down.
+    fn move_cursor_up(&mut self) {
+        if self.board_display.is_upside_down() {
+            self.board.move_position_down(&mut self.current_position)
+        } else {
+            self.board.move_position_up(&mut self.current_position)
+        }
+    }
+
+    /// Moves the cursor down on the screen, which is up on the board turned upside down.
+    fn move_cursor_down(&mut self) {
+        if self.board_display.is_upside_down() {
+            self.board.move_position_up(&mut self.current_position)
+        } else {
+            self.board.move_position_down(&mut self.current_position)
+        }
+    }
+
+    /// Turns the board upside down or back, keeping the cursor in view.
+    fn rotate_board(&mut self) {
+        if self.board_display.toggle_upside_down() {
+            self.board_display.scroll_into_view(
+                &self.board,
+                self.current_position,
+                self.board_rect,
+            );
+        } else {
+            self.clear_message();
+            self.message_color = self.settings.color_config.error_color();
+            write!(
+                self.message,
+                " {}",
+                self.settings.messages.rotate_unavailable
+            )
+            .unwrap();
+            self.log_message();
+        }
+    }
+
+    fn open_message_log(&mut self) {
+        self.message_log_scroll = 0;
+        self.update_status(Status::MessageLog);
+    }
+
+    /// Writes the description of the current state to the message log and opens it.
+    fn describe(&mut self, play: Play) {
+        let headline = self.status_span(play).content.trim().to_owned();
+        let availables = matches!(play, Play::Turn | Play::Skipped).then_some(&self.availables);
+        let text = description::describe(
+            &self.board,
+            &headline,
+            &self.player_names(),
+            self.current_player,
+            self.cursor(),
+            availables,
+            self.settings.describe_rows,
+        )
+        .join("\n");
+        self.message_log
+            .push(self.history.current_turn(), Color::Reset, &text);
+        self.open_message_log();
+    }
+
+    fn browse_message_log(&mut self, key: Key) {
+        match self.settings.key_binding.action(key) {
+            Some(Action::MoveDown | Action::ScrollDown)
+                if self.message_log_scroll + 1 < self.message_log.len() =>
+            {
+                self.message_log_scroll += 1
+            }
+            Some(Action::MoveUp | Action::ScrollUp) => {
+                self.message_log_scroll = self.message_log_scroll.saturating_sub(1)
+            }
+            Some(Action::ScrollReset) => self.message_log_scroll = 0,
+            Some(Action::MessageLog | Action::Select | Action::Quit) => {
+                self.update_status(self.previous_status)
+            }
+            _ => (),
+        }
+    }
+
+    fn ask_quit(&mut self, key: Key) {
+        match key {
+            Key::Char('Y') => self.update_status(Status::Quit),
+            Key::Char('s') if self.is_match_continuing() => {
+                if self.save_match() {
+                    self.update_status(Status::Quit);
+                } else {
+                    self.update_status(self.previous_status);
+                }
+            }
+            _ => self.update_status(self.previous_status),
+        }
+    }
+
+    fn ask_swap(&mut self, key: Key) {
+        self.update_status(self.previous_status);
+        if key == Key::Char('Y') {
+            self.swap_seats();
+        }
+    }
+
+    /// Asks the name of the slot to save the game in, which is available only in a single local game.
+    fn ask_slot_name(&mut self) {
+        if self.network.is_some() || self.puzzle.is_some() || self.match_state.is_some() {
+            self.clear_message();
+            self.message_color = self.settings.color_config.error_color();
+            write!(self.message, " {}", self.settings.messages.single_game_only).unwrap();
+            return;
+        }
+        self.slot_name.clear();
+        self.update_status(Status::AskSlot);
+    }
+
+    fn ask_slot(&mut self, key: Key) {
+        match key {
+            Key::Char('\n') if !self.slot_name.is_empty() => {
+                let exists = self
+                    .settings
+                    .saves_dir
+                    .as_ref()
+                    .is_some_and(|dir| SavedGame::path(dir, &self.slot_name).exists());
+                if exists {
+                    // Previous status is kept to go back to the game after the confirmation.
+                    self.current_status = Status::AskOverwrite;
+                } else {
+                    self.update_status(self.previous_status);
+                    self.save_game();
+                }
+            }
+            Key::Char(c) if saves::is_name_char(c) && self.slot_name.len() < MAX_SLOT_NAME_LEN => {
+                self.slot_name.push(c)
+            }
+            Key::Backspace => {
+                self.slot_name.pop();
+            }
+            Key::Esc => self.update_status(self.previous_status),
+            _ => (),
+        }
+    }
+
+    fn ask_overwrite(&mut self, key: Key) {
+        self.update_status(self.previous_status);
+        if key == Key::Char('Y') {
+            self.save_game();
+        }
+    }
+
+    /// Saves the record, the configuration, and the current turn in the slot of `slot_name`.
+    fn save_game(&mut self) {
+        self.clear_message();
+        let Some(dir) = self.settings.saves_dir.clone() else {
+            self.message_color = self.settings.color_config.error_color();
+            write!(
+                self.message,
+                " {}",
+                self.settings.messages.saves_dir_unknown
+            )
+            .unwrap();
+            self.log_message();
+            return;
+        };
+        let saved_game = SavedGame::new(
+            archive::today(),
+            self.settings.config.clone(),
+            self.history.record().clone(),
+            self.history.current_turn(),
+        );
+        match saved_game.save(&dir, &self.slot_name) {
+            Ok(()) => write!(
+                self.message,
+                " {}",
+                fill(&self.settings.messages.saved, &[&self.slot_name])
+            )
+            .unwrap(),
+            Err(err) => {
+                self.message_color = self.settings.color_config.error_color();
+                write!(
+                    self.message,
+                    " {}",
+                    fill(&self.settings.messages.not_saved, &[&format!("{:#}", err)])
+                )
+                .unwrap();
+            }
+        }
+        self.log_message();
+    }
+
+    fn ask_init(&mut self, key: Key) {
+        match key {
+            Key::Char('Y') => self.init(),
+            _ => self.update_status(self.previous_status),
+        }
+    }
+
+    /// Size of the terminal needed to render the blocks and a part of the board at the current zoom.
+    fn min_terminal_size(&self) -> (u16, u16) {
+        let (min_board_width, min_board_height) = self.board_display.min_board_block_size();
+        // The compact HUD is cut at the right edge instead of requiring the width.
+        let status_row_width = if self.compact_hud {
+            0
+        } else {
+            self.player_box_width()
+                + POSITION_BOX_WIDTH
+                + SCROLL_BOX_WIDTH
+                + ZOOM_BOX_WIDTH
+                + self.legend_box_width()
+                + MIN_MESSAGE_BOX_WIDTH
+        };
+        // The side panel takes a half of the width.
+        let board_row_width = if self.has_side_panel() {
+            2 * min_board_width
+        } else {
+            min_board_width
+        };
+        (
+            cmp::max(status_row_width, board_row_width),
+            self.hud_height() + min_board_height,
+        )
+    }
+
+    fn ui_too_small<B: Backend>(&self, frame: &mut Frame<B>, min_width: u16, min_height: u16) {
+        let size = frame.size();
+        let rect = Rect::new(
+            0,
+            size.height / 2,
+            size.width,
+            size.height - size.height / 2,
+        );
+        frame.render_widget(
+            Paragraph::new(fill(
+                &self.settings.messages.terminal_too_small,
+                &[&min_width, &min_height],
+            ))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+            rect,
+        );
+    }
+
+    fn player_box_width(&self) -> u16 {
+        6 + PLAYERS
+            .iter()
+            .map(|player| {
+                self.board_display.player_name(*player).width_cjk()
+                    + format!("({})", self.mobility(*player)).len()
+                    + self.controller_mark(*player).map_or(0, str::len)
+                    + self.first_mark(*player).map_or(0, str::len)
+            })
+            .sum::<usize>() as u16
+            + 2
+            + self.leader_text().width_cjk() as u16
+    }
+
+    /// Width of the legend block, which is zero if it is hidden.
+    fn legend_box_width(&self) -> u16 {
+        if !self.legend_visibility {
+            return 0;
+        }
+        // Marks and names are separated by a space, and players by two spaces.
+        (2 + 2 * (PLAYERS.len() - 1)
+            + PLAYERS
+                .iter()
+                .map(|player| {
+                    self.board_display.player_mark(*player).width()
+                        + 1
+                        + self.board_display.player_name(*player).width_cjk()
+                })
+                .sum::<usize>()) as u16
+    }
+
+    fn ui_play<B: Backend>(&mut self, frame: &mut Frame<B>, play: Play) {
+        let debug_box_width = if self.has_side_panel() {
+            frame.size().width / 2
+        } else {
+            0
+        };
+        let hud_height = self.hud_height();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(hud_height),
+                    Constraint::Length(frame.size().height - hud_height),
+                ]
+                .as_ref(),
+            )
+            .split(frame.size());
+        let chunks_2 = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Length(frame.size().width - debug_box_width),
+                    Constraint::Length(debug_box_width),
+                ]
+                .as_ref(),
+            )
+            .split(chunks[1]);
+        if self.compact_hud {
+            self.render_compact_hud(frame, chunks[0], play, chunks_2[0]);
+        } else {
+            self.render_hud(frame, chunks[0], play, chunks_2[0]);
+        }
+        self.board_rect = chunks_2[0];
+        let capture_lines = self.capture_lines(play);
+        let capture_arrows = self.capture_arrows();
+        let move_diff = self.move_diff(play);
+        let fog = self.fog(play);
+        let mut color_config = if self.is_suggested {
+            self.settings.color_config.with_blinking_cursor()
+        } else if self.notifier.is_flashing()
+            || play == Play::History
+                && self
+                    .current_annotation()
+                    .is_some_and(|annotation| annotation.is_blunder())
+        {
+            self.settings.color_config.with_alert_border()
+        } else {
+            self.settings.color_config
+        };
+        // Legality is told only while the human on this terminal is selecting a position.
+        if self.current_status == Status::Play(Play::Turn) && self.is_local_turn() {
+            color_config = color_config.with_cursor_legality(
+                self.availables[self.current_player].contains_key(&self.current_position),
+            );
+        }
+        self.board_display.render_board_block(
+            frame,
+            chunks_2[0],
+            &self.settings.messages.board_title,
+            &self.board,
+            color_config,
+            play,
             self.current_player,
             self.current_position,
+            capture_lines.as_ref(),
+            capture_arrows.as_ref(),
+            move_diff.as_ref(),
+            fog.as_ref(),
+        );
+        match self.debug_panel {
+            DebugPanel::Off if self.shows_standings() => {
+                self.render_standings_block(frame, chunks_2[1]);
+                return;
+            }
+            DebugPanel::Off if self.flip_panel_visibility => {
+                self.render_flips_block(frame, chunks_2[1], play);
+                return;
+            }
+            DebugPanel::Off => return,
+            DebugPanel::History => self.write_debug_info_of_history(),
+            DebugPanel::Availables => self.write_debug_info_of_available_position(play),
+        }
+        frame.render_widget(
+            Paragraph::new(self.debug_information.as_ref())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("DebugInformation"),
+                )
+                .wrap(Wrap { trim: false }),
+            chunks_2[1],
+        );
+    }
+
+    fn ui_ask_init<B: Backend>(&self, frame: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .margin(1)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Length(3),
+                Constraint::Percentage(50),
+            ])
+            .split(frame.size());
+        frame.render_widget(
+            Paragraph::new(self.settings.messages.init_question.as_str())
+                .alignment(Alignment::Center)
+                .block(Block::default()),
+            chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new(self.settings.messages.yes_no.as_str())
+                .alignment(Alignment::Center)
+                .block(Block::default()),
+            chunks[2],
+        );
+    }
+
+    fn ui_ask_swap<B: Backend>(&self, frame: &mut Frame<B>) {
+        let (first, second) = self.swap_pair();
+        let chunks = Layout::default()
+            .margin(1)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Length(3),
+                Constraint::Percentage(50),
+            ])
+            .split(frame.size());
+        frame.render_widget(
+            Paragraph::new(fill(
+                &self.settings.messages.swap_question,
+                &[
+                    &self.board_display.player_name(first),
+                    &self.board_display.player_name(second),
+                ],
+            ))
+            .alignment(Alignment::Center)
+            .block(Block::default()),
+            chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new(self.settings.messages.yes_no.as_str())
+                .alignment(Alignment::Center)
+                .block(Block::default()),
+            chunks[2],
+        );
+    }
+
+    fn ui_select_size<B: Backend>(&self, frame: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .margin(1)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Length(2),
+                Constraint::Length(2 * SIZE_MENU_SIDE as u16 + 1),
+                Constraint::Length(2),
+                Constraint::Min(0),
+            ])
+            .split(frame.size());
+        frame.render_widget(
+            Paragraph::new(self.settings.messages.size_question.as_str())
+                .alignment(Alignment::Center)
+                .block(Block::default()),
+            chunks[1],
+        );
+        let valid = |range: &usize| Board::validate_range(*range).is_ok();
+        let mut ranges = (MIN_RANGE..self.size_selection)
+            .rev()
+            .filter(valid)
+            .take(SIZE_MENU_SIDE)
+            .collect::<Vec<_>>();
+        ranges.reverse();
+        ranges.extend(
+            (self.size_selection..)
+                .filter(valid)
+                .take(SIZE_MENU_SIDE + 1),
+        );
+        let entries = ranges
+            .into_iter()
+            .map(|range| {
+                let entry = fill(
+                    &self.settings.messages.size_entry,
+                    &[&range, &(range * (range + 1) / 2)],
+                );
+                if range == self.size_selection {
+                    Spans::from(Span::styled(
+                        format!("▶ {} ◀", entry),
+                        Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                    ))
+                } else {
+                    Spans::from(entry)
+                }
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            Paragraph::new(entries)
+                .alignment(Alignment::Center)
+                .block(Block::default()),
+            chunks[2],
+        );
+        frame.render_widget(
+            Paragraph::new(fill(
+                &self.settings.messages.size_hint,
+                &[
+                    &self.settings.key_binding.key_str(Action::MoveUp),
+                    &self.settings.key_binding.key_str(Action::MoveDown),
+                    &self.settings.key_binding.key_str(Action::Select),
+                    &self.settings.key_binding.key_str(Action::Quit),
+                ],
+            ))
+            .alignment(Alignment::Center)
+            .block(Block::default()),
+            chunks[3],
+        );
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                self.message.as_str(),
+                Style::default().fg(self.message_color),
+            ))
+            .alignment(Alignment::Center)
+            .block(Block::default()),
+            chunks[4],
         );
-        #[cfg(debug_assertions)]
-        {
-            // self.write_debug_info_of_available_position(play);
-            self.write_debug_info_of_history();
-            frame.render_widget(
-                Paragraph::new(self.debug_information.as_ref())
-                    .block(
-                        Block::default()
-                            .borders(Borders::ALL)
-                            .title("DebugInformation"),
-                    )
-                    .wrap(Wrap { trim: false }),
-                chunks_2[1],
-            );
-        }
     }
 
-    fn ui_ask_init<B: Backend>(&self, frame: &mut Frame<B>) {
+    fn ui_ask_slot<B: Backend>(&self, frame: &mut Frame<B>) {
         let chunks = Layout::default()
             .margin(1)
             .constraints([
@@ -464,19 +2854,100 @@ impl<D: BoardDisplay> System<D> {
             ])
             .split(frame.size());
         frame.render_widget(
-            Paragraph::new("Are you sure to initialize?")
+            Paragraph::new(self.settings.messages.slot_question.as_str())
                 .alignment(Alignment::Center)
                 .block(Block::default()),
             chunks[1],
         );
         frame.render_widget(
-            Paragraph::new("Y / [n]")
+            Paragraph::new(vec![
+                Spans::from(Span::styled(
+                    format!("{}_", self.slot_name),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Spans::default(),
+                Spans::from(fill(
+                    &self.settings.messages.slot_hint,
+                    &[&MAX_SLOT_NAME_LEN],
+                )),
+            ])
+            .alignment(Alignment::Center)
+            .block(Block::default()),
+            chunks[2],
+        );
+    }
+
+    fn ui_ask_overwrite<B: Backend>(&self, frame: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .margin(1)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Length(3),
+                Constraint::Percentage(50),
+            ])
+            .split(frame.size());
+        frame.render_widget(
+            Paragraph::new(fill(
+                &self.settings.messages.overwrite_question,
+                &[&self.slot_name],
+            ))
+            .alignment(Alignment::Center)
+            .block(Block::default()),
+            chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new(self.settings.messages.yes_no.as_str())
                 .alignment(Alignment::Center)
                 .block(Block::default()),
             chunks[2],
         );
     }
 
+    fn ui_message_log<B: Backend>(&self, frame: &mut Frame<B>) {
+        let lines = self
+            .message_log
+            .iter_newest_first()
+            .skip(self.message_log_scroll)
+            .flat_map(|entry| {
+                // Lines following the first one, e.g. of descriptions, are indented below the turn.
+                let prefix = format!(
+                    " {} ",
+                    fill(
+                        &self.settings.messages.message_log_turn,
+                        &[&format!("{:>3}", entry.turn())]
+                    )
+                );
+                let indent = " ".repeat(prefix.width());
+                entry.text().lines().enumerate().map(move |(i, line)| {
+                    Spans::from(vec![
+                        Span::styled(
+                            if i == 0 {
+                                prefix.clone()
+                            } else {
+                                indent.clone()
+                            },
+                            Style::default().add_modifier(Modifier::DIM),
+                        ),
+                        Span::styled(line, Style::default().fg(entry.color())),
+                    ])
+                })
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(fill(
+                    &self.settings.messages.message_log_title,
+                    &[
+                        &self.settings.key_binding.key_str(Action::MoveDown),
+                        &self.settings.key_binding.key_str(Action::MoveUp),
+                        &self.settings.key_binding.key_str(Action::MessageLog),
+                    ],
+                )))
+                .wrap(Wrap { trim: false }),
+            frame.size(),
+        );
+    }
+
     fn ui_ask_quit<B: Backend>(&self, frame: &mut Frame<B>) {
         let chunks = Layout::default()
             .margin(1)
@@ -487,19 +2958,147 @@ impl<D: BoardDisplay> System<D> {
             ])
             .split(frame.size());
         frame.render_widget(
-            Paragraph::new("Are you sure to quit?")
+            Paragraph::new(self.settings.messages.quit_question.as_str())
                 .alignment(Alignment::Center)
                 .block(Block::default()),
             chunks[1],
         );
         frame.render_widget(
-            Paragraph::new("Y / [n]")
-                .alignment(Alignment::Center)
-                .block(Block::default()),
+            Paragraph::new(if self.is_match_continuing() {
+                self.settings.messages.yes_no_save.as_str()
+            } else {
+                self.settings.messages.yes_no.as_str()
+            })
+            .alignment(Alignment::Center)
+            .block(Block::default()),
             chunks[2],
         );
     }
 
+    /// Number of rows above the board.
+    fn hud_height(&self) -> u16 {
+        if self.compact_hud {
+            COMPACT_HUD_HEIGHT
+        } else {
+            GUIDANCE_BOX_HEIGHT + MESSAGE_BOX_HEIGHT + STATUS_STRIP_HEIGHT
+        }
+    }
+
+    /// Guidance, blocks of players, the position, the scroll, the zoom, the legend, and the message,
+    /// and the status strip, where `board_rect` is the board block.
+    fn render_hud<B: Backend>(
+        &self,
+        frame: &mut Frame<B>,
+        rect: Rect,
+        play: Play,
+        board_rect: Rect,
+    ) {
+        let player_box_width = self.player_box_width();
+        let legend_box_width = self.legend_box_width();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(GUIDANCE_BOX_HEIGHT),
+                    Constraint::Length(MESSAGE_BOX_HEIGHT),
+                    Constraint::Length(STATUS_STRIP_HEIGHT),
+                ]
+                .as_ref(),
+            )
+            .split(rect);
+        let chunks_1 = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Length(player_box_width),
+                    Constraint::Length(POSITION_BOX_WIDTH),
+                    Constraint::Length(SCROLL_BOX_WIDTH),
+                    Constraint::Length(ZOOM_BOX_WIDTH),
+                    Constraint::Length(legend_box_width),
+                    Constraint::Length(
+                        rect.width
+                            - player_box_width
+                            - POSITION_BOX_WIDTH
+                            - SCROLL_BOX_WIDTH
+                            - ZOOM_BOX_WIDTH
+                            - legend_box_width,
+                    ),
+                ]
+                .as_ref(),
+            )
+            .split(chunks[1]);
+        let guidance = match play {
+            _ if self.current_status == Status::PuzzleResult => {
+                self.settings.key_binding.make_guidance_in_puzzle_result()
+            }
+            Play::History => self.settings.key_binding.make_guidance_in_history(),
+            Play::Finished => self.settings.key_binding.make_guidance_in_finished(),
+            Play::Turn | Play::Skipped => self.settings.key_binding.make_guidance_in_turn(),
+        };
+        self.render_guidance_block(frame, chunks[0], guidance);
+        self.render_player_block(frame, chunks_1[0], play);
+        self.render_position_block(frame, chunks_1[1]);
+        let messages = &self.settings.messages;
+        self.board_display.render_scroll_block(
+            frame,
+            chunks_1[2],
+            &messages.scroll_title,
+            &self.board,
+            board_rect,
+        );
+        self.board_display
+            .render_zoom_block(frame, chunks_1[3], &messages.zoom_title);
+        if self.legend_visibility {
+            self.render_legend_block(frame, chunks_1[4]);
+        }
+        self.render_message_block(frame, chunks_1[5]);
+        frame.render_widget(Paragraph::new(self.status_span(play)), chunks[2]);
+    }
+
+    /// A line of the status, players, the position, the scroll, and the zoom, followed by a line of
+    /// the message, which leave the rest of the terminal to the board.
+    fn render_compact_hud<B: Backend>(
+        &self,
+        frame: &mut Frame<B>,
+        rect: Rect,
+        play: Play,
+        board_rect: Rect,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)].as_ref())
+            .split(rect);
+        let separator = || Span::styled(" │ ", Style::default().add_modifier(Modifier::DIM));
+        let mut spans = vec![self.status_span(play), separator()];
+        spans.extend(self.player_spans(play));
+        spans.push(separator());
+        let messages = &self.settings.messages;
+        spans.push(Span::raw(fill(
+            &messages.hud_position,
+            &[&self.current_position.0, &self.current_position.1],
+        )));
+        spans.push(separator());
+        spans.push(Span::raw(fill(
+            &messages.hud_scroll,
+            &[&self.board_display.scroll_text(&self.board, board_rect)],
+        )));
+        spans.push(separator());
+        spans.push(Span::raw(fill(
+            &messages.hud_zoom,
+            &[&self.board_display.zoom_level()],
+        )));
+        spans.push(separator());
+        spans.push(Span::styled(
+            fill(
+                &messages.hud_full,
+                &[&self.settings.key_binding.key_str(Action::HudToggle)],
+            ),
+            Style::default().add_modifier(Modifier::DIM),
+        ));
+        frame.render_widget(Paragraph::new(Spans::from(spans)), chunks[0]);
+        frame.render_widget(Paragraph::new(self.message_spans()), chunks[1]);
+    }
+
     fn render_guidance_block<B: Backend>(
         &self,
         frame: &mut Frame<B>,
@@ -512,34 +3111,344 @@ impl<D: BoardDisplay> System<D> {
         );
     }
 
+    /// Status telling the turn and the player to move, the browsed turn, or the result in words.
+    fn status_span(&self, play: Play) -> Span<'static> {
+        let player_name = |player| self.board_display.player_name(player);
+        let player_style = |player| {
+            self.settings
+                .color_config
+                .player_style(player)
+                .add_modifier(Modifier::BOLD)
+        };
+        let messages = &self.settings.messages;
+        let turn = self.history.current_turn() + 1;
+        let (text, style) = match play {
+            _ if self.current_status == Status::PuzzleResult => (
+                fill(
+                    &messages.status_puzzle,
+                    &[&player_name(self.current_player)],
+                ),
+                player_style(self.current_player),
+            ),
+            Play::Turn => (
+                fill(
+                    &messages.status_turn,
+                    &[&turn, &player_name(self.current_player)],
+                ),
+                player_style(self.current_player),
+            ),
+            Play::Skipped => (
+                fill(
+                    &messages.status_pass,
+                    &[&turn, &player_name(self.current_player)],
+                ),
+                player_style(self.current_player),
+            ),
+            Play::History => (
+                fill(
+                    &messages.status_history,
+                    &[&self.history.current_turn(), &self.history.branch().len()],
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Play::Finished => {
+                let tiebreak = self.settings.tiebreak;
+                match self.board.winner(tiebreak, &self.skips, self.first_player) {
+                    Some(winner) => (
+                        fill(&messages.status_won, &[&player_name(winner)]),
+                        player_style(winner),
+                    ),
+                    None => (
+                        messages.status_drawn.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                }
+            }
+        };
+        Span::styled(format!(" {}", text), style)
+    }
+
     fn render_player_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect, play: Play) {
+        frame.render_widget(
+            Paragraph::new(Spans::from(self.player_spans(play)))
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(self.settings.messages.player_title.as_str()),
+                ),
+            rect,
+        );
+    }
+
+    /// Names of players with their controllers and mobilities, followed by the leader.
+    fn player_spans(&self, play: Play) -> Vec<Span<'static>> {
         let mut player_names: Vec<Span> = Vec::new();
         let mut players_iter = PLAYERS.iter().peekable();
         while let Some(player) = players_iter.next() {
-            if player != &self.current_player && play != Play::Finished {
-                player_names.push(Span::styled(
-                    if players_iter.peek().is_none() {
-                        self.board_display.player_name(*player).to_owned()
-                    } else {
-                        format!("{} ", self.board_display.player_name(*player))
-                    },
-                    Style::default().add_modifier(Modifier::DIM),
-                ))
+            let style = if player != &self.current_player && play != Play::Finished {
+                Style::default().add_modifier(Modifier::DIM)
             } else {
-                player_names.push(Span::styled(
-                    if players_iter.peek().is_none() {
-                        self.board_display.player_name(*player).to_owned()
-                    } else {
-                        format!("{} ", self.board_display.player_name(*player))
-                    },
-                    Style::default().fg(self.color_config.player(*player)),
-                ))
+                self.settings.color_config.player_style(*player)
+            };
+            player_names.push(Span::styled(
+                self.board_display.player_name(*player).to_owned(),
+                style,
+            ));
+            if let Some(mark) = self.controller_mark(*player) {
+                let mark_style = if mark == OFFLINE_MARK {
+                    style.fg(self.settings.color_config.error_color())
+                } else {
+                    style.add_modifier(Modifier::DIM)
+                };
+                player_names.push(Span::styled(mark, mark_style));
+            }
+            if let Some(mark) = self.first_mark(*player) {
+                player_names.push(Span::styled(mark, style.add_modifier(Modifier::BOLD)));
+            }
+            let mobility = self.mobility(*player);
+            player_names.push(Span::styled(
+                format!("({})", mobility),
+                if mobility == 0 {
+                    style.fg(self.settings.color_config.error_color())
+                } else {
+                    style
+                },
+            ));
+            if players_iter.peek().is_some() {
+                player_names.push(Span::raw(" "));
+            }
+        }
+        let (leader, margin) = self.board.score_margin();
+        player_names.push(Span::raw("  "));
+        player_names.push(if margin == 0 {
+            Span::raw(self.leader_text())
+        } else {
+            Span::styled(
+                self.leader_text(),
+                self.settings.color_config.player_style(leader),
+            )
+        });
+        player_names
+    }
+
+    /// Capture lines of the position under the cursor if the current player can select it,
+    /// or otherwise of the last move, which are not drawn in the history or if they are hidden.
+    fn capture_lines(&self, play: Play) -> Option<CaptureLines> {
+        if !self.capture_line_visibility || play == Play::History {
+            return None;
+        }
+        if play == Play::Turn {
+            if let Some(positions) =
+                self.availables[self.current_player].get(&self.current_position)
+            {
+                return Some(CaptureLines {
+                    player: self.current_player,
+                    lines: self.board.capture_lines(self.current_position, positions),
+                });
+            }
+        }
+        self.last_capture.clone()
+    }
+
+    /// Stones changed by the move leading to the turn browsed in the history, which are shown while the diff is toggled on.
+    fn move_diff(&self, play: Play) -> Option<MoveDiff> {
+        if !self.diff_visibility || play != Play::History {
+            return None;
+        }
+        let (before, placed) = self.history.previous_board()?;
+        Some(MoveDiff::new(before, self.history.board(), placed))
+    }
+
+    /// Stones left visible to the player to move while the fog is toggled on,
+    /// which are its own stones and those it can flip by a move.
+    fn fog(&self, play: Play) -> Option<Fog> {
+        if !self.fog_visibility || !matches!(play, Play::Turn | Play::Skipped) {
+            return None;
+        }
+        Some(Fog {
+            player: self.current_player,
+            capturable: self.availables[self.current_player]
+                .values()
+                .flatten()
+                .copied()
+                .collect(),
+        })
+    }
+
+    /// Directions captured by the position under the cursor, which are drawn only while the human
+    /// on this terminal is selecting a position and the position can be selected.
+    fn capture_arrows(&self) -> Option<CaptureArrows> {
+        if !self.settings.capture_arrows
+            || self.current_status != Status::Play(Play::Turn)
+            || !self.is_local_turn()
+        {
+            return None;
+        }
+        let positions = self.availables[self.current_player].get(&self.current_position)?;
+        Some(CaptureArrows {
+            player: self.current_player,
+            position: self.current_position,
+            directions: self
+                .board
+                .capture_directions(self.current_position, positions),
+        })
+    }
+
+    /// Player having the most stones and the lead, shown in the player block.
+    fn leader_text(&self) -> String {
+        match self.board.score_margin() {
+            (_, 0) => self.settings.messages.tied.clone(),
+            (leader, margin) => fill(
+                &self.settings.messages.leader,
+                &[&self.board_display.player_name(leader), &margin],
+            ),
+        }
+    }
+
+    /// Annotation of the controller of `player` in the player block, which is the connection status
+    /// for seats played on other terminals.
+    fn controller_mark(&self, player: Player) -> Option<&'static str> {
+        match self.controller(player) {
+            Controller::Human => None,
+            Controller::Ai(_) => Some(AI_MARK),
+            Controller::Remote
+                if self
+                    .network
+                    .as_ref()
+                    .is_some_and(|network| network.is_offline(player)) =>
+            {
+                Some(OFFLINE_MARK)
             }
+            Controller::Remote => Some(ONLINE_MARK),
         }
+    }
+
+    fn first_mark(&self, player: Player) -> Option<&'static str> {
+        (player == self.first_player && self.puzzle.is_none() && self.history.current_turn() == 0)
+            .then_some(FIRST_MARK)
+    }
+
+    /// Number of positions `player` can select.
+    fn mobility(&self, player: Player) -> usize {
+        self.availables[player].len()
+    }
+
+    /// Whether the standings of the match are shown beside the board, which gives way to the debug panel.
+    /// Whether the right half of the board row is taken by the debug panel, the standings, or the flips.
+    fn has_side_panel(&self) -> bool {
+        self.debug_panel != DebugPanel::Off || self.shows_standings() || self.flip_panel_visibility
+    }
+
+    fn shows_standings(&self) -> bool {
+        self.match_state.is_some()
+            && self.debug_panel == DebugPanel::Off
+            && self.current_status == Status::Play(Play::Finished)
+    }
+
+    fn render_standings_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect) {
+        let Some(match_state) = &self.match_state else {
+            return;
+        };
         frame.render_widget(
-            Paragraph::new(Spans::from(player_names))
-                .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).title("Player")),
+            Paragraph::new(match_state.standings(&self.player_names())).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.settings.messages.standings_title.as_str()),
+            ),
+            rect,
+        );
+    }
+
+    /// Stones flipped by the position under the cursor if the player to move can select it,
+    /// with their numbers in each direction and for each owner.
+    fn render_flips_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect, play: Play) {
+        let messages = &self.settings.messages;
+        let position = self.current_position;
+        let mut lines = vec![Spans::from(format!(" {:?}", position))];
+        match self.availables[self.current_player]
+            .get(&position)
+            .filter(|_| play == Play::Turn)
+        {
+            Some(positions) => {
+                let mut flipped = positions
+                    .iter()
+                    .copied()
+                    .filter(|flipped| *flipped != position)
+                    .map(Position::from)
+                    .collect::<Vec<_>>();
+                flipped.sort();
+                lines.push(Spans::from(format!(
+                    " {}",
+                    fill(&messages.flips_count, &[&flipped.len()])
+                )));
+                for Position(flipped) in &flipped {
+                    lines.push(Spans::from(format!("   {:?}", flipped)));
+                }
+                lines.push(Spans::from(format!(" {}", messages.flips_by_direction)));
+                for direction in DIRECTIONS {
+                    let count = self
+                        .board
+                        .ray(position, *direction)
+                        .take_while(|ray_position| positions.contains(ray_position))
+                        .count();
+                    if count > 0 {
+                        lines.push(Spans::from(format!("   {:?}: {}", direction, count)));
+                    }
+                }
+                lines.push(Spans::from(format!(" {}", messages.flips_by_player)));
+                for player in PLAYERS {
+                    let count = flipped
+                        .iter()
+                        .filter(|Position(flipped)| self.board.player(*flipped) == Some(*player))
+                        .count();
+                    if count > 0 {
+                        lines.push(Spans::from(vec![
+                            Span::raw("   "),
+                            Span::styled(
+                                self.board_display.player_name(*player),
+                                self.settings.color_config.player_style(*player),
+                            ),
+                            Span::raw(format!(": {}", count)),
+                        ]));
+                    }
+                }
+            }
+            None => lines.push(Spans::from(format!(" {}", messages.not_legal_move))),
+        }
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(fill(
+                    &messages.flips_title,
+                    &[&self.board_display.player_name(self.current_player)],
+                )))
+                .wrap(Wrap { trim: false }),
+            rect,
+        );
+    }
+
+    /// Marks of stones in the colors of players followed by their names.
+    fn render_legend_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect) {
+        let mut spans = Vec::new();
+        for player in PLAYERS.iter() {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                self.board_display.player_mark(*player),
+                self.settings.color_config.player_style(*player),
+            ));
+            spans.push(Span::raw(format!(
+                " {}",
+                self.board_display.player_name(*player)
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(Spans::from(spans)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.settings.messages.legend_title.as_str()),
+            ),
             rect,
         );
     }
@@ -551,24 +3460,76 @@ impl<D: BoardDisplay> System<D> {
                 self.current_position.0, self.current_position.1,
             ))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("Position")),
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.settings.messages.position_title.as_str()),
+            ),
             rect,
         );
     }
 
     fn render_message_block<B: Backend>(&self, frame: &mut Frame<B>, rect: Rect) {
         frame.render_widget(
-            Paragraph::new(Span::styled(
-                &self.message,
-                Style::default().fg(self.message_color),
-            ))
-            .block(Block::default().borders(Borders::ALL).title("Message")),
+            Paragraph::new(self.message_spans()).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.settings.messages.message_title.as_str()),
+            ),
             rect,
         );
     }
 
-    #[cfg(debug_assertions)]
-    #[allow(dead_code)]
+    /// Message followed by the player being computed by the computer with the spinner.
+    fn message_spans(&self) -> Spans<'_> {
+        let style = if self.skip_banner {
+            Style::default()
+                .fg(Color::Black)
+                .bg(self.message_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.message_color)
+        };
+        let mut spans = vec![Span::styled(self.message.as_str(), style)];
+        if !self.message_warning.is_empty() {
+            spans.push(Span::styled(
+                self.message_warning.as_str(),
+                Style::default().fg(self.settings.color_config.warning_color()),
+            ));
+        }
+        if let Some(ai_worker) = self.ai_worker.as_ref().filter(|_| !self.ai_paused) {
+            let mut thinking = format!(
+                " {}",
+                fill(
+                    &self.settings.messages.thinking,
+                    &[
+                        &self.board_display.player_name(ai_worker.player()),
+                        &SPINNER[self.tick_count % SPINNER.len()],
+                    ],
+                )
+            );
+            if self.settings.search_config.time_budget.is_some() && ai_worker.depth_reached() > 0 {
+                write!(
+                    thinking,
+                    " {}",
+                    fill(
+                        &self.settings.messages.thinking_depth,
+                        &[&ai_worker.depth_reached()]
+                    )
+                )
+                .unwrap();
+            }
+            spans.push(Span::styled(
+                thinking,
+                self.settings
+                    .color_config
+                    .player_style(ai_worker.player())
+                    .add_modifier(Modifier::DIM),
+            ));
+        }
+        Spans::from(spans)
+    }
+
     fn write_debug_info_of_history(&mut self) {
         self.debug_information.clear();
         writeln!(
@@ -577,13 +3538,66 @@ impl<D: BoardDisplay> System<D> {
             self.history.current_turn(),
         )
         .unwrap();
-        for player_putting in self.history.record().player_positions() {
-            writeln!(self.debug_information, " {:?}", player_putting).unwrap();
+        if self.history.branches().len() > 1 {
+            for branch in self.history.branches() {
+                write!(
+                    self.debug_information,
+                    " {} {}: {} moves",
+                    if branch.id() == self.history.branch().id() {
+                        '*'
+                    } else {
+                        ' '
+                    },
+                    branch.name(),
+                    branch.len()
+                )
+                .unwrap();
+                if branch.id() != 0 {
+                    write!(
+                        self.debug_information,
+                        ", diverging at turn {}",
+                        branch.fork_turn() + 1
+                    )
+                    .unwrap();
+                }
+                writeln!(self.debug_information).unwrap();
+            }
+            writeln!(self.debug_information).unwrap();
+        }
+        let record = self.history.record();
+        for (i_move, (player, (x, y))) in record.player_positions().iter().enumerate() {
+            write!(
+                self.debug_information,
+                " {}: ({}, {}) {}",
+                player,
+                x,
+                y,
+                format_duration(record.think_time(i_move))
+            )
+            .unwrap();
+            if let Some(annotation) = self
+                .annotations
+                .as_ref()
+                .and_then(|annotations| annotations.get(i_move))
+            {
+                write!(
+                    self.debug_information,
+                    " {}{}",
+                    annotation.quality().label(),
+                    annotation.mark()
+                )
+                .unwrap();
+                if annotation.quality() != Quality::Best {
+                    write!(self.debug_information, " -{}", annotation.delta()).unwrap();
+                    if let Some((x, y)) = annotation.best() {
+                        write!(self.debug_information, ", best ({}, {})", x, y).unwrap();
+                    }
+                }
+            }
+            writeln!(self.debug_information).unwrap();
         }
     }
 
-    #[cfg(debug_assertions)]
-    #[allow(dead_code)]
     fn write_debug_info_of_available_position(&mut self, play: Play) {
         self.debug_information.clear();
         writeln!(self.debug_information, " Play: {:?}\n", play).unwrap();
@@ -596,19 +3610,24 @@ impl<D: BoardDisplay> System<D> {
                 self.board_display.player_name(*player)
             )
             .unwrap();
-            let mut keys = self
-                .availables
-                .get(player)
-                .unwrap()
-                .keys()
-                .collect::<Vec<_>>();
-            keys.sort();
-            for key in keys {
+            for Position(key) in sorted_moves(&self.availables, *player) {
+                let Some(flips) = self.availables[*player].get(&key) else {
+                    continue;
+                };
+                let mut flips = flips
+                    .iter()
+                    .copied()
+                    .map(Position::from)
+                    .collect::<Vec<_>>();
+                flips.sort();
                 writeln!(
                     &mut self.debug_information,
                     " {:?}: {:?}",
                     key,
-                    self.availables.get(player).unwrap().get(key).unwrap()
+                    flips
+                        .into_iter()
+                        .map(|Position(flip)| flip)
+                        .collect::<Vec<_>>()
                 )
                 .unwrap();
             }
@@ -616,3 +3635,260 @@ impl<D: BoardDisplay> System<D> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::board_display::paragraph_board::{FrameStyleKind, ParagraphBoard};
+    use crate::app::key_binding::key;
+    use crate::app::SeatConfig;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// System on a board of `range` shown by the default ASCII board.
+    fn system(range: usize, settings: Settings) -> System<ParagraphBoard> {
+        system_with_observers(range, settings, Vec::new())
+    }
+
+    /// System of `system` which tells events to `observers` from its construction.
+    fn system_with_observers(
+        range: usize,
+        settings: Settings,
+        observers: Vec<Box<dyn Observer>>,
+    ) -> System<ParagraphBoard> {
+        let paragraph_board = ParagraphBoard::try_new(
+            3,
+            "Cyan,Magenta,Yellow",
+            "C,M,Y",
+            false,
+            FrameStyleKind::Ascii.into(),
+            true,
+            false,
+            false,
+        )
+        .unwrap();
+        System::try_new(
+            Board::try_new(range).unwrap(),
+            paragraph_board,
+            settings,
+            observers,
+        )
+        .unwrap()
+    }
+
+    /// Stones, availables, and the player to move, which are not changed by relabeling seats.
+    fn position(system: &System<ParagraphBoard>) -> (String, Count, String, Player) {
+        (
+            system.board.to_string(),
+            system.board.count().clone(),
+            system.board.availables_snapshot(),
+            system.current_player,
+        )
+    }
+
+    #[test]
+    fn swap_relabels_seats_without_changing_the_position() {
+        let settings = || Settings {
+            variant: Variant::Swap,
+            seat_config: SeatConfig::with_local_player(Player::One),
+            ..Settings::default()
+        };
+        let mut swapped = system(8, settings());
+        let mut kept = system(8, settings());
+        let first_move = Move::Put(swapped.board.selectable_positions(Player::Zero)[0]);
+        for system in [&mut swapped, &mut kept] {
+            system.apply_move(first_move).unwrap();
+        }
+        assert!(swapped.can_swap());
+        assert_eq!(swapped.controller(Player::Zero), Controller::Remote);
+        assert_eq!(swapped.controller(Player::One), Controller::Human);
+
+        let before = position(&swapped);
+        swapped.transition(key::SWAP);
+        assert_eq!(swapped.current_status, Status::AskSwap);
+        swapped.transition(Key::Char('Y'));
+        assert!(swapped.history.record().swapped());
+        assert!(!swapped.can_swap());
+        assert_eq!(position(&swapped), before);
+        assert_eq!(swapped.controller(Player::Zero), Controller::Human);
+        assert_eq!(swapped.controller(Player::One), Controller::Remote);
+        assert_eq!(swapped.controller(Player::Two), Controller::Remote);
+
+        // The same moves lead to the same positions with and without the swap.
+        for _ in 0..6 {
+            let player_move = match swapped.board.selectable_positions(swapped.current_player)[..] {
+                [] => Move::Pass,
+                [position, ..] => Move::Put(position),
+            };
+            for system in [&mut swapped, &mut kept] {
+                system.apply_move(player_move).unwrap();
+            }
+            assert_eq!(position(&swapped), position(&kept));
+        }
+    }
+
+    #[test]
+    fn coach_warns_apart_from_the_flipped_stones() {
+        let settings = Settings {
+            coach_margin: Some(1),
+            ..Settings::default()
+        };
+        let mut system = system(14, settings);
+        let player = system.current_player;
+        let flips = |position: &(usize, usize)| system.board.flips(player, *position).len();
+        let positions = system.board.selectable_positions(player);
+        let worst = *positions
+            .iter()
+            .min_by_key(|position| flips(position))
+            .unwrap();
+        let best = positions.iter().map(flips).max().unwrap();
+        let flipped = flips(&worst);
+        assert!(best > flipped);
+        system.set_cursor(worst).unwrap();
+        system.transition(key::SELECT);
+        let player_color = system.settings.color_config.player(player);
+        let warning_color = system.settings.color_config.warning_color();
+        assert_eq!(system.message_color, player_color);
+        assert!(
+            system.message.contains(&format!("+{}", flipped)),
+            "{}",
+            system.message
+        );
+        assert!(
+            system.message_warning.contains("would have flipped"),
+            "{}",
+            system.message_warning
+        );
+        let spans = system.message_spans().0;
+        assert_eq!(spans[0].style.fg, Some(player_color));
+        assert_eq!(spans[1].style.fg, Some(warning_color));
+        let log = system.message_log.iter_newest_first().collect::<Vec<_>>();
+        assert_eq!(log[0].color(), warning_color);
+        assert_eq!(log[1].color(), player_color);
+    }
+
+    /// Observer keeping every event it is told.
+    #[test]
+    fn skipped_players_are_written_by_their_events() {
+        let settings = Settings {
+            auto_skip: true,
+            ..Settings::default()
+        };
+        let mut system = system(5, settings);
+        for position in [(0, 4), (0, 0)] {
+            system.set_cursor(position).unwrap();
+            system.transition(key::SELECT);
+        }
+        assert_eq!(system.current_player, Player::Zero);
+        assert!(system.skip_banner);
+        assert_eq!(
+            system.message,
+            " Player-Yellow skipped, no position can be selected."
+        );
+        let texts = system
+            .message_log
+            .iter_newest_first()
+            .map(|entry| entry.text().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts[..2],
+            [
+                "Player-Yellow skipped, no position can be selected.",
+                "Player-Magenta: +1"
+            ]
+        );
+    }
+
+    struct Recorder(Rc<RefCell<Vec<GameEvent>>>);
+
+    impl Observer for Recorder {
+        fn notify(&mut self, event: &GameEvent) {
+            self.0.borrow_mut().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn observer_records_the_events_of_a_scripted_game() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut system = system_with_observers(
+            5,
+            Settings::default(),
+            vec![Box::new(Recorder(events.clone()))],
+        );
+        // Seat 3 cannot select any position after the second move, which is acknowledged by the select key.
+        for position in [Some((0, 4)), Some((0, 0)), None, Some((4, 4))] {
+            if let Some(position) = position {
+                system.set_cursor(position).unwrap();
+            }
+            system.transition(key::SELECT);
+        }
+        assert_eq!(system.current_status(), Status::Play(Play::Finished));
+        system.transition(key::UNDO);
+        assert_eq!(
+            *events.borrow(),
+            [
+                GameEvent::Init {
+                    range: 5,
+                    counts: vec![4, 4, 4],
+                },
+                GameEvent::Move {
+                    turn: 1,
+                    player: Player::Zero,
+                    position: (0, 4),
+                    flipped: vec![(1, 4)],
+                    counts: vec![6, 4, 3],
+                    next_player: Player::One,
+                },
+                GameEvent::Move {
+                    turn: 2,
+                    player: Player::One,
+                    position: (0, 0),
+                    flipped: vec![(1, 1)],
+                    counts: vec![6, 6, 2],
+                    next_player: Player::Two,
+                },
+                GameEvent::Skip {
+                    turn: 2,
+                    player: Player::Two,
+                    counts: vec![6, 6, 2],
+                },
+                GameEvent::Pass {
+                    turn: 2,
+                    player: Player::Two,
+                    counts: vec![6, 6, 2],
+                    next_player: Player::Zero,
+                },
+                GameEvent::Move {
+                    turn: 3,
+                    player: Player::Zero,
+                    position: (4, 4),
+                    flipped: vec![(3, 4)],
+                    counts: vec![8, 5, 2],
+                    next_player: Player::Zero,
+                },
+                GameEvent::Finish {
+                    turn: 3,
+                    winner: Some(Player::Zero),
+                    counts: vec![8, 5, 2],
+                },
+                GameEvent::History {
+                    turn: 2,
+                    counts: vec![6, 6, 2],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn view_keys_are_handled_in_every_play() {
+        for play in [Play::Turn, Play::Skipped, Play::Finished, Play::History] {
+            let mut system = system(5, Settings::default());
+            system.update_status(Status::Play(play));
+            system.transition(key::ROTATE);
+            system.transition(key::FOG_TOGGLE);
+            assert!(system.board_display.is_upside_down(), "{:?}", play);
+            assert!(system.fog_visibility, "{:?}", play);
+            assert_eq!(system.current_status, Status::Play(play));
+        }
+    }
+}
@@ -6,37 +6,108 @@
 // use crate::app::{Status, System};
 use crate::app::board_display::BoardDisplay;
 use crate::app::system::{Status, System};
+use anyhow::anyhow;
 use std::io;
 use std::io::Stdout;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use termion::event::Key;
 use termion::input::TermRead;
 use termion::raw::{IntoRawMode, RawTerminal};
 use termion::screen::{AlternateScreen, IntoAlternateScreen};
 use tui::backend::{Backend, TermionBackend};
 use tui::terminal::Terminal;
 
+/// Interval of ticks, which animate the screen and receive moves of the computer.
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
 #[derive(Debug)]
 pub struct Tui<B: Backend> {
     terminal: Terminal<B>,
 }
 
-impl Tui<TermionBackend<AlternateScreen<RawTerminal<Stdout>>>> {
-    pub fn try_new() -> anyhow::Result<Self> {
-        let stdout = io::stdout().into_raw_mode()?.into_alternate_screen()?;
-        let backend = TermionBackend::new(stdout);
+impl<B: Backend> Tui<B> {
+    /// Terminal drawn on `backend`, e.g., `TestBackend` of tui to inspect the rendered buffer.
+    pub fn with_backend(backend: B) -> anyhow::Result<Self> {
         let mut terminal = Terminal::new(backend)?;
         terminal.hide_cursor()?;
         Ok(Self { terminal })
     }
 
-    pub fn run<D: BoardDisplay>(&mut self, app: &mut System<D>) -> anyhow::Result<()> {
+    pub fn terminal(&self) -> &Terminal<B> {
+        &self.terminal
+    }
+
+    /// Renders `app` once.
+    pub fn draw<D: BoardDisplay>(&mut self, app: &mut System<D>) -> anyhow::Result<()> {
         self.terminal.draw(|frame| app.ui(frame))?;
-        while let Some(Ok(key)) = io::stdin().keys().next() {
-            app.transition(key);
+        Ok(())
+    }
+}
+
+impl Tui<TermionBackend<AlternateScreen<RawTerminal<Stdout>>>> {
+    pub fn try_new() -> anyhow::Result<Self> {
+        let stdout = io::stdout().into_raw_mode()?.into_alternate_screen()?;
+        Self::with_backend(TermionBackend::new(stdout))
+    }
+
+    /// Runs the loop of keys and ticks, where a panic is returned as an error instead of unwinding further.
+    /// The message of the panic is kept instead of being written over the screen,
+    /// so that the caller can print it after the terminal is restored by dropping `self`.
+    pub fn run<D: BoardDisplay>(&mut self, app: &mut System<D>) -> anyhow::Result<()> {
+        let panic_message = Arc::new(Mutex::new(None));
+        let hook_message = Arc::clone(&panic_message);
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if let Ok(mut message) = hook_message.lock() {
+                message.get_or_insert_with(|| info.to_string());
+            }
+        }));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.run_loop(app)));
+        panic::set_hook(default_hook);
+        result.unwrap_or_else(|_| {
+            let message = panic_message
+                .lock()
+                .ok()
+                .and_then(|mut message| message.take())
+                .unwrap_or_else(|| "the game panicked".to_owned());
+            Err(anyhow!(message))
+        })
+    }
+
+    /// Keys are read on another thread so that ticks are not blocked by waiting for keys.
+    /// They are read from the terminal rather than the standard input, which may be piped moves.
+    /// The terminal is restored when `self` is dropped, however the loop ends.
+    /// The bell requested by `app` is written between the keys and the ticks.
+    fn run_loop<D: BoardDisplay>(&mut self, app: &mut System<D>) -> anyhow::Result<()> {
+        let tty = termion::get_tty()?;
+        let (key_sender, key_receiver) = mpsc::channel::<Key>();
+        thread::spawn(move || {
+            for key in tty.keys().map_while(Result::ok) {
+                if key_sender.send(key).is_err() {
+                    break;
+                }
+            }
+        });
+        self.draw(app)?;
+        loop {
+            match key_receiver.recv_timeout(TICK_INTERVAL) {
+                Ok(key) => app.transition(key),
+                Err(RecvTimeoutError::Timeout) => app.tick(),
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
             if let Status::Quit = app.current_status() {
                 break;
-            } else {
-                self.terminal.draw(|frame| app.ui(frame))?;
             }
+            if app.take_bell() {
+                let backend = self.terminal.backend_mut();
+                io::Write::write_all(backend, b"\x07")?;
+                io::Write::flush(backend)?;
+            }
+            self.draw(app)?;
         }
         Ok(())
     }
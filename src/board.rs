@@ -14,21 +14,56 @@
 //! oooo
 //! ```
 
+pub mod archive;
 pub mod availables;
+pub mod capture_rules;
+pub mod code;
 pub mod count;
+pub mod direction;
+pub mod fen;
+pub mod first_player;
 pub mod history;
+pub mod move_list;
+pub mod move_undo;
+pub mod per_player;
 pub mod player;
+pub mod position;
+pub mod scoring;
+pub mod tiebreak;
+pub mod variant;
+pub mod zobrist;
 
-pub use availables::Availables;
+pub use archive::{parse_archive, Archive};
+pub use availables::{sorted_moves, Availables};
+pub use capture_rules::CaptureRules;
 pub use count::Count;
-pub use history::History;
+pub use direction::{Direction, DIRECTIONS};
+pub use fen::{parse_fen, to_fen};
+pub use first_player::FirstPlayer;
+pub use history::{History, MoveStats};
+pub use move_list::{parse_move_list, Move};
+pub use move_undo::MoveUndo;
+pub use per_player::PerPlayer;
 pub use player::{Player, PLAYERS};
+pub use position::Position;
+pub use scoring::Scoring;
+pub use tiebreak::Tiebreak;
+pub use variant::{Variant, SWAP_TURN};
+pub use zobrist::Zobrist;
 
 use crate::error::TriversiError;
-use getset::{CopyGetters, Getters, MutGetters};
+use crate::util::validate_position;
+use getset::{CopyGetters, Getters, MutGetters, Setters};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::iter;
+use std::sync::Arc;
 
-#[derive(Clone, Debug, CopyGetters, Getters, MutGetters)]
+/// Smallest range of a board.
+pub const MIN_RANGE: usize = 5;
+
+#[derive(Clone, Debug, CopyGetters, Getters, MutGetters, Setters)]
 pub struct Board {
     #[getset(get = "pub", get_mut = "pub")]
     board: Vec<Vec<Option<Player>>>,
@@ -36,28 +71,63 @@ pub struct Board {
     range: usize,
     #[getset(get = "pub")]
     count: Count,
+    #[getset(get = "pub", set = "pub")]
+    capture_rules: CaptureRules,
+    #[getset(get_copy = "pub", set = "pub")]
+    scoring: Scoring,
+    /// Numbers of extra starting stones of players indexed by `Player::index`.
+    #[getset(get_copy = "pub")]
+    handicap: [usize; 3],
+    zobrist: Arc<Zobrist>,
+    /// Zobrist hash of the stones, which is updated whenever a stone is set.
+    #[getset(get_copy = "pub")]
+    hash: u64,
 }
 
 impl Board {
     pub fn try_new(range: usize) -> Result<Self, TriversiError> {
-        if range < 5 {
-            return Err(TriversiError::InvalidBoardRange(range));
-        }
-        match range % 3 {
-            0 | 2 => (),
-            _ => return Err(TriversiError::InvalidBoardRange(range)),
-        };
+        Self::validate_range(range)?;
         let mut logic_board = Self {
             board: (1..=range)
                 .map(|i_row| vec![None; i_row])
                 .collect::<Vec<_>>(),
             range,
             count: Count::default(),
+            capture_rules: CaptureRules::default(),
+            scoring: Scoring::default(),
+            handicap: [0; 3],
+            zobrist: Arc::new(Zobrist::new(range)),
+            hash: 0,
         };
         logic_board.init();
         Ok(logic_board)
     }
 
+    /// Checks that `range` is at least `MIN_RANGE` and is 0 or 2 modulo 3.
+    pub fn validate_range(range: usize) -> Result<(), TriversiError> {
+        if range < MIN_RANGE {
+            return Err(TriversiError::InvalidBoardRange(range));
+        }
+        match range % 3 {
+            0 | 2 => Ok(()),
+            _ => Err(TriversiError::InvalidBoardRange(range)),
+        }
+    }
+
+    /// Valid range nearest to `range`, which is smaller if two are as near.
+    pub fn nearest_range(range: usize) -> usize {
+        match range {
+            _ if range < MIN_RANGE => MIN_RANGE,
+            _ if range % 3 == 1 => range - 1,
+            _ => range,
+        }
+    }
+
+    /// Number of positions of the board.
+    pub fn num_cells(&self) -> usize {
+        self.range * (self.range + 1) / 2
+    }
+
     pub fn init(&mut self) {
         for row in self.board.iter_mut() {
             for player in row.iter_mut() {
@@ -65,49 +135,188 @@ impl Board {
             }
         }
         self.count.reset();
-        match self.range % 3 {
-            0 => {
+        self.hash = 0;
+        for (position, player) in self.opening_stones() {
+            self.set_player(position, Some(player));
+        }
+        for player in PLAYERS {
+            for position in self.handicap_positions(*player) {
+                self.set_player(position, Some(*player));
+            }
+        }
+    }
+
+    /// Starting stones of the players without the extra stones of the handicap.
+    #[allow(clippy::manual_div_ceil)]
+    fn opening_stones(&self) -> Vec<((usize, usize), Player)> {
+        let range = self.range;
+        match range % 3 {
+            0 => vec![
                 // Player 0
-                let player = Some(Player::Zero);
-                self.set_player((self.range / 3, 2 * self.range / 3), player);
-                self.set_player((self.range / 3 + 1, 2 * self.range / 3 - 1), player);
-                self.set_player((self.range / 3 - 1, 2 * self.range / 3 - 2), player);
-                self.set_player((self.range / 3 - 2, 2 * self.range / 3 - 1), player);
+                ((range / 3, 2 * range / 3), Player::Zero),
+                ((range / 3 + 1, 2 * range / 3 - 1), Player::Zero),
+                ((range / 3 - 1, 2 * range / 3 - 2), Player::Zero),
+                ((range / 3 - 2, 2 * range / 3 - 1), Player::Zero),
                 // Player 1
-                let player = Some(Player::One);
-                self.set_player((self.range / 3, 2 * self.range / 3 - 1), player);
-                self.set_player((self.range / 3 - 2, 2 * self.range / 3 - 2), player);
-                self.set_player((self.range / 3 - 1, 2 * self.range / 3), player);
-                self.set_player((self.range / 3 + 1, 2 * self.range / 3 + 1), player);
+                ((range / 3, 2 * range / 3 - 1), Player::One),
+                ((range / 3 - 2, 2 * range / 3 - 2), Player::One),
+                ((range / 3 - 1, 2 * range / 3), Player::One),
+                ((range / 3 + 1, 2 * range / 3 + 1), Player::One),
                 // Player 2
-                let player = Some(Player::Two);
-                self.set_player((self.range / 3 - 1, 2 * self.range / 3 - 1), player);
-                self.set_player((self.range / 3, 2 * self.range / 3 + 1), player);
-                self.set_player((self.range / 3 + 1, 2 * self.range / 3), player);
-                self.set_player((self.range / 3, 2 * self.range / 3 - 2), player);
-            }
-            2 => {
+                ((range / 3 - 1, 2 * range / 3 - 1), Player::Two),
+                ((range / 3, 2 * range / 3 + 1), Player::Two),
+                ((range / 3 + 1, 2 * range / 3), Player::Two),
+                ((range / 3, 2 * range / 3 - 2), Player::Two),
+            ],
+            2 => vec![
                 // Player 0
-                let player = Some(Player::Zero);
-                self.set_player(((self.range - 2) / 3, (2 * self.range - 4) / 3), player);
-                self.set_player(((self.range - 5) / 3, (2 * self.range - 1) / 3), player);
-                self.set_player(((self.range + 1) / 3, (2 * self.range + 2) / 3), player);
-                self.set_player(((self.range + 4) / 3, (2 * self.range - 1) / 3), player);
+                (((range - 2) / 3, (2 * range - 4) / 3), Player::Zero),
+                (((range - 5) / 3, (2 * range - 1) / 3), Player::Zero),
+                (((range + 1) / 3, (2 * range + 2) / 3), Player::Zero),
+                (((range + 4) / 3, (2 * range - 1) / 3), Player::Zero),
                 // Player 1
-                let player = Some(Player::One);
-                self.set_player(((self.range - 2) / 3, (2 * self.range - 1) / 3), player);
-                self.set_player(((self.range + 4) / 3, (2 * self.range + 2) / 3), player);
-                self.set_player(((self.range + 1) / 3, (2 * self.range - 4) / 3), player);
-                self.set_player(((self.range - 5) / 3, (2 * self.range - 7) / 3), player);
+                (((range - 2) / 3, (2 * range - 1) / 3), Player::One),
+                (((range + 4) / 3, (2 * range + 2) / 3), Player::One),
+                (((range + 1) / 3, (2 * range - 4) / 3), Player::One),
+                (((range - 5) / 3, (2 * range - 7) / 3), Player::One),
                 // Player 2
-                let player = Some(Player::Two);
-                self.set_player(((self.range + 1) / 3, (2 * self.range - 1) / 3), player);
-                self.set_player(((self.range - 2) / 3, (2 * self.range - 7) / 3), player);
-                self.set_player(((self.range - 5) / 3, (2 * self.range - 4) / 3), player);
-                self.set_player(((self.range - 2) / 3, (2 * self.range + 2) / 3), player);
+                (((range + 1) / 3, (2 * range - 1) / 3), Player::Two),
+                (((range - 2) / 3, (2 * range - 7) / 3), Player::Two),
+                (((range - 5) / 3, (2 * range - 4) / 3), Player::Two),
+                (((range - 2) / 3, (2 * range + 2) / 3), Player::Two),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Checks that the starting stones without the handicap are on the board, do not coincide,
+    /// are as many for every player, and are turned into those of the next player
+    /// by rotating the board by 120 degrees from the corner of `Player::Zero` to that of `Player::One`.
+    pub fn validate_opening(&self) -> Result<(), TriversiError> {
+        let invalid = |reason: String| Err(TriversiError::InvalidOpening(self.range, reason));
+        let stones = self.opening_stones();
+        let mut owners = HashMap::new();
+        for ((x, y), player) in &stones {
+            if *y >= self.range || x > y {
+                return invalid(format!("({}, {}) is out of the board", x, y));
+            }
+            if let Some(other) = owners.insert((*x, *y), *player) {
+                return invalid(format!(
+                    "stones of seats {} and {} coincide at ({}, {})",
+                    other.seat(),
+                    player.seat(),
+                    x,
+                    y
+                ));
+            }
+        }
+        let mut count = Count::default();
+        for (_, player) in &stones {
+            count.increment(*player);
+        }
+        if PLAYERS
+            .iter()
+            .any(|player| count[*player] != count[Player::Zero])
+        {
+            return invalid(format!(
+                "seats have {}, {}, and {} stones",
+                count[Player::Zero],
+                count[Player::One],
+                count[Player::Two]
+            ));
+        }
+        for ((x, y), player) in &stones {
+            // Distances from the edges opposite to the corners are rotated.
+            let rotated = (y - x, self.range - 1 - x);
+            let mut next_player = *player;
+            next_player.advance();
+            if owners.get(&rotated) != Some(&next_player) {
+                return invalid(format!(
+                    "the stone of seat {} at ({}, {}) is not rotated into one of seat {} at ({}, {})",
+                    player.seat(),
+                    x,
+                    y,
+                    next_player.seat(),
+                    rotated.0,
+                    rotated.1
+                ));
             }
-            _ => (),
         }
+        Ok(())
+    }
+
+    /// Gives extra starting stones to players and initializes the board,
+    /// where `handicap` is the numbers of stones indexed by `Player::index`.
+    /// The board is not changed if the stones do not fit in the home regions,
+    /// overlap the opening, or leave no position for `first_player`.
+    pub fn try_set_handicap(
+        &mut self,
+        handicap: [usize; 3],
+        first_player: Player,
+    ) -> Result<(), TriversiError> {
+        let home_size = self.range / 3 * (self.range / 3 + 1) / 2;
+        for player in PLAYERS {
+            if handicap[player.index()] > home_size {
+                return Err(TriversiError::HandicapTooLarge(player.seat(), home_size));
+            }
+        }
+        let previous = self.handicap;
+        self.handicap = [0; 3];
+        self.init();
+        let overlaps = PLAYERS.iter().any(|player| {
+            self.home_positions(*player)
+                .into_iter()
+                .take(handicap[player.index()])
+                .any(|position| self.player(position).is_some())
+        });
+        self.handicap = handicap;
+        self.init();
+        if overlaps || self.selectable_positions(first_player).is_empty() {
+            self.handicap = previous;
+            self.init();
+            return Err(TriversiError::HandicapBlocksOpening(first_player.seat()));
+        }
+        Ok(())
+    }
+
+    /// Positions of the extra starting stones of `player`.
+    pub fn handicap_positions(&self, player: Player) -> Vec<(usize, usize)> {
+        let mut positions = self.home_positions(player);
+        positions.truncate(self.handicap[player.index()]);
+        positions
+    }
+
+    /// Positions in the home region of `player`, nearest to its center first
+    /// and in the row-major order among the same distance.
+    fn home_positions(&self, player: Player) -> Vec<(usize, usize)> {
+        let center = self.home_center(player);
+        let mut positions = (0..self.range)
+            .flat_map(|y| (0..=y).map(move |x| (x, y)))
+            .filter(|position| self.home_player(*position) == Some(player))
+            .collect::<Vec<_>>();
+        positions.sort_by_key(|position| (distance(*position, center), Position::from(*position)));
+        positions
+    }
+
+    /// Every position of the triangle with its stone, in the order of rows from the apex.
+    pub fn iter_cells(&self) -> impl Iterator<Item = ((usize, usize), Option<Player>)> + '_ {
+        self.board.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, player)| ((x, y), *player))
+        })
+    }
+
+    /// Positions without stones, in the order of rows from the apex.
+    pub fn iter_empty(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.iter_cells()
+            .filter_map(|(position, player)| player.is_none().then_some(position))
+    }
+
+    /// Positions with stones and their owners, in the order of rows from the apex.
+    pub fn iter_occupied(&self) -> impl Iterator<Item = ((usize, usize), Player)> + '_ {
+        self.iter_cells()
+            .filter_map(|(position, player)| Some((position, player?)))
     }
 
     /// Player in a position.
@@ -127,13 +336,203 @@ impl Board {
     pub fn set_player(&mut self, (x, y): (usize, usize), player: Option<Player>) {
         if let Some(player) = player {
             self.count.increment(player);
+            self.hash ^= self.zobrist.key((x, y), player);
         }
         if let Some(player) = self.player((x, y)) {
             self.count.decrement(player);
+            self.hash ^= self.zobrist.key((x, y), player);
         }
         *self.board.get_mut(y).unwrap().get_mut(x).unwrap() = player;
     }
 
+    /// Zobrist hash computed from all of the stones, which equals `hash` unless the cells are modified directly.
+    pub fn recompute_hash(&self) -> u64 {
+        self.zobrist.hash(self)
+    }
+
+    /// Positions of the three vertices of the board.
+    pub fn corners(&self) -> [(usize, usize); 3] {
+        [
+            (0, 0),
+            (0, self.range - 1),
+            (self.range - 1, self.range - 1),
+        ]
+    }
+
+    /// Position nearest to the centroid of the board.
+    pub fn center(&self) -> (usize, usize) {
+        let last = self.range - 1;
+        (round_third(last), round_third(2 * last))
+    }
+
+    /// Position nearest to the centroid of the home region of `player`.
+    pub fn home_center(&self, player: Player) -> (usize, usize) {
+        // Home regions are triangles whose edges have `range / 3` positions.
+        let home_last = self.range / 3 - 1;
+        let (near, far) = (round_third(home_last), round_third(2 * home_last));
+        let last = self.range - 1;
+        match player {
+            Player::Zero => (near, far),
+            Player::One => (near, last - near),
+            Player::Two => (last - far, last - near),
+        }
+    }
+
+    /// Player whose home region contains `position`.
+    /// Home regions are the triangles at the corners whose edges are one third of `range`,
+    /// and belong to players in the order of `corners`.
+    pub fn home_player(&self, (x, y): (usize, usize)) -> Option<Player> {
+        // Distances from the edges opposite to each corner, whose sum is `range - 1`.
+        let closeness = [self.range - 1 - y, y - x, x];
+        closeness
+            .iter()
+            .position(|c| *c > self.range - 1 - self.range / 3)
+            .and_then(Player::from_index)
+    }
+
+    /// Numbers of empty positions in regions bordered only by stones of each player,
+    /// where a region is a set of empty positions connected to their neighbors.
+    pub fn territories(&self) -> Count {
+        let mut territories = Count::default();
+        let mut visited = HashSet::new();
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, player) in row.iter().enumerate() {
+                if player.is_some() || !visited.insert((x, y)) {
+                    continue;
+                }
+                let mut size = 0;
+                let mut borders = HashSet::new();
+                let mut stack = vec![(x, y)];
+                while let Some(position) = stack.pop() {
+                    size += 1;
+                    for (_, neighbor) in self.neighbors(position) {
+                        match self.player(neighbor) {
+                            Some(player) => {
+                                borders.insert(player);
+                            }
+                            None => {
+                                if visited.insert(neighbor) {
+                                    stack.push(neighbor);
+                                }
+                            }
+                        }
+                    }
+                }
+                if borders.len() == 1 {
+                    territories.add(*borders.iter().next().unwrap(), size);
+                }
+            }
+        }
+        territories
+    }
+
+    /// Scores of the players by `scoring`.
+    pub fn scores(&self) -> Count {
+        let mut scores = self.count.clone();
+        if self.scoring == Scoring::Territory {
+            for (player, territory) in self.territories().iter() {
+                scores.add(player, *territory);
+            }
+        }
+        scores
+    }
+
+    /// Players and their scores, ordered from the best.
+    /// Players having the same score are ordered by `tiebreak`,
+    /// where `skips` is the number of skipped turns of each player and `first_player` moved first.
+    pub fn ranking(
+        &self,
+        tiebreak: Tiebreak,
+        skips: &Count,
+        first_player: Player,
+    ) -> Vec<(Player, u64)> {
+        let mut ranking = self.scores().ranking();
+        ranking.sort_by_key(|(player, count)| {
+            (
+                cmp::Reverse(*count),
+                self.tiebreak_key(tiebreak, skips, first_player, *player),
+            )
+        });
+        ranking
+    }
+
+    /// Player having the best score and the lead over the second, where a lead of 0 means a tie.
+    pub fn score_margin(&self) -> (Player, u64) {
+        let ranking = self.scores().ranking();
+        (ranking[0].0, ranking[0].1 - ranking[1].1)
+    }
+
+    /// Players having the best score which cannot be resolved by `tiebreak`, ordered as in `ranking`.
+    pub fn leaders(&self, tiebreak: Tiebreak, skips: &Count, first_player: Player) -> Vec<Player> {
+        let ranking = self.ranking(tiebreak, skips, first_player);
+        let (first, first_count) = ranking[0];
+        let first_key = self.tiebreak_key(tiebreak, skips, first_player, first);
+        ranking
+            .into_iter()
+            .take_while(|(player, count)| {
+                *count == first_count
+                    && self.tiebreak_key(tiebreak, skips, first_player, *player) == first_key
+            })
+            .map(|(player, _)| player)
+            .collect()
+    }
+
+    /// The best player, or `None` if the best players cannot be resolved by `tiebreak`.
+    pub fn winner(
+        &self,
+        tiebreak: Tiebreak,
+        skips: &Count,
+        first_player: Player,
+    ) -> Option<Player> {
+        match self.leaders(tiebreak, skips, first_player).as_slice() {
+            [winner] => Some(*winner),
+            _ => None,
+        }
+    }
+
+    /// Points of the game in a match, 2 for the first, 1 for the second, and 0 for the last,
+    /// where players who cannot be resolved by `tiebreak` share the points of the lower rank.
+    pub fn match_points(&self, tiebreak: Tiebreak, skips: &Count, first_player: Player) -> Count {
+        let scores = self.scores();
+        let key = |player: Player| {
+            (
+                cmp::Reverse(scores[player]),
+                self.tiebreak_key(tiebreak, skips, first_player, player),
+            )
+        };
+        let mut points = Count::default();
+        for player in PLAYERS.iter() {
+            let n_worse = PLAYERS
+                .iter()
+                .filter(|other| key(**other) > key(*player))
+                .count();
+            points.add(*player, n_worse as u64);
+        }
+        points
+    }
+
+    /// Key of `player` used by `tiebreak`, where smaller is better.
+    fn tiebreak_key(
+        &self,
+        tiebreak: Tiebreak,
+        skips: &Count,
+        first_player: Player,
+        player: Player,
+    ) -> i64 {
+        match tiebreak {
+            Tiebreak::None => 0,
+            Tiebreak::Corners => {
+                -(self
+                    .corners()
+                    .iter()
+                    .filter(|corner| self.player(**corner) == Some(player))
+                    .count() as i64)
+            }
+            Tiebreak::FirstMove => player.turn_order(first_player) as i64,
+            Tiebreak::FewestSkips => skips[player] as i64,
+        }
+    }
+
     pub fn initial_position(&self) -> (usize, usize) {
         (0, 0)
     }
@@ -170,156 +569,708 @@ impl Board {
         }
     }
 
-    pub fn update_availables(&self, availables: &mut Availables) {
-        for &player in PLAYERS {
-            availables.get_mut(&player).unwrap().clear();
-            for (y, row) in self.board.iter().enumerate() {
-                for (x, target_player) in row.iter().enumerate() {
-                    if target_player.is_none() {
-                        if x != 0 {
-                            self.add_available_for_left(player, (x, y), availables);
-                            self.add_available_for_left_up(player, (x, y), availables);
-                        }
-                        if x != y {
-                            self.add_available_for_right(player, (x, y), availables);
-                            self.add_available_for_up(player, (x, y), availables);
-                        }
-                        if y != self.range - 1 {
-                            self.add_available_for_down(player, (x, y), availables);
-                            self.add_available_for_right_down(player, (x, y), availables);
-                        }
+    /// Position next to `position` in `direction`, if it is on the board.
+    pub fn neighbor(
+        &self,
+        position: (usize, usize),
+        direction: Direction,
+    ) -> Option<(usize, usize)> {
+        direction.step(position, self.range)
+    }
+
+    /// Positions next to `position` which are on the board.
+    pub fn neighbors(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (Direction, (usize, usize))> + '_ {
+        DIRECTIONS.iter().filter_map(move |&direction| {
+            self.neighbor(position, direction)
+                .map(|neighbor| (direction, neighbor))
+        })
+    }
+
+    /// Positions on the board from `from` (exclusive) to the edge in `direction`.
+    pub fn ray(
+        &self,
+        from: (usize, usize),
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        iter::successors(self.neighbor(from, direction), move |&position| {
+            self.neighbor(position, direction)
+        })
+    }
+
+    /// Positions flipped when `player` places a stone at `position`.
+    pub fn flips(&self, player: Player, position: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut flips = Vec::new();
+        for &direction in DIRECTIONS {
+            if !self.capture_rules.is_enabled(direction) {
+                continue;
+            }
+            let start = flips.len();
+            let mut is_closed = false;
+            for under_line_position in self.ray(position, direction) {
+                match self.player(under_line_position) {
+                    Some(under_line_player) if under_line_player == player => {
+                        is_closed = true;
+                        break;
                     }
+                    Some(_) => flips.push(under_line_position),
+                    None => break,
                 }
             }
+            if !is_closed {
+                flips.truncate(start);
+            }
         }
+        flips
     }
 
-    fn add_available_for_left(
+    /// Lines from `position` through the consecutive positions of `flipped` in each direction,
+    /// each of which starts at `position`.
+    pub fn capture_lines(
         &self,
-        player: Player,
-        (x, y): (usize, usize),
-        availables: &mut Availables,
-    ) {
-        self.add_available(
-            player,
-            (x, y),
-            (x - 1, y),
-            (0..x).rev(),
-            iter::repeat(y),
-            availables,
-        );
+        position: (usize, usize),
+        flipped: &HashSet<(usize, usize)>,
+    ) -> Vec<Vec<(usize, usize)>> {
+        DIRECTIONS
+            .iter()
+            .map(|&direction| {
+                iter::once(position)
+                    .chain(
+                        self.ray(position, direction)
+                            .take_while(|position| flipped.contains(position)),
+                    )
+                    .collect::<Vec<_>>()
+            })
+            .filter(|line| line.len() > 1)
+            .collect()
     }
 
-    fn add_available_for_right(
+    /// Directions in which placing a stone at `position` flips the stones of `flipped`,
+    /// which are those of the neighbors in `flipped`.
+    pub fn capture_directions(
         &self,
+        position: (usize, usize),
+        flipped: &HashSet<(usize, usize)>,
+    ) -> Vec<Direction> {
+        self.neighbors(position)
+            .filter(|(_, neighbor)| flipped.contains(neighbor))
+            .map(|(direction, _)| direction)
+            .collect()
+    }
+
+    /// Empty positions where `player` can place a stone, in the order of rows.
+    pub fn selectable_positions(&self, player: Player) -> Vec<(usize, usize)> {
+        self.iter_empty()
+            .filter(|position| !self.flips(player, *position).is_empty())
+            .collect()
+    }
+
+    /// Places a stone of `player` at `position` and flips stones,
+    /// where `position` should be selectable by `player`.
+    /// Unlike cloning the board, this costs only the number of flipped stones besides finding them.
+    pub fn apply_move(&mut self, player: Player, position: (usize, usize)) -> MoveUndo {
+        let flipped = self
+            .flips(player, position)
+            .into_iter()
+            .map(|flipped_position| (flipped_position, self.player(flipped_position).unwrap()))
+            .collect::<Vec<_>>();
+        for (flipped_position, _) in flipped.iter() {
+            self.set_player(*flipped_position, Some(player));
+        }
+        self.set_player(position, Some(player));
+        MoveUndo::new(position, flipped)
+    }
+
+    /// Whether `player` can place a stone at `position`, which must be on the board, empty, and flip stones.
+    pub fn is_legal_move(&self, player: Player, position: (usize, usize)) -> bool {
+        validate_position(position, self.range).is_ok()
+            && self.player(position).is_none()
+            && !self.flips(player, position).is_empty()
+    }
+
+    /// Places a stone of `player` at `position` and flips stones like `apply_move`,
+    /// or returns an error without changing the board if `player` cannot select `position`.
+    pub fn try_apply_move(
+        &mut self,
         player: Player,
         (x, y): (usize, usize),
-        availables: &mut Availables,
-    ) {
-        self.add_available(
-            player,
-            (x, y),
-            (x + 1, y),
-            x + 1..=y,
-            iter::repeat(y),
-            availables,
-        );
+    ) -> Result<MoveUndo, TriversiError> {
+        validate_position((x, y), self.range)?;
+        if let Some(occupant) = self.player((x, y)) {
+            return Err(TriversiError::PositionOccupied(x, y, occupant.to_string()));
+        }
+        if self.flips(player, (x, y)).is_empty() {
+            return Err(TriversiError::IllegalMove {
+                player: player.to_string(),
+                position: (x, y),
+            });
+        }
+        Ok(self.apply_move(player, (x, y)))
+    }
+
+    /// Reverts a move applied by `apply_move`, including the count of stones.
+    pub fn undo_move(&mut self, undo: MoveUndo) {
+        for (flipped_position, player) in undo.flipped() {
+            self.set_player(*flipped_position, Some(*player));
+        }
+        self.set_player(undo.position(), None);
+    }
+
+    /// Positions selectable by each player as pretty JSON, which is deterministic for the same position.
+    pub fn availables_snapshot(&self) -> String {
+        let mut availables = Availables::default();
+        self.update_availables(&mut availables);
+        serde_json::to_string_pretty(&availables).unwrap()
     }
 
-    fn add_available_for_up(
+    pub fn update_availables(&self, availables: &mut Availables) {
+        availables.clear();
+        for target_position in self.iter_empty() {
+            for &direction in DIRECTIONS {
+                if !self.capture_rules.is_enabled(direction) {
+                    continue;
+                }
+                self.add_available(target_position, direction, availables);
+            }
+        }
+    }
+
+    /// Adds the stones in `direction` from `target_position` to the availables of every player
+    /// who closes them, where a ray is walked once for all the players.
+    fn add_available(
         &self,
-        player: Player,
-        (x, y): (usize, usize),
+        target_position: (usize, usize),
+        direction: Direction,
         availables: &mut Availables,
     ) {
-        self.add_available(
-            player,
-            (x, y),
-            (x, y - 1),
-            iter::repeat(x),
-            (x..y).rev(),
-            availables,
+        availables.positions_buf_mut().clear();
+        for under_line_position in self.ray(target_position, direction) {
+            if self.player(under_line_position).is_none() {
+                break;
+            }
+            availables.positions_buf_mut().push(under_line_position);
+        }
+        for &player in PLAYERS {
+            // Stones before the first one of `player` are those of the others, which are flipped if there are any.
+            let closing = availables
+                .positions_buf()
+                .iter()
+                .position(|position| self.player(*position) == Some(player));
+            if let Some(len @ 1..) = closing {
+                availables.add_from_buf(player, target_position, len);
+            }
+        }
+    }
+}
+
+/// Plain text rendering of the board.
+/// Empty positions are `.` and stones are the player numbers `1`, `2`, and `3`.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.board.iter().enumerate() {
+            write!(f, "{}", " ".repeat(self.range - y - 1))?;
+            for (x, player) in row.iter().enumerate() {
+                if x != 0 {
+                    write!(f, " ")?;
+                }
+                match player {
+                    Some(player) => write!(f, "{}", player)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// `n / 3` rounded to the nearest integer.
+fn round_third(n: usize) -> usize {
+    (n + 1) / 3
+}
+
+/// Number of steps between positions, where a step is along the edges or the left-up/right-down diagonal.
+fn distance((x0, y0): (usize, usize), (x1, y1): (usize, usize)) -> usize {
+    let (dx, dy) = (x1 as i64 - x0 as i64, y1 as i64 - y0 as i64);
+    cmp::max(dx.abs(), cmp::max(dy.abs(), (dx - dy).abs())) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use rand::Rng;
+
+    /// Board of `range` having only `stones`.
+    fn board_with(range: usize, stones: &[((usize, usize), Player)]) -> Board {
+        let mut board = Board::try_new(range).unwrap();
+        let occupied = board
+            .iter_occupied()
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        for position in occupied {
+            board.set_player(position, None);
+        }
+        for (position, player) in stones {
+            board.set_player(*position, Some(*player));
+        }
+        board
+    }
+
+    /// Board of range 5 where every player has two stones,
+    /// and players 1, 2, and 3 have two, none, and one of the corners.
+    fn tied_board() -> Board {
+        board_with(
+            5,
+            &[
+                ((0, 0), Player::Zero),
+                ((0, 4), Player::Zero),
+                ((1, 2), Player::One),
+                ((2, 2), Player::One),
+                ((4, 4), Player::Two),
+                ((2, 4), Player::Two),
+            ],
+        )
+    }
+
+    fn ranked_players(
+        board: &Board,
+        tiebreak: Tiebreak,
+        skips: &Count,
+        first: Player,
+    ) -> Vec<Player> {
+        board
+            .ranking(tiebreak, skips, first)
+            .into_iter()
+            .map(|(player, _)| player)
+            .collect()
+    }
+
+    #[test]
+    fn tie_is_kept_without_tiebreak() {
+        let board = tied_board();
+        let skips = Count::default();
+        assert_eq!(board.winner(Tiebreak::None, &skips, Player::Zero), None);
+        assert_eq!(
+            board.leaders(Tiebreak::None, &skips, Player::Zero),
+            PLAYERS.to_vec()
         );
     }
 
-    fn add_available_for_down(
-        &self,
-        player: Player,
-        (x, y): (usize, usize),
-        availables: &mut Availables,
-    ) {
-        self.add_available(
-            player,
-            (x, y),
-            (x, y + 1),
-            iter::repeat(x),
-            y + 1..self.range,
-            availables,
+    #[test]
+    fn corners_tiebreak_prefers_more_corners() {
+        let board = tied_board();
+        let skips = Count::default();
+        assert_eq!(
+            ranked_players(&board, Tiebreak::Corners, &skips, Player::Zero),
+            [Player::Zero, Player::Two, Player::One]
+        );
+        assert_eq!(
+            board.winner(Tiebreak::Corners, &skips, Player::Zero),
+            Some(Player::Zero)
         );
     }
 
-    fn add_available_for_left_up(
-        &self,
-        player: Player,
-        (x, y): (usize, usize),
-        availables: &mut Availables,
-    ) {
-        self.add_available(
-            player,
-            (x, y),
-            (x - 1, y - 1),
-            (0..x).rev(),
-            (y - x..y).rev(),
-            availables,
+    #[test]
+    fn first_move_tiebreak_follows_turn_order_from_first_player() {
+        let board = tied_board();
+        let skips = Count::default();
+        assert_eq!(
+            ranked_players(&board, Tiebreak::FirstMove, &skips, Player::One),
+            [Player::One, Player::Two, Player::Zero]
+        );
+        assert_eq!(
+            board.winner(Tiebreak::FirstMove, &skips, Player::Two),
+            Some(Player::Two)
         );
     }
 
-    fn add_available_for_right_down(
-        &self,
-        player: Player,
-        (x, y): (usize, usize),
-        availables: &mut Availables,
-    ) {
-        self.add_available(
-            player,
-            (x, y),
-            (x + 1, y + 1),
-            x + 1..=self.range + x - y + 1,
-            y + 1..self.range,
-            availables,
+    #[test]
+    fn fewest_skips_tiebreak_prefers_fewer_skips() {
+        let board = tied_board();
+        let mut skips = Count::default();
+        skips.add(Player::Zero, 2);
+        skips.add(Player::Two, 1);
+        assert_eq!(
+            ranked_players(&board, Tiebreak::FewestSkips, &skips, Player::Zero),
+            [Player::One, Player::Two, Player::Zero]
+        );
+        // Players skipping as many turns stay tied.
+        skips.add(Player::One, 1);
+        assert_eq!(
+            board.leaders(Tiebreak::FewestSkips, &skips, Player::Zero),
+            [Player::One, Player::Two]
+        );
+        assert_eq!(
+            board.winner(Tiebreak::FewestSkips, &skips, Player::Zero),
+            None
         );
     }
 
-    fn add_available<IX: IntoIterator<Item = usize>, IY: IntoIterator<Item = usize>>(
-        &self,
-        player: Player,
-        target_position: (usize, usize),
-        neighbor_position: (usize, usize),
-        x_iter: IX,
-        y_iter: IY,
-        availables: &mut Availables,
-    ) {
-        if let Some(neighbor) = self.player(neighbor_position) {
-            availables.positions_buf_mut().clear();
-            availables.positions_buf_mut().push(target_position);
-            if neighbor != player {
-                for under_line_position in x_iter.into_iter().zip(y_iter) {
-                    if let Some(under_line_player) = self.player(under_line_position) {
-                        if under_line_player == player {
-                            availables.add_or_extend(
+    #[test]
+    fn tiebreak_does_not_reorder_different_scores() {
+        let mut board = tied_board();
+        board.set_player((1, 4), Some(Player::One));
+        let skips = Count::default();
+        for tiebreak in [
+            Tiebreak::Corners,
+            Tiebreak::FirstMove,
+            Tiebreak::FewestSkips,
+        ] {
+            assert_eq!(
+                board.winner(tiebreak, &skips, Player::Zero),
+                Some(Player::One)
+            );
+        }
+    }
+
+    /// Number of stones flipped over all the selectable positions of every player.
+    fn total_flips(availables: &Availables) -> usize {
+        availables
+            .values()
+            .flat_map(|available| available.values())
+            .map(|positions| positions.len() - 1)
+            .sum()
+    }
+
+    #[test]
+    fn orthogonal_rules_generate_fewer_availables() {
+        for range in [5, 8, 14, 30] {
+            let mut board = Board::try_new(range).unwrap();
+            let mut all = Availables::default();
+            board.update_availables(&mut all);
+            board.set_capture_rules(CaptureRules::orthogonal());
+            let mut orthogonal = Availables::default();
+            board.update_availables(&mut orthogonal);
+            assert!(
+                total_flips(&orthogonal) < total_flips(&all),
+                "range {}",
+                range
+            );
+            for &player in PLAYERS {
+                assert!(!orthogonal[player].is_empty(), "range {}", range);
+                for (position, positions) in orthogonal[player].iter() {
+                    assert!(positions.is_subset(&all[player][position]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orthogonal_rules_skip_diagonal_captures() {
+        let mut board = board_with(5, &[((0, 0), Player::Zero), ((1, 1), Player::One)]);
+        assert_eq!(board.flips(Player::Zero, (2, 2)), [(1, 1)]);
+        board.set_capture_rules(CaptureRules::orthogonal());
+        assert_eq!(board.flips(Player::Zero, (2, 2)), []);
+        let mut availables = Availables::default();
+        board.update_availables(&mut availables);
+        assert!(availables[Player::Zero].is_empty());
+    }
+
+    #[test]
+    fn ray_from_corners_reaches_the_other_corners() {
+        let board = Board::try_new(5).unwrap();
+        let ray = |from, direction| board.ray(from, direction).collect::<Vec<_>>();
+        assert_eq!(
+            ray((0, 0), Direction::Down),
+            [(0, 1), (0, 2), (0, 3), (0, 4)]
+        );
+        assert_eq!(
+            ray((0, 0), Direction::RightDown),
+            [(1, 1), (2, 2), (3, 3), (4, 4)]
+        );
+        assert_eq!(
+            ray((0, 4), Direction::Right),
+            [(1, 4), (2, 4), (3, 4), (4, 4)]
+        );
+        assert_eq!(ray((0, 4), Direction::Up), [(0, 3), (0, 2), (0, 1), (0, 0)]);
+        assert_eq!(
+            ray((4, 4), Direction::Left),
+            [(3, 4), (2, 4), (1, 4), (0, 4)]
+        );
+        assert_eq!(
+            ray((4, 4), Direction::LeftUp),
+            [(3, 3), (2, 2), (1, 1), (0, 0)]
+        );
+        for direction in [Direction::Left, Direction::Up, Direction::LeftUp] {
+            assert_eq!(ray((0, 0), direction), []);
+        }
+        for direction in [Direction::Down, Direction::RightDown] {
+            assert_eq!(ray((2, 4), direction), []);
+        }
+    }
+
+    /// Rays end at the edges, including the last row and the last position of each row.
+    #[test]
+    fn ray_lengths_reach_the_edges() {
+        let board = Board::try_new(8).unwrap();
+        let last = board.range() - 1;
+        for y in 0..board.range() {
+            for x in 0..=y {
+                for &direction in DIRECTIONS {
+                    let expected = match direction {
+                        Direction::Left | Direction::LeftUp => x,
+                        Direction::Right | Direction::Up => y - x,
+                        Direction::Down | Direction::RightDown => last - y,
+                    };
+                    assert_eq!(
+                        board.ray((x, y), direction).count(),
+                        expected,
+                        "{:?} from {:?}",
+                        direction,
+                        (x, y)
+                    );
+                }
+            }
+        }
+    }
+
+    /// Cells, count, availables, and hash, which must be restored by undoing moves.
+    fn snapshot(board: &Board) -> (Vec<Vec<Option<Player>>>, Count, String, u64) {
+        (
+            board.board().clone(),
+            board.count().clone(),
+            board.availables_snapshot(),
+            board.hash(),
+        )
+    }
+
+    #[test]
+    fn undo_move_restores_the_board() {
+        for range in [5, 8, 14] {
+            let mut rng = rng::seeded(range as u64);
+            let mut board = Board::try_new(range).unwrap();
+            let mut player = Player::default();
+            let mut applied = Vec::new();
+            for _ in 0..200 {
+                if !applied.is_empty() && rng.gen_bool(0.3) {
+                    let (before, undo) = applied.pop().unwrap();
+                    board.undo_move(undo);
+                    assert_eq!(snapshot(&board), before);
+                    continue;
+                }
+                let positions = board.selectable_positions(player);
+                if !positions.is_empty() {
+                    let position = positions[rng.gen_range(0..positions.len())];
+                    let before = snapshot(&board);
+                    let undo = board.apply_move(player, position);
+                    applied.push((before, undo));
+                }
+                player.advance();
+            }
+            while let Some((before, undo)) = applied.pop() {
+                board.undo_move(undo);
+                assert_eq!(snapshot(&board), before);
+            }
+        }
+    }
+
+    #[test]
+    fn handicap_is_checked_for_the_first_player() {
+        let handicap = [3, 3, 2];
+        let mut board = Board::try_new(6).unwrap();
+        assert!(matches!(
+            board.try_set_handicap(handicap, Player::from_seat(3).unwrap()),
+            Err(TriversiError::HandicapBlocksOpening(3))
+        ));
+        assert_eq!(board.handicap(), [0; 3]);
+        board
+            .try_set_handicap(handicap, Player::from_seat(1).unwrap())
+            .unwrap();
+        assert_eq!(board.handicap(), handicap);
+    }
+
+    /// Board of `range` filled with stones of `rest` except `empties` and `stones`.
+    fn filled_board(
+        range: usize,
+        rest: Player,
+        empties: &[(usize, usize)],
+        stones: &[((usize, usize), Player)],
+    ) -> Board {
+        let mut board = board_with(range, stones);
+        let positions = board
+            .iter_empty()
+            .filter(|position| !empties.contains(position))
+            .collect::<Vec<_>>();
+        for position in positions {
+            board.set_player(position, Some(rest));
+        }
+        board
+    }
+
+    /// Territories in the order of `PLAYERS`.
+    fn territory_list(board: &Board) -> Vec<u64> {
+        let territories = board.territories();
+        PLAYERS.iter().map(|player| territories[*player]).collect()
+    }
+
+    #[test]
+    fn corner_surrounded_by_a_player_is_its_territory() {
+        let board = filled_board(
+            5,
+            Player::One,
+            &[(0, 0)],
+            &[((0, 1), Player::Zero), ((1, 1), Player::Zero)],
+        );
+        assert_eq!(territory_list(&board), [1, 0, 0]);
+    }
+
+    #[test]
+    fn region_bordered_by_two_players_is_neutral() {
+        let board = filled_board(
+            5,
+            Player::Two,
+            &[(0, 0)],
+            &[((0, 1), Player::Zero), ((1, 1), Player::One)],
+        );
+        assert_eq!(territory_list(&board), [0, 0, 0]);
+    }
+
+    #[test]
+    fn connected_empty_positions_are_one_region() {
+        // The top three positions are bordered by the third row of the second player,
+        // and two separate positions of the bottom row by the first and the third players.
+        let board = filled_board(
+            5,
+            Player::One,
+            &[(0, 0), (0, 1), (1, 1), (0, 4), (4, 4)],
+            &[
+                ((0, 3), Player::Zero),
+                ((1, 4), Player::Zero),
+                ((3, 3), Player::Two),
+                ((3, 4), Player::Two),
+            ],
+        );
+        assert_eq!(territory_list(&board), [1, 3, 1]);
+    }
+
+    #[test]
+    fn board_without_stones_has_no_territory() {
+        assert_eq!(territory_list(&board_with(8, &[])), [0, 0, 0]);
+    }
+
+    #[test]
+    fn territory_scoring_adds_territories_to_stones() {
+        let mut board = filled_board(
+            5,
+            Player::One,
+            &[(0, 0)],
+            &[((0, 1), Player::Zero), ((1, 1), Player::Zero)],
+        );
+        assert_eq!(board.scores(), *board.count());
+        board.set_scoring(Scoring::Territory);
+        let scores = board.scores();
+        assert_eq!(scores[Player::Zero], 3);
+        assert_eq!(scores[Player::One], 12);
+        assert_eq!(scores[Player::Two], 0);
+    }
+
+    #[test]
+    fn incremental_hash_equals_recomputed_hash() {
+        for (seed, range) in [(0, 5), (1, 8), (2, 14), (3, 30)] {
+            let mut rng = rng::seeded(seed);
+            let mut board = Board::try_new(range).unwrap();
+            assert_eq!(board.hash(), board.recompute_hash());
+            let mut player = Player::default();
+            let mut undos = Vec::new();
+            loop {
+                let positions = board.selectable_positions(player);
+                if positions.is_empty() {
+                    if PLAYERS
+                        .iter()
+                        .all(|player| board.selectable_positions(*player).is_empty())
+                    {
+                        break;
+                    }
+                } else {
+                    let position = positions[rng.gen_range(0..positions.len())];
+                    undos.push(board.apply_move(player, position));
+                    assert_eq!(board.hash(), board.recompute_hash(), "range {}", range);
+                }
+                player.advance();
+            }
+            // Taking back the moves goes through the same positions.
+            while let Some(undo) = undos.pop() {
+                board.undo_move(undo);
+                assert_eq!(board.hash(), board.recompute_hash(), "range {}", range);
+            }
+            assert_eq!(board.hash(), Board::try_new(range).unwrap().hash());
+        }
+    }
+
+    #[test]
+    fn availables_equal_flips_of_every_cell() {
+        for (seed, range) in [(0, 5), (1, 8), (2, 14), (3, 30)] {
+            let mut rng = rng::seeded(seed);
+            for capture_rules in [CaptureRules::default(), CaptureRules::orthogonal()] {
+                for _ in 0..10 {
+                    // Cells are filled at random, which reaches positions no game does.
+                    let mut board = Board::try_new(range).unwrap();
+                    board.set_capture_rules(capture_rules.clone());
+                    let positions = board
+                        .iter_cells()
+                        .map(|(position, _)| position)
+                        .collect::<Vec<_>>();
+                    let fill = rng.gen_range(0.2..0.9);
+                    for position in positions {
+                        let player = rng
+                            .gen_bool(fill)
+                            .then(|| PLAYERS[rng.gen_range(0..PLAYERS.len())]);
+                        board.set_player(position, player);
+                    }
+                    let mut availables = Availables::default();
+                    board.update_availables(&mut availables);
+                    for player in PLAYERS {
+                        for position in board.iter_empty() {
+                            let flips = board.flips(*player, position);
+                            let expected = (!flips.is_empty()).then(|| {
+                                flips.into_iter().chain([position]).collect::<HashSet<_>>()
+                            });
+                            assert_eq!(
+                                availables[*player].get(&position),
+                                expected.as_ref(),
+                                "range {} {:?} at {:?}",
+                                range,
                                 player,
-                                target_position,
-                                availables.positions_buf().clone(),
+                                position
                             );
-                            break;
-                        } else {
-                            availables.positions_buf_mut().push(under_line_position);
                         }
-                    } else {
-                        break;
+                        assert!(availables[*player]
+                            .keys()
+                            .all(|position| board.player(*position).is_none()));
                     }
                 }
             }
         }
     }
+
+    #[test]
+    fn opening_is_valid_for_every_range() {
+        for range in (MIN_RANGE..=200).filter(|range| Board::validate_range(*range).is_ok()) {
+            let board = Board::try_new(range).unwrap();
+            assert!(
+                board.validate_opening().is_ok(),
+                "{}",
+                board.validate_opening().unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn board_is_displayed_by_seats() {
+        let board = board_with(
+            5,
+            &[
+                ((0, 0), Player::Zero),
+                ((1, 2), Player::One),
+                ((4, 4), Player::Two),
+            ],
+        );
+        assert_eq!(
+            board.to_string(),
+            "    1\n   . .\n  . 2 .\n . . . .\n. . . . 3\n"
+        );
+    }
 }
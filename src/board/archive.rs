@@ -0,0 +1,405 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Archive of a game in a human-readable text format resembling PGN of chess.
+//! Tags are followed by rounds of moves of the three players,
+//! where positions are written as a column letter and a row number, e.g. `a1` for the top.
+//!
+//! ```text
+//! [Date "2023-04-01"]
+//! [Version "0.2.0"]
+//! [Range "8"]
+//! [Player1 "Cyan"]
+//! [Player2 "Magenta"]
+//! [Player3 "Yellow"]
+//! [Score "12-9-15"]
+//!
+//! 1. c6 b5 d6
+//! 2. pass e7 b6
+//! ```
+
+use crate::board::history::validate_version;
+use crate::board::{Move, Player, Variant};
+use crate::error::TriversiError;
+use clap::ValueEnum;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Debug, Default)]
+pub struct Archive {
+    /// Date when the game is archived, as `YYYY-MM-DD`.
+    pub date: String,
+    /// Version of Triversi which played the game, which is empty in older archives.
+    pub version: String,
+    /// Start of the game in seconds since the Unix epoch, which is 0 if it is unknown.
+    pub timestamp: u64,
+    pub range: usize,
+    /// Variant of the rules, which is `None` in older archives.
+    pub variant: Option<Variant>,
+    pub seed: Option<u64>,
+    /// Numbers of extra starting stones indexed by `Player::index`.
+    pub handicap: [usize; 3],
+    pub first_player: Player,
+    /// Whether the second player took over the seat of the first in the swap variant.
+    pub swapped: bool,
+    /// Names of the players in the order of seats.
+    pub player_names: Vec<String>,
+    /// Marks of the players in the order of seats, which may be empty.
+    pub player_marks: Vec<String>,
+    /// Final numbers of stones in the order of seats.
+    pub score: Vec<u64>,
+    /// Moves with the numbers of lines where they are written, as in `parse_move_list`.
+    pub moves: Vec<(usize, Move)>,
+}
+
+impl fmt::Display for Archive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[Date \"{}\"]", self.date)?;
+        if !self.version.is_empty() {
+            writeln!(f, "[Version \"{}\"]", self.version)?;
+        }
+        if self.timestamp != 0 {
+            writeln!(f, "[Timestamp \"{}\"]", self.timestamp)?;
+        }
+        writeln!(f, "[Range \"{}\"]", self.range)?;
+        if let Some(variant) = self.variant {
+            let name = variant.to_possible_value().unwrap();
+            writeln!(f, "[Variant \"{}\"]", name.get_name())?;
+        }
+        if let Some(seed) = self.seed {
+            writeln!(f, "[Seed \"{}\"]", seed)?;
+        }
+        if self.handicap != [0; 3] {
+            writeln!(
+                f,
+                "[Handicap \"{},{}\"]",
+                self.handicap[1], self.handicap[2]
+            )?;
+        }
+        if self.first_player != Player::default() {
+            writeln!(f, "[FirstPlayer \"{}\"]", self.first_player)?;
+        }
+        if self.swapped {
+            writeln!(f, "[Swapped \"true\"]")?;
+        }
+        for (i_player, name) in self.player_names.iter().enumerate() {
+            writeln!(f, "[Player{} \"{}\"]", i_player + 1, name)?;
+        }
+        for (i_player, mark) in self.player_marks.iter().enumerate() {
+            writeln!(f, "[Player{}Mark \"{}\"]", i_player + 1, mark)?;
+        }
+        let score = self
+            .score
+            .iter()
+            .map(|count| count.to_string())
+            .collect::<Vec<_>>();
+        writeln!(f, "[Score \"{}\"]", score.join("-"))?;
+        writeln!(f)?;
+        for (i_round, round) in self.moves.chunks(3).enumerate() {
+            write!(f, "{}.", i_round + 1)?;
+            for (_, player_move) in round {
+                write!(f, " {}", format_move(*player_move))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads an archive written by `Archive`, where unknown tags are ignored.
+pub fn parse_archive(text: &str) -> Result<Archive, TriversiError> {
+    let mut archive = Archive::default();
+    let mut range = None;
+    for (line_number, line) in text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+    {
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            let (name, value) = parse_tag(line)
+                .ok_or_else(|| TriversiError::InvalidArchiveLine(line_number, line.to_owned()))?;
+            match name {
+                "Date" => archive.date = value.to_owned(),
+                "Version" => {
+                    validate_version(value)?;
+                    archive.version = value.to_owned();
+                }
+                "Timestamp" => archive.timestamp = value.parse().unwrap_or_default(),
+                "Range" => range = value.parse().ok(),
+                "Variant" => {
+                    archive.variant = Some(
+                        Variant::from_str(value, false)
+                            .map_err(|_| TriversiError::UnsupportedVariant(value.to_owned()))?,
+                    )
+                }
+                "Seed" => archive.seed = value.parse().ok(),
+                "Handicap" => {
+                    let extras = value
+                        .split(',')
+                        .map(|extra| extra.trim().parse().unwrap_or_default())
+                        .collect::<Vec<_>>();
+                    if let [second, third] = extras[..] {
+                        archive.handicap = [0, second, third];
+                    }
+                }
+                "FirstPlayer" => {
+                    archive.first_player = value
+                        .parse()
+                        .ok()
+                        .and_then(Player::from_seat)
+                        .unwrap_or_default()
+                }
+                "Swapped" => archive.swapped = value == "true",
+                "Score" => {
+                    archive.score = value
+                        .split('-')
+                        .map(|count| count.parse().unwrap_or_default())
+                        .collect()
+                }
+                _ => {
+                    if let Some(seat) = name.strip_prefix("Player") {
+                        if seat.parse::<usize>().is_ok() {
+                            archive.player_names.push(value.to_owned());
+                        } else if seat
+                            .strip_suffix("Mark")
+                            .is_some_and(|seat| seat.parse::<usize>().is_ok())
+                        {
+                            archive.player_marks.push(value.to_owned());
+                        }
+                    }
+                }
+            }
+            continue;
+        }
+        for token in line.split_whitespace() {
+            // Round numbers are only for readers.
+            if token
+                .strip_suffix('.')
+                .is_some_and(|number| number.parse::<usize>().is_ok())
+            {
+                continue;
+            }
+            let player_move = parse_move(token)
+                .ok_or_else(|| TriversiError::InvalidArchiveLine(line_number, token.to_owned()))?;
+            archive.moves.push((line_number, player_move));
+        }
+    }
+    archive.range = range.ok_or_else(|| TriversiError::MissingArchiveTag("Range".to_owned()))?;
+    Ok(archive)
+}
+
+/// `[Name "value"]` split into the name and the value.
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let (name, value) = line.strip_prefix('[')?.strip_suffix(']')?.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name, value))
+}
+
+/// Column letters followed by the row number, where `x` is written as `a`, ..., `z`, `aa`, ...
+pub fn format_move(player_move: Move) -> String {
+    match player_move {
+        Move::Put((x, y)) => {
+            let mut letters = Vec::new();
+            let mut x = x + 1;
+            while x > 0 {
+                x -= 1;
+                letters.push((b'a' + (x % 26) as u8) as char);
+                x /= 26;
+            }
+            format!("{}{}", letters.iter().rev().collect::<String>(), y + 1)
+        }
+        Move::Pass => "pass".to_owned(),
+    }
+}
+
+/// Inverse of `format_move`.
+fn parse_move(token: &str) -> Option<Move> {
+    if token == "pass" {
+        return Some(Move::Pass);
+    }
+    let i_digit = token.find(|c: char| c.is_ascii_digit())?;
+    let (letters, number) = token.split_at(i_digit);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let x = letters
+        .bytes()
+        .fold(0, |x, letter| 26 * x + (letter - b'a') as usize + 1)
+        - 1;
+    let y = number.parse::<usize>().ok()?.checked_sub(1)?;
+    Some(Move::Put((x, y)))
+}
+
+/// Today in UTC as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86400)
+        .unwrap_or_default() as i64;
+    // Conversion from days since 1970-01-01 to the civil date in the proleptic Gregorian calendar.
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{Board, PLAYERS};
+    use crate::game::Game;
+    use crate::rng;
+    use rand::Rng;
+
+    /// Moves of a game of random positions on a board of `range` and its final game.
+    fn random_game(range: usize, seed: u64) -> (Vec<Move>, Game) {
+        let mut rng = rng::seeded(seed);
+        let mut game = Game::new(Board::try_new(range).unwrap());
+        let mut moves = Vec::new();
+        while !game.is_over() {
+            let player = game.current_player();
+            let positions = game.legal_positions(player);
+            let player_move = if positions.is_empty() {
+                Move::Pass
+            } else {
+                Move::Put(positions[rng.gen_range(0..positions.len())])
+            };
+            game.play(player, player_move).unwrap();
+            moves.push(player_move);
+        }
+        (moves, game)
+    }
+
+    /// Moves of `archive` without the numbers of lines.
+    fn moves_of(archive: &Archive) -> Vec<Move> {
+        archive
+            .moves
+            .iter()
+            .map(|(_, player_move)| *player_move)
+            .collect()
+    }
+
+    #[test]
+    fn game_with_a_pass_round_trips() {
+        let (moves, game) = (0..)
+            .map(|seed| random_game(8, seed))
+            .find(|(moves, _)| moves[..moves.len() - 1].contains(&Move::Pass))
+            .unwrap();
+        let archive = Archive {
+            date: "2023-04-01".to_owned(),
+            range: 8,
+            score: PLAYERS
+                .iter()
+                .map(|player| game.board().count()[*player])
+                .collect(),
+            moves: moves.iter().map(|player_move| (0, *player_move)).collect(),
+            ..Archive::default()
+        };
+        let text = archive.to_string();
+        assert!(text.contains(" pass"));
+
+        let parsed = parse_archive(&text).unwrap();
+        assert_eq!(parsed.range, 8);
+        assert_eq!(parsed.score, archive.score);
+        assert_eq!(moves_of(&parsed), moves);
+        assert_eq!(parsed.to_string(), text);
+
+        // The parsed moves including the pass replay the game.
+        let mut replayed = Game::new(Board::try_new(parsed.range).unwrap());
+        for player_move in moves_of(&parsed) {
+            replayed
+                .play(replayed.current_player(), player_move)
+                .unwrap();
+        }
+        assert!(replayed.is_over());
+        assert_eq!(replayed.board().count(), game.board().count());
+    }
+
+    #[test]
+    fn tags_round_trip() {
+        let archive = Archive {
+            date: "2023-04-01".to_owned(),
+            version: "0.2.0".to_owned(),
+            timestamp: 1680307200,
+            range: 30,
+            variant: Some(Variant::Swap),
+            seed: Some(42),
+            handicap: [0, 1, 2],
+            first_player: Player::from_seat(2).unwrap(),
+            swapped: true,
+            player_names: vec!["Cyan".to_owned(), "Magenta".to_owned(), "Yellow".to_owned()],
+            player_marks: vec!["o".to_owned(), "x".to_owned(), "<>".to_owned()],
+            score: vec![12, 9, 15],
+            moves: vec![
+                (0, Move::Put((26, 29))),
+                (0, Move::Pass),
+                (0, Move::Put((0, 0))),
+                (0, Move::Put((27, 28))),
+            ],
+        };
+        let text = archive.to_string();
+        assert!(text.contains("1. aa30 pass a1\n2. ab29\n"));
+
+        let parsed = parse_archive(&text).unwrap();
+        assert_eq!(parsed.date, archive.date);
+        assert_eq!(parsed.version, archive.version);
+        assert_eq!(parsed.timestamp, archive.timestamp);
+        assert_eq!(parsed.range, archive.range);
+        assert_eq!(parsed.variant, archive.variant);
+        assert_eq!(parsed.seed, archive.seed);
+        assert_eq!(parsed.handicap, archive.handicap);
+        assert_eq!(parsed.first_player, archive.first_player);
+        assert_eq!(parsed.swapped, archive.swapped);
+        assert_eq!(parsed.player_names, archive.player_names);
+        assert_eq!(parsed.player_marks, archive.player_marks);
+        assert_eq!(parsed.score, archive.score);
+        assert_eq!(moves_of(&parsed), moves_of(&archive));
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn moves_are_numbered_by_lines() {
+        let parsed =
+            parse_archive("[Range \"5\"]\n\n1. a1 pass\n[Unknown \"tag\"]\n b2\n").unwrap();
+        assert_eq!(
+            parsed.moves,
+            vec![
+                (3, Move::Put((0, 0))),
+                (3, Move::Pass),
+                (5, Move::Put((1, 1)))
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_archives_are_errors() {
+        assert!(matches!(
+            parse_archive("[Range \"5\"]\n1. a1 1a\n"),
+            Err(TriversiError::InvalidArchiveLine(2, token)) if token == "1a"
+        ));
+        assert!(matches!(
+            parse_archive("[Range 5]\n"),
+            Err(TriversiError::InvalidArchiveLine(1, _))
+        ));
+        assert!(matches!(
+            parse_archive("[Date \"2023-04-01\"]\n1. a1\n"),
+            Err(TriversiError::MissingArchiveTag(tag)) if tag == "Range"
+        ));
+    }
+}
@@ -3,33 +3,75 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use crate::board::{Player, PLAYERS};
+use crate::board::{PerPlayer, Player, Position};
 use getset::{Getters, MutGetters};
+use serde::ser::{Serialize, Serializer};
+use serde_derive::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::ops::{Deref, DerefMut};
 
-#[derive(Clone, Debug, Getters, MutGetters)]
+/// Positions selectable by each player and the positions whose stones become the player's by selecting them.
+/// It is serialized with positions as `[x, y]` in the row-major order.
+#[derive(Debug, Default, Getters, MutGetters, Serialize)]
 #[getset(get = "pub", get_mut = "pub")]
 pub struct Availables {
     #[allow(clippy::type_complexity)]
-    availables: HashMap<Player, HashMap<(usize, usize), HashSet<(usize, usize)>>>,
+    #[serde(serialize_with = "serialize_sorted")]
+    availables: PerPlayer<HashMap<(usize, usize), HashSet<(usize, usize)>>>,
+    #[serde(skip)]
     positions_buf: Vec<(usize, usize)>,
+    /// Emptied sets of positions kept by `clear` to be reused without allocating.
+    #[serde(skip)]
+    #[getset(skip)]
+    spare_sets: Vec<HashSet<(usize, usize)>>,
 }
 
-impl Default for Availables {
-    fn default() -> Self {
-        Self {
-            availables: PLAYERS
+/// Selectable position and the positions changed by selecting it, including itself.
+#[derive(Serialize)]
+struct AvailableMove {
+    position: (usize, usize),
+    positions: Vec<(usize, usize)>,
+}
+
+#[allow(clippy::type_complexity)]
+fn serialize_sorted<S: Serializer>(
+    availables: &PerPlayer<HashMap<(usize, usize), HashSet<(usize, usize)>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let moves = availables
+        .values()
+        .map(|available| {
+            let mut moves = available
                 .iter()
-                .map(|player| (*player, HashMap::new()))
-                .collect::<HashMap<_, _>>(),
+                .map(|(position, positions)| {
+                    let mut positions = positions.iter().copied().collect::<Vec<_>>();
+                    positions.sort_by_key(|position| Position::from(*position));
+                    AvailableMove {
+                        position: *position,
+                        positions,
+                    }
+                })
+                .collect::<Vec<_>>();
+            moves.sort_by_key(|available_move| Position::from(available_move.position));
+            moves
+        })
+        .collect::<Vec<_>>();
+    moves.serialize(serializer)
+}
+
+/// Clone of the positions without the buffers, which are only used while updating them.
+impl Clone for Availables {
+    fn clone(&self) -> Self {
+        Self {
+            availables: self.availables.clone(),
             positions_buf: Vec::new(),
+            spare_sets: Vec::new(),
         }
     }
 }
 
 impl Deref for Availables {
-    type Target = HashMap<Player, HashMap<(usize, usize), HashSet<(usize, usize)>>>;
+    type Target = PerPlayer<HashMap<(usize, usize), HashSet<(usize, usize)>>>;
     fn deref(&self) -> &Self::Target {
         &self.availables
     }
@@ -42,19 +84,67 @@ impl DerefMut for Availables {
 }
 
 impl Availables {
+    /// Adds `position` and `candidates` to the positions changed by `player` selecting `position`.
     pub fn add_or_extend(
         &mut self,
         player: Player,
         position: (usize, usize),
-        candidate_list: Vec<(usize, usize)>,
+        candidates: impl IntoIterator<Item = (usize, usize)>,
     ) {
-        for candidate in candidate_list {
-            self.availables
-                .get_mut(&player)
-                .unwrap()
-                .entry(position)
-                .or_insert_with(|| HashSet::from([candidate]))
-                .insert(candidate);
+        self.entry(player, position).extend(candidates);
+    }
+
+    /// Adds `position` and the first `len` positions of the buffer as in `add_or_extend`.
+    pub fn add_from_buf(&mut self, player: Player, position: (usize, usize), len: usize) {
+        // Buffer is taken out while the set is borrowed mutably, which keeps its allocation.
+        let candidates = std::mem::take(&mut self.positions_buf);
+        self.add_or_extend(player, position, candidates[..len].iter().copied());
+        self.positions_buf = candidates;
+    }
+
+    /// Removes the positions of every player, whose sets are kept to be reused.
+    pub fn clear(&mut self) {
+        for available in self.availables.values_mut() {
+            self.spare_sets
+                .extend(available.drain().map(|(_, mut positions)| {
+                    positions.clear();
+                    positions
+                }));
         }
     }
+
+    /// Set of the positions changed by `player` selecting `position`, which includes `position`.
+    fn entry(&mut self, player: Player, position: (usize, usize)) -> &mut HashSet<(usize, usize)> {
+        let spare_sets = &mut self.spare_sets;
+        self.availables[player].entry(position).or_insert_with(|| {
+            let mut positions = spare_sets.pop().unwrap_or_default();
+            positions.insert(position);
+            positions
+        })
+    }
+
+    /// Position flipping the most stones of `player` and the number of the stones,
+    /// where the first in the row-major order is chosen among the same number.
+    pub fn max_flip_move(&self, player: Player) -> Option<((usize, usize), u64)> {
+        sorted_moves(self, player)
+            .into_iter()
+            .map(|Position(position)| {
+                // The selected position itself is included in the positions.
+                let flips = self.availables[player][&position].len() as u64 - 1;
+                (position, flips)
+            })
+            .rev()
+            .max_by_key(|(_, flips)| *flips)
+    }
+}
+
+/// Positions selectable by `player` in the row-major order,
+/// which does not depend on the iteration order of the hash map.
+pub fn sorted_moves(availables: &Availables, player: Player) -> Vec<Position> {
+    let mut positions = availables[player]
+        .keys()
+        .map(|position| Position::from(*position))
+        .collect::<Vec<_>>();
+    positions.sort();
+    positions
 }
@@ -0,0 +1,42 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use super::direction::{Direction, DIRECTIONS};
+use std::collections::HashSet;
+
+/// Directions along which stones are captured.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaptureRules {
+    directions: HashSet<Direction>,
+}
+
+impl Default for CaptureRules {
+    /// All the six directions.
+    fn default() -> Self {
+        Self::new(DIRECTIONS.iter().copied())
+    }
+}
+
+impl CaptureRules {
+    pub fn new(directions: impl IntoIterator<Item = Direction>) -> Self {
+        Self {
+            directions: directions.into_iter().collect(),
+        }
+    }
+
+    /// Horizontal and vertical directions only, i.e., without `LeftUp` and `RightDown`.
+    pub fn orthogonal() -> Self {
+        Self::new(
+            DIRECTIONS
+                .iter()
+                .copied()
+                .filter(|direction| !direction.is_diagonal()),
+        )
+    }
+
+    pub fn is_enabled(&self, direction: Direction) -> bool {
+        self.directions.contains(&direction)
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Short code of a board to share it in a chat, which is URL-safe base64 without padding.
+//! The encoded bytes are the range in two bytes of big endian and the positions in the order of rows,
+//! each of which takes two bits of 0 for empty and the seat for a stone, and the last byte is filled with zeros.
+
+use crate::board::{Board, Player};
+use crate::error::TriversiError;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Board {
+    /// Code of the range and the stones, which is the inverse of `from_code`.
+    pub fn to_code(&self) -> String {
+        let range = u16::try_from(self.range).expect("range of a board fits in two bytes");
+        let positions = self.iter_cells().collect::<Vec<_>>();
+        let mut bytes = range.to_be_bytes().to_vec();
+        bytes.resize(2 + positions.len().div_ceil(4), 0);
+        for (i, (_, player)) in positions.into_iter().enumerate() {
+            let value = player.map_or(0, |player| player.seat() as u8);
+            bytes[2 + i / 4] |= value << (6 - 2 * (i % 4));
+        }
+        encode(&bytes)
+    }
+
+    /// Board written in `code`, which rejects unknown characters, a truncated or extended code,
+    /// and an invalid range.
+    pub fn from_code(code: &str) -> Result<Self, TriversiError> {
+        let invalid = || TriversiError::InvalidCode(code.to_owned());
+        let bytes = decode(code.trim()).ok_or_else(invalid)?;
+        let (range, cells) = bytes.split_at_checked(2).ok_or_else(invalid)?;
+        let range = usize::from(u16::from_be_bytes([range[0], range[1]]));
+        let mut board = Board::try_new(range).map_err(|_| invalid())?;
+        let positions = board
+            .iter_cells()
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        if cells.len() != positions.len().div_ceil(4) {
+            return Err(invalid());
+        }
+        if positions.len() % 4 != 0
+            && cells[cells.len() - 1] & (0xff >> (2 * (positions.len() % 4))) != 0
+        {
+            return Err(invalid());
+        }
+        for (i, position) in positions.into_iter().enumerate() {
+            let value = (cells[i / 4] >> (6 - 2 * (i % 4))) & 0b11;
+            let player = match value {
+                0 => None,
+                seat => Some(Player::from_seat(usize::from(seat)).ok_or_else(invalid)?),
+            };
+            board.set_player(position, player);
+        }
+        Ok(board)
+    }
+}
+
+fn encode(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buffer = [0; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+        for i in 0..=chunk.len() {
+            code.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    code
+}
+
+/// Inverse of `encode`, which is `None` unless `code` is canonical.
+fn decode(code: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(code.len() / 4 * 3 + 2);
+    for chunk in code.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut bits = 0;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+            bits |= value << (18 - 6 * i);
+        }
+        let decoded = &bits.to_be_bytes()[1..chunk.len()];
+        // Bits left over by a short chunk must be zero so that each board has only one code.
+        if bits & (0xff_ffff >> (8 * (chunk.len() - 1))) != 0 {
+            return None;
+        }
+        bytes.extend_from_slice(decoded);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::MIN_RANGE;
+    use crate::rng;
+    use rand::Rng;
+
+    /// Board of `range` whose cells are set at random, including empty cells.
+    fn random_board(range: usize, seed: u64) -> Board {
+        let mut rng = rng::seeded(seed);
+        let mut board = Board::try_new(range).unwrap();
+        let positions = board
+            .iter_cells()
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        for position in positions {
+            board.set_player(position, Player::from_seat(rng.gen_range(0..=3)));
+        }
+        board
+    }
+
+    fn cells(board: &Board) -> Vec<((usize, usize), Option<Player>)> {
+        board.iter_cells().collect()
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for range in (MIN_RANGE..=50).filter(|range| Board::try_new(*range).is_ok()) {
+            for board in [
+                Board::try_new(range).unwrap(),
+                random_board(range, range as u64),
+            ] {
+                let code = board.to_code();
+                assert!(code.bytes().all(|c| ALPHABET.contains(&c)), "{}", code);
+                let decoded = Board::from_code(&code).unwrap();
+                assert_eq!(decoded.range(), range);
+                assert_eq!(cells(&decoded), cells(&board), "range {}", range);
+                assert_eq!(decoded.to_code(), code);
+                assert_eq!(
+                    cells(&Board::from_code(&format!(" {}\n", code)).unwrap()),
+                    cells(&board)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_codes_are_invalid() {
+        // The 15 cells of range 5 take four bytes, whose last two bits are padding.
+        let code = random_board(5, 0).to_code();
+        assert_eq!(code.len(), 8);
+        let invalid =
+            |code: &str| matches!(Board::from_code(code), Err(TriversiError::InvalidCode(_)));
+        let mut unknown = code.clone();
+        unknown.replace_range(3..4, "!");
+        let mut padded = code.clone();
+        padded.push('=');
+        for code in [
+            "",
+            "A",
+            unknown.as_str(),
+            padded.as_str(),
+            &code[..7],
+            &code[..6],
+            &code[..4],
+            &format!("{}AA", code),
+            &format!("{}AAAA", code),
+        ] {
+            assert!(invalid(code), "{:?}", code);
+        }
+
+        // Nonzero padding bits of the last byte.
+        let mut bytes = decode(&code).unwrap();
+        bytes[5] |= 1;
+        assert!(invalid(&encode(&bytes)));
+        // The 11 bytes of range 8 end in a short chunk of three characters, whose last two bits are padding.
+        let code = random_board(8, 0).to_code();
+        assert_eq!(code.len(), 15);
+        let last = ALPHABET
+            .iter()
+            .position(|&c| c == code.as_bytes()[14])
+            .unwrap();
+        let mut extra_bits = code.clone();
+        extra_bits.replace_range(14..15, &(ALPHABET[last | 1] as char).to_string());
+        assert!(invalid(&extra_bits), "{}", extra_bits);
+
+        // Ranges which are not of boards.
+        for range in [0u16, 4, 7, 1000] {
+            let mut bytes = range.to_be_bytes().to_vec();
+            bytes.resize(
+                2 + (range as usize * (range as usize + 1) / 2).div_ceil(4),
+                0,
+            );
+            assert!(invalid(&encode(&bytes)), "range {}", range);
+        }
+    }
+}
@@ -3,28 +3,17 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use crate::board::{Player, PLAYERS};
-use std::collections::HashMap;
+use crate::board::{Board, PerPlayer, Player};
+use std::cmp;
 use std::ops::{Deref, DerefMut};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Count {
-    count: HashMap<Player, u64>,
-}
-
-impl Default for Count {
-    fn default() -> Self {
-        Self {
-            count: PLAYERS
-                .iter()
-                .map(|player| (*player, 0))
-                .collect::<HashMap<_, _>>(),
-        }
-    }
+    count: PerPlayer<u64>,
 }
 
 impl Deref for Count {
-    type Target = HashMap<Player, u64>;
+    type Target = PerPlayer<u64>;
     fn deref(&self) -> &Self::Target {
         &self.count
     }
@@ -37,15 +26,44 @@ impl DerefMut for Count {
 }
 
 impl Count {
-    pub fn reset(&mut self) {
-        for player in PLAYERS.iter() {
-            *self.count.get_mut(player).unwrap() = 0;
+    /// Numbers of stones of each player counted from the cells of `board`.
+    pub fn from_board(board: &Board) -> Self {
+        let mut count = Self::default();
+        for (_, player) in board.iter_occupied() {
+            count.increment(player);
         }
+        count
+    }
+    pub fn reset(&mut self) {
+        self.count = PerPlayer::default();
     }
     pub fn increment(&mut self, player: Player) {
-        *self.count.get_mut(&player).unwrap() += 1;
+        self.count[player] += 1;
     }
     pub fn decrement(&mut self, player: Player) {
-        *self.count.get_mut(&player).unwrap() -= 1;
+        self.count[player] -= 1;
+    }
+    pub fn add(&mut self, player: Player, n: u64) {
+        self.count[player] += n;
+    }
+    /// Sum of the values of all players.
+    pub fn total(&self) -> u64 {
+        self.count.values().sum()
+    }
+    /// Players and their values, ordered from the largest value,
+    /// where players having the same value are in the order of `PLAYERS`.
+    pub fn ranking(&self) -> Vec<(Player, u64)> {
+        let mut ranking = self
+            .count
+            .iter()
+            .map(|(player, value)| (player, *value))
+            .collect::<Vec<_>>();
+        ranking.sort_by_key(|(_, value)| cmp::Reverse(*value));
+        ranking
+    }
+    /// Player having the largest value, or `None` if several players have it.
+    pub fn leader(&self) -> Option<Player> {
+        let ranking = self.ranking();
+        (ranking[0].1 != ranking[1].1).then_some(ranking[0].0)
     }
 }
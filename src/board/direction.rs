@@ -0,0 +1,131 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Six directions to go from a position to its neighbors.
+//!
+//! ```text
+//!   LeftUp Up
+//!       \ |
+//! Left -- o -- Right
+//!         | \
+//!      Down RightDown
+//! ```
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+    LeftUp,
+    RightDown,
+}
+
+pub const DIRECTIONS: &[Direction] = &[
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+    Direction::LeftUp,
+    Direction::RightDown,
+];
+
+impl Direction {
+    /// Whether this direction is `LeftUp` or `RightDown`.
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Direction::LeftUp | Direction::RightDown)
+    }
+
+    /// Position next to `(x, y)` in this direction, or `None` if it is out of the board of `range`.
+    pub fn step(&self, (x, y): (usize, usize), range: usize) -> Option<(usize, usize)> {
+        let (x, y) = match self {
+            Direction::Left => (x.checked_sub(1)?, y),
+            Direction::Right => (x + 1, y),
+            Direction::Up => (x, y.checked_sub(1)?),
+            Direction::Down => (x, y + 1),
+            Direction::LeftUp => (x.checked_sub(1)?, y.checked_sub(1)?),
+            Direction::RightDown => (x + 1, y + 1),
+        };
+        if x <= y && y < range {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps from the corners and the middles of the edges of the board of range 5,
+    /// in the order of `DIRECTIONS`.
+    #[test]
+    fn step_from_corners_and_edges() {
+        let cases = [
+            ((0, 0), [None, None, None, Some((0, 1)), None, Some((1, 1))]),
+            ((0, 4), [None, Some((1, 4)), Some((0, 3)), None, None, None]),
+            ((4, 4), [Some((3, 4)), None, None, None, Some((3, 3)), None]),
+            (
+                (0, 2),
+                [
+                    None,
+                    Some((1, 2)),
+                    Some((0, 1)),
+                    Some((0, 3)),
+                    None,
+                    Some((1, 3)),
+                ],
+            ),
+            (
+                (2, 2),
+                [
+                    Some((1, 2)),
+                    None,
+                    None,
+                    Some((2, 3)),
+                    Some((1, 1)),
+                    Some((3, 3)),
+                ],
+            ),
+            (
+                (2, 4),
+                [
+                    Some((1, 4)),
+                    Some((3, 4)),
+                    Some((2, 3)),
+                    None,
+                    Some((1, 3)),
+                    None,
+                ],
+            ),
+        ];
+        for (position, steps) in cases {
+            for (direction, step) in DIRECTIONS.iter().zip(steps) {
+                assert_eq!(
+                    direction.step(position, 5),
+                    step,
+                    "{:?} from {:?}",
+                    direction,
+                    position
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn step_stays_in_triangle() {
+        let range = 8;
+        for y in 0..range {
+            for x in 0..=y {
+                for direction in DIRECTIONS {
+                    if let Some((next_x, next_y)) = direction.step((x, y), range) {
+                        assert!(next_x <= next_y && next_y < range);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Position of a game in one line, similar to FEN of chess.
+//! Rows from the top are separated by `/`, where empty positions are `.` and stones are the seats,
+//! and the seat to move follows after a space, e.g. `./../.../.12./.312./.2.3.. 1`.
+
+use crate::board::{Board, Player};
+use crate::error::TriversiError;
+
+/// Board and the player to move written in `text`, where the range is the number of rows.
+pub fn parse_fen(text: &str) -> Result<(Board, Player), TriversiError> {
+    let invalid = || TriversiError::InvalidFen(text.to_owned());
+    let (rows, seat) = text.trim().split_once(' ').ok_or_else(invalid)?;
+    let player = seat
+        .trim()
+        .parse()
+        .ok()
+        .and_then(Player::from_seat)
+        .ok_or_else(invalid)?;
+    let rows = rows.split('/').collect::<Vec<_>>();
+    let mut board = Board::try_new(rows.len())?;
+    for (y, row) in rows.iter().enumerate() {
+        if row.chars().count() != y + 1 {
+            return Err(invalid());
+        }
+        for (x, cell) in row.chars().enumerate() {
+            let player = match cell {
+                '.' => None,
+                _ => Some(
+                    cell.to_digit(10)
+                        .and_then(|seat| Player::from_seat(seat as usize))
+                        .ok_or_else(invalid)?,
+                ),
+            };
+            board.set_player((x, y), player);
+        }
+    }
+    Ok((board, player))
+}
+
+/// Inverse of `parse_fen`.
+pub fn to_fen(board: &Board, player: Player) -> String {
+    let rows = board
+        .board()
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Some(player) => char::from_digit(player.seat() as u32, 10).unwrap(),
+                    None => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    format!("{} {}", rows.join("/"), player)
+}
@@ -0,0 +1,42 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use super::{Player, PLAYERS};
+use crate::rng::{self, Component};
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
+use serde_derive::{Deserialize, Serialize};
+
+/// Rule to choose the player who moves first.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+pub enum FirstPlayer {
+    #[default]
+    #[value(name = "1")]
+    #[serde(rename = "1")]
+    Seat1,
+    #[value(name = "2")]
+    #[serde(rename = "2")]
+    Seat2,
+    #[value(name = "3")]
+    #[serde(rename = "3")]
+    Seat3,
+    /// A seat chosen from `seed`.
+    #[value(name = "random")]
+    #[serde(rename = "random")]
+    Random,
+}
+
+impl FirstPlayer {
+    pub fn player(self, seed: u64) -> Player {
+        match self {
+            FirstPlayer::Seat1 => PLAYERS[0],
+            FirstPlayer::Seat2 => PLAYERS[1],
+            FirstPlayer::Seat3 => PLAYERS[2],
+            FirstPlayer::Random => *PLAYERS
+                .choose(&mut rng::child(seed, Component::Seating, 0))
+                .unwrap(),
+        }
+    }
+}
@@ -3,67 +3,474 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use crate::board::{Board, Player};
+use crate::board::{Board, Count, Move, PerPlayer, Player, Variant, SWAP_TURN};
+use crate::error::TriversiError;
+use crate::util::validate_position;
 use getset::{CopyGetters, Getters};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+/// Name of the first branch of the history.
+const MAIN_BRANCH_NAME: &str = "Main";
+
+/// Version of Triversi written in records.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Moves of a game with the metadata to reproduce it,
+/// where fields added after the first version are defaulted in older records.
+#[derive(Clone, Debug, CopyGetters, Getters, Serialize, Deserialize)]
 pub struct Record {
+    /// Version of Triversi which played the game, which is empty in older records.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    version: String,
+    /// Start of the game in seconds since the Unix epoch.
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    timestamp: u64,
+    #[getset(get_copy = "pub")]
     range: usize,
+    /// Variant of the rules, which is `None` in older records.
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    variant: Option<Variant>,
+    /// Seed of randomized components in the game.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    seed: Option<u64>,
+    /// Numbers of extra starting stones indexed by `Player::index`.
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    handicap: [usize; 3],
+    /// Player who moves first in the game.
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    first_player: Player,
+    /// Whether the second player took over the seat of the first in the swap variant.
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    swapped: bool,
+    /// Names of the players in the order of seats.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    player_names: Vec<String>,
+    /// Marks of the players in the order of seats.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    player_marks: Vec<String>,
     #[getset(get = "pub")]
     player_positions: Vec<(Player, (usize, usize))>,
+    /// Times of the moves of `player_positions` in seconds since the Unix epoch.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    move_times: Vec<u64>,
+    /// Times the players spent on the moves of `player_positions` in milliseconds,
+    /// which are paused while the history is browsed or a question is asked.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    think_times: Vec<u64>,
 }
 
 #[derive(Clone, Debug, CopyGetters, Getters)]
 pub struct History {
     #[getset(get_copy = "pub")]
     current_turn: usize,
+    /// Lines of moves sharing the initial board, which are all but the first created from another one.
+    #[getset(get = "pub")]
+    branches: Vec<Branch>,
+    /// Index of the branch being played or browsed.
+    i_branch: usize,
+    /// Whether selecting a position at an earlier turn keeps the following moves as a branch instead of discarding them.
+    keeps_branches: bool,
+}
+
+/// Line of moves in the history.
+#[derive(Clone, Debug, CopyGetters, Getters)]
+pub struct Branch {
+    #[getset(get_copy = "pub")]
+    id: usize,
+    #[getset(get = "pub")]
+    name: String,
+    /// Turn from which the moves differ from the branch where it is created.
+    #[getset(get_copy = "pub")]
+    fork_turn: usize,
     #[getset(get = "pub")]
     record: Record,
     boards: Vec<Board>,
+    flips: Vec<u64>,
+}
+
+impl Branch {
+    /// Number of moves of the branch.
+    pub fn len(&self) -> usize {
+        self.record.player_positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.record.player_positions.is_empty()
+    }
+
+    /// Drops moves after `turn`, where the swap of seats is dropped too if it is not made yet at `turn`.
+    fn truncate(&mut self, turn: usize) {
+        if turn <= SWAP_TURN {
+            self.record.swapped = false;
+        }
+        self.boards.truncate(turn + 1);
+        self.record.truncate(turn);
+        self.flips.truncate(turn);
+    }
+}
+
+/// Statistics of a move.
+#[derive(Clone, Copy, Debug, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct MoveStats {
+    /// Turn after the move, which starts from 1.
+    turn: usize,
+    player: Player,
+    position: (usize, usize),
+    /// Time the player spent before the move.
+    think_time: Duration,
+    flips: u64,
+    /// Number of stones of the player after the move.
+    count: u64,
 }
 
 impl Record {
-    pub fn new(range: usize) -> Self {
+    pub fn new(board: &Board, first_player: Player) -> Self {
         Self {
-            range,
+            version: VERSION.to_owned(),
+            timestamp: unix_time(),
+            range: board.range(),
+            variant: None,
+            seed: None,
+            handicap: board.handicap(),
+            first_player,
+            swapped: false,
+            player_names: Vec::new(),
+            player_marks: Vec::new(),
             player_positions: Vec::new(),
+            move_times: Vec::new(),
+            think_times: Vec::new(),
         }
     }
 
-    pub fn init(&mut self) {
+    /// Starts recording a game on `board`, whose range and handicap may differ from the previous game.
+    pub fn init(&mut self, board: &Board, first_player: Player) {
+        self.timestamp = unix_time();
+        self.range = board.range();
+        self.handicap = board.handicap();
+        self.first_player = first_player;
+        self.swapped = false;
         self.player_positions.clear();
+        self.move_times.clear();
+        self.think_times.clear();
     }
 
-    fn push(&mut self, player_positions: (Player, (usize, usize))) {
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = Some(variant);
+    }
+
+    /// Records names and marks of the players in the order of seats.
+    pub fn set_players(&mut self, player_names: Vec<String>, player_marks: Vec<String>) {
+        self.player_names = player_names;
+        self.player_marks = player_marks;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    pub fn set_swapped(&mut self) {
+        self.swapped = true;
+    }
+
+    /// Moves including skipped turns, which are not recorded but inferred from the order of players.
+    pub fn moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let mut expected_player = self.first_player;
+        for (player, position) in &self.player_positions {
+            while expected_player != *player {
+                moves.push(Move::Pass);
+                expected_player.advance();
+            }
+            moves.push(Move::Put(*position));
+            expected_player.advance();
+        }
+        moves
+    }
+
+    fn player_name(&self, player: Player) -> String {
+        self.player_names
+            .get(player.index())
+            .cloned()
+            .unwrap_or_else(|| player.to_string())
+    }
+
+    /// Checks a record read from a file, which must be written by this or an older version,
+    /// whose range must be valid, and whose positions must be on the board and selected at most once,
+    /// where the legality of each move is checked by replaying it.
+    /// Turns in the errors are the numbers of the recorded positions from 1 as turns of game events,
+    /// which exclude skipped turns.
+    pub fn validate(&self) -> Result<(), TriversiError> {
+        validate_version(&self.version)?;
+        Board::validate_range(self.range)?;
+        for players in [&self.player_names, &self.player_marks] {
+            if !players.is_empty() && players.len() != 3 {
+                return Err(TriversiError::InvalidRecordPlayers(players.len()));
+            }
+        }
+        if self.swapped && self.variant == Some(Variant::Standard) {
+            return Err(TriversiError::RecordParse {
+                turn: SWAP_TURN + 1,
+                reason: "seats are swapped in the standard variant".to_owned(),
+            });
+        }
+        for (times, kind) in [
+            (&self.move_times, "times"),
+            (&self.think_times, "thinking times"),
+        ] {
+            if !times.is_empty() && times.len() != self.player_positions.len() {
+                return Err(TriversiError::RecordParse {
+                    turn: times.len().min(self.player_positions.len()) + 1,
+                    reason: format!(
+                        "{} moves have {} {}",
+                        self.player_positions.len(),
+                        times.len(),
+                        kind
+                    ),
+                });
+            }
+        }
+        let mut selected = HashSet::new();
+        for (i_position, (_, (x, y))) in self.player_positions.iter().enumerate() {
+            let invalid = |reason: String| TriversiError::RecordParse {
+                turn: i_position + 1,
+                reason,
+            };
+            if validate_position((*x, *y), self.range).is_err() {
+                return Err(invalid(format!(
+                    "({}, {}) is outside the board of range {}",
+                    x, y, self.range
+                )));
+            }
+            if !selected.insert((*x, *y)) {
+                return Err(invalid(format!("({}, {}) is selected again", x, y)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replays the moves on `board`, the initial board of the game with its rules,
+    /// and returns the board after them or the first move which cannot be played.
+    /// A move is illegal if its player cannot select the position,
+    /// and a pass inferred between it and the previous move is invalid if the skipped player could select a position.
+    /// Turns in the errors are the numbers of the recorded positions from 1 as turns of game events,
+    /// where a skipped turn has the turn of the move before it as a skip event.
+    pub fn replay(&self, mut board: Board) -> Result<Board, TriversiError> {
+        let mut expected_player = self.first_player;
+        for (i_position, (player, position)) in self.player_positions.iter().enumerate() {
+            while expected_player != *player {
+                if !board.selectable_positions(expected_player).is_empty() {
+                    return Err(TriversiError::RecordParse {
+                        turn: i_position,
+                        reason: format!(
+                            "Player-{} passed but could select a position",
+                            self.player_name(expected_player)
+                        ),
+                    });
+                }
+                expected_player.advance();
+            }
+            if !board.is_legal_move(*player, *position) {
+                return Err(TriversiError::IllegalMoveInReplay {
+                    turn: i_position + 1,
+                    player: self.player_name(*player),
+                    position: *position,
+                });
+            }
+            board.apply_move(*player, *position);
+            expected_player.advance();
+        }
+        Ok(board)
+    }
+
+    fn push(&mut self, player_positions: (Player, (usize, usize)), think_time: Duration) {
         self.player_positions.push(player_positions);
+        self.move_times.push(unix_time());
+        self.think_times.push(think_time.as_millis() as u64);
+    }
+
+    fn truncate(&mut self, turn: usize) {
+        self.player_positions.truncate(turn);
+        self.move_times.truncate(turn);
+        self.think_times.truncate(turn);
     }
+
+    /// Time the player spent on the move at `i_move`, which is zero if it is not recorded.
+    pub fn think_time(&self, i_move: usize) -> Duration {
+        Duration::from_millis(self.think_times.get(i_move).copied().unwrap_or_default())
+    }
+}
+
+/// Checks that a game recorded by `version` of Triversi can be read by this version,
+/// which reads records of the same or older minor versions and records without a version.
+pub fn validate_version(version: &str) -> Result<(), TriversiError> {
+    if version.is_empty() {
+        return Ok(());
+    }
+    let minor_version = |version: &str| {
+        let mut numbers = version.split('.').map(|number| number.parse::<u64>().ok());
+        Some((numbers.next()??, numbers.next()??))
+    };
+    match minor_version(version) {
+        Some(recorded) if recorded <= minor_version(VERSION).unwrap() => Ok(()),
+        _ => Err(TriversiError::IncompatibleVersion(
+            version.to_owned(),
+            VERSION.to_owned(),
+        )),
+    }
+}
+
+/// Now in seconds since the Unix epoch.
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
 }
 
 impl History {
-    pub fn new(board: Board) -> Self {
+    /// Starts from `board` where `first_player` moves first.
+    pub fn new(board: Board, first_player: Player) -> Self {
         Self {
             current_turn: 0,
-            record: Record::new(board.range()),
-            boards: vec![board],
+            branches: vec![Branch {
+                id: 0,
+                name: MAIN_BRANCH_NAME.to_owned(),
+                fork_turn: 0,
+                record: Record::new(&board, first_player),
+                boards: vec![board],
+                flips: Vec::new(),
+            }],
+            i_branch: 0,
+            keeps_branches: false,
+        }
+    }
+
+    /// Records the seed of randomized components, which is kept by `init`.
+    pub fn set_seed(&mut self, seed: u64) {
+        for branch in &mut self.branches {
+            branch.record.set_seed(seed);
         }
     }
 
-    pub fn init(&mut self, board: Board) {
+    /// Records the variant and the names and marks of the players, which are kept by `init`.
+    pub fn set_metadata(
+        &mut self,
+        variant: Variant,
+        player_names: Vec<String>,
+        player_marks: Vec<String>,
+    ) {
+        for branch in &mut self.branches {
+            branch.record.set_variant(variant);
+            branch
+                .record
+                .set_players(player_names.clone(), player_marks.clone());
+        }
+    }
+
+    /// Records that the second player took over the seat of the first in the current branch.
+    pub fn set_swapped(&mut self) {
+        self.branch_mut().record.set_swapped();
+    }
+
+    /// Keeps moves after the current turn as a branch when a move is pushed there.
+    pub fn set_keeps_branches(&mut self, keeps_branches: bool) {
+        self.keeps_branches = keeps_branches;
+    }
+
+    /// Starts from `board` where `first_player` moves first, with only the main branch.
+    pub fn init(&mut self, board: Board, first_player: Player) {
         self.current_turn = 0;
-        self.record.init();
-        self.boards.clear();
-        self.boards.push(board);
+        self.branches.truncate(1);
+        self.i_branch = 0;
+        let branch = &mut self.branches[0];
+        branch.record.init(&board, first_player);
+        branch.boards.clear();
+        branch.boards.push(board);
+        branch.flips.clear();
+    }
+
+    /// Record of the current branch.
+    pub fn record(&self) -> &Record {
+        &self.branch().record
+    }
+
+    pub fn branch(&self) -> &Branch {
+        &self.branches[self.i_branch]
     }
 
-    pub fn push(&mut self, player_position: (Player, (usize, usize)), board: Board) {
-        if self.current_turn < self.boards.len() - 1 {
-            self.boards.drain(self.current_turn + 1..);
-            self.record.player_positions.drain(self.current_turn..);
+    fn branch_mut(&mut self) -> &mut Branch {
+        &mut self.branches[self.i_branch]
+    }
+
+    /// Makes the branch of `id` current, where the current turn is kept within the branch.
+    /// It returns false if there is no such branch.
+    pub fn switch_branch(&mut self, id: usize) -> bool {
+        let Some(i_branch) = self.branches.iter().position(|branch| branch.id == id) else {
+            return false;
+        };
+        self.i_branch = i_branch;
+        self.current_turn = self.current_turn.min(self.branch().len());
+        true
+    }
+
+    /// Pushes a move, where `flips` is the number of stones flipped by it
+    /// and `think_time` is the time the player spent before it.
+    /// If the move is the next recorded one, it is redone and the following moves are kept.
+    /// Otherwise moves after the current turn are discarded, or kept in the current branch
+    /// while the move is pushed to a new branch if branches are kept.
+    pub fn push(
+        &mut self,
+        player_position: (Player, (usize, usize)),
+        flips: u64,
+        think_time: Duration,
+        board: Board,
+    ) {
+        let current_turn = self.current_turn;
+        if self.record().player_positions.get(current_turn) == Some(&player_position) {
+            self.current_turn += 1;
+            return;
+        }
+        if current_turn < self.branch().len() {
+            if self.keeps_branches {
+                let id = self.branches.iter().map(|branch| branch.id).max().unwrap() + 1;
+                let mut branch = self.branch().clone();
+                branch.id = id;
+                branch.name = format!("Branch {}", id);
+                branch.fork_turn = current_turn;
+                self.branches.push(branch);
+                self.i_branch = self.branches.len() - 1;
+            }
+            self.branch_mut().truncate(current_turn);
         }
         self.current_turn += 1;
-        self.record.push(player_position);
-        self.boards.push(board);
+        let branch = self.branch_mut();
+        branch.record.push(player_position, think_time);
+        branch.boards.push(board);
+        branch.flips.push(flips);
+    }
+
+    /// Whether a move before the current turn can be taken back.
+    pub fn can_undo(&self) -> bool {
+        self.current_turn != 0
+    }
+
+    /// Whether a move after the current turn is kept to be played again.
+    pub fn can_redo(&self) -> bool {
+        self.current_turn != self.branch().len()
     }
 
     pub fn go_prev(&mut self) {
@@ -73,26 +480,214 @@ impl History {
     }
 
     pub fn go_next(&mut self) {
-        if self.current_turn != self.boards.len() - 1 {
+        if self.current_turn != self.branch().len() {
             self.current_turn += 1;
         }
     }
 
+    /// Moves to `turn`, or returns false if the current branch does not reach it.
+    pub fn go_to(&mut self, turn: usize) -> bool {
+        if turn > self.branch().len() {
+            return false;
+        }
+        self.current_turn = turn;
+        true
+    }
+
     pub fn past_position(&self) -> Option<(usize, usize)> {
-        self.record
+        self.record()
             .player_positions
             .get(self.current_turn)
             .map(|player_position| player_position.1)
     }
 
     pub fn past_player(&self) -> Option<Player> {
-        self.record
+        self.record()
             .player_positions
             .get(self.current_turn)
             .map(|player_position| player_position.0)
     }
 
+    /// Number of skipped turns of each player until the move at the current turn,
+    /// which are inferred from the order of players.
+    pub fn skips(&self) -> Count {
+        let mut skips = Count::default();
+        let record = self.record();
+        let mut expected_player = record.first_player;
+        for (player, _) in record.player_positions.iter().take(self.current_turn + 1) {
+            while expected_player != *player {
+                skips.increment(expected_player);
+                expected_player.advance();
+            }
+            expected_player.advance();
+        }
+        skips
+    }
+
+    /// Total number of stones flipped by each player until the current turn.
+    pub fn flip_totals(&self) -> Count {
+        let mut flip_totals = Count::default();
+        let branch = self.branch();
+        for ((player, _), flips) in branch
+            .record
+            .player_positions
+            .iter()
+            .zip(branch.flips.iter())
+            .take(self.current_turn)
+        {
+            flip_totals.add(*player, *flips);
+        }
+        flip_totals
+    }
+
+    /// Statistics of moves until the current turn.
+    pub fn move_stats(&self) -> Vec<MoveStats> {
+        let branch = self.branch();
+        branch
+            .record
+            .player_positions
+            .iter()
+            .zip(branch.flips.iter())
+            .zip(branch.boards.iter().skip(1))
+            .take(self.current_turn)
+            .enumerate()
+            .map(|(i_move, (((player, position), flips), board))| MoveStats {
+                turn: i_move + 1,
+                player: *player,
+                position: *position,
+                think_time: branch.record.think_time(i_move),
+                flips: *flips,
+                count: board.count()[*player],
+            })
+            .collect()
+    }
+
+    /// Total time each player spent on the moves until the current turn.
+    pub fn think_totals(&self) -> PerPlayer<Duration> {
+        let record = self.record();
+        let mut think_totals = PerPlayer::<Duration>::default();
+        for (i_move, (player, _)) in record
+            .player_positions
+            .iter()
+            .enumerate()
+            .take(self.current_turn)
+        {
+            think_totals[*player] += record.think_time(i_move);
+        }
+        think_totals
+    }
+
+    /// Replaces the times spent on the replayed moves by `think_times` read from a record,
+    /// where moves beyond them keep the times measured while replaying.
+    pub fn restore_think_times(&mut self, think_times: &[u64]) {
+        let record = &mut self.branch_mut().record;
+        for (think_time, restored) in record.think_times.iter_mut().zip(think_times) {
+            *think_time = *restored;
+        }
+    }
+
+    /// Board before the move leading to the current turn with the position of the move,
+    /// or `None` at the first turn.
+    pub fn previous_board(&self) -> Option<(&Board, (usize, usize))> {
+        let i_move = self.current_turn.checked_sub(1)?;
+        let branch = self.branch();
+        Some((
+            &branch.boards[i_move],
+            branch.record.player_positions[i_move].1,
+        ))
+    }
+
+    /// Recorded moves, each with the board before it.
+    pub fn moves_with_boards(&self) -> Vec<(Board, Player, (usize, usize))> {
+        let branch = self.branch();
+        branch
+            .boards
+            .iter()
+            .zip(branch.record.player_positions.iter())
+            .map(|(board, (player, position))| (board.clone(), *player, *position))
+            .collect()
+    }
+
     pub fn board(&self) -> &Board {
-        self.boards.get(self.current_turn).unwrap()
+        self.branch().boards.get(self.current_turn).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Record on a board of range 5 of `player_positions` written as JSON.
+    fn record(player_positions: &str) -> Record {
+        serde_json::from_str(&format!(
+            r#"{{"range": 5, "player_positions": {}}}"#,
+            player_positions
+        ))
+        .unwrap()
+    }
+
+    /// Turn of the error of a record.
+    fn turn(result: Result<impl std::fmt::Debug, TriversiError>) -> usize {
+        match result {
+            Err(TriversiError::RecordParse { turn, .. })
+            | Err(TriversiError::IllegalMoveInReplay { turn, .. }) => turn,
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn skipped_turns_are_moves() {
+        // Seat 3 cannot select any position after the second move.
+        let record = record(r#"[["Zero", [0, 4]], ["One", [0, 0]], ["Zero", [4, 4]]]"#);
+        assert_eq!(
+            record.moves(),
+            [
+                Move::Put((0, 4)),
+                Move::Put((0, 0)),
+                Move::Pass,
+                Move::Put((4, 4))
+            ]
+        );
+        record.validate().unwrap();
+        let board = record.replay(Board::try_new(5).unwrap()).unwrap();
+        assert_eq!(board.count().into_array(), [8, 5, 2]);
+    }
+
+    #[test]
+    fn errors_number_turns_without_skipped_turns() {
+        let outside = record(r#"[["Zero", [0, 4]], ["One", [0, 0]], ["Zero", [5, 5]]]"#);
+        assert_eq!(turn(outside.validate()), 3);
+        assert_eq!(
+            outside.validate().unwrap_err().to_string(),
+            "Turn 3 of the record is invalid: (5, 5) is outside the board of range 5."
+        );
+        let again = record(r#"[["Zero", [0, 4]], ["One", [0, 0]], ["Zero", [0, 0]]]"#);
+        assert_eq!(turn(again.validate()), 3);
+
+        let occupied = record(r#"[["Zero", [0, 4]], ["One", [0, 0]], ["Zero", [1, 2]]]"#);
+        occupied.validate().unwrap();
+        assert_eq!(turn(occupied.replay(Board::try_new(5).unwrap())), 3);
+        assert_eq!(
+            occupied
+                .replay(Board::try_new(5).unwrap())
+                .unwrap_err()
+                .to_string(),
+            "Turn 3 of the record, (1, 2) of Player-1, is an illegal move on the replayed board."
+        );
+        // Seat 2 could select a position, so it must not be skipped after the first move.
+        let skipped = record(r#"[["Zero", [0, 4]], ["Two", [0, 0]]]"#);
+        assert_eq!(
+            skipped
+                .replay(Board::try_new(5).unwrap())
+                .unwrap_err()
+                .to_string(),
+            "Turn 1 of the record is invalid: Player-2 passed but could select a position."
+        );
+
+        let mut timed = record(r#"[["Zero", [0, 4]], ["One", [0, 0]], ["Zero", [4, 4]]]"#);
+        timed.move_times = vec![0; 2];
+        assert_eq!(turn(timed.validate()), 3);
+        timed.move_times = vec![0; 4];
+        assert_eq!(turn(timed.validate()), 4);
     }
 }
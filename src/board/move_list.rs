@@ -0,0 +1,57 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::error::TriversiError;
+use crate::util::parse_position;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Move of a player in a move list.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Move {
+    /// Places a stone at the position.
+    Put((usize, usize)),
+    /// Acknowledges a skipped turn.
+    Pass,
+}
+
+impl Move {
+    /// Parses `x,y` on the board of `range` or `pass`, where spaces around the values are ignored.
+    pub fn parse(s: &str, range: usize) -> Result<Self, TriversiError> {
+        let s = s.trim();
+        if s == "pass" {
+            return Ok(Move::Pass);
+        }
+        if !s.contains(',') {
+            return Err(TriversiError::InvalidMove(s.to_owned()));
+        }
+        Ok(Move::Put(parse_position(s, range)?))
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Put((x, y)) => write!(f, "{},{}", x, y),
+            Move::Pass => write!(f, "pass"),
+        }
+    }
+}
+
+/// Moves on the board of `range` written one per line, each with its line number starting from 1.
+/// Blank lines and lines starting with `#` are ignored.
+pub fn parse_move_list(text: &str, range: usize) -> Result<Vec<(usize, Move)>, TriversiError> {
+    text.lines()
+        .enumerate()
+        .map(|(i_line, line)| (i_line + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            Move::parse(line, range)
+                .map(|player_move| (line_number, player_move))
+                .map_err(|error| TriversiError::InvalidMoveLine(line_number, Box::new(error)))
+        })
+        .collect()
+}
@@ -0,0 +1,24 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::Player;
+use getset::{CopyGetters, Getters};
+
+/// Changes made by `Board::apply_move`, which are reverted by `Board::undo_move`.
+#[derive(Clone, Debug, CopyGetters, Getters)]
+pub struct MoveUndo {
+    /// Position where the stone is placed.
+    #[getset(get_copy = "pub")]
+    position: (usize, usize),
+    /// Flipped positions and their players before the move.
+    #[getset(get = "pub")]
+    flipped: Vec<((usize, usize), Player)>,
+}
+
+impl MoveUndo {
+    pub(crate) fn new(position: (usize, usize), flipped: Vec<((usize, usize), Player)>) -> Self {
+        Self { position, flipped }
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::{Player, PLAYERS};
+use serde_derive::{Deserialize, Serialize};
+use std::ops::{Index, IndexMut};
+
+/// Value of each player indexed by `Player`, iterated in the order of `PLAYERS`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PerPlayer<T>([T; 3]);
+
+impl<T> PerPlayer<T> {
+    pub fn new(values: [T; 3]) -> Self {
+        Self(values)
+    }
+
+    /// Values given by `f` for each player.
+    pub fn from_fn(mut f: impl FnMut(Player) -> T) -> Self {
+        Self([f(PLAYERS[0]), f(PLAYERS[1]), f(PLAYERS[2])])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Player, &T)> {
+        PLAYERS.iter().copied().zip(self.0.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Player, &mut T)> {
+        PLAYERS.iter().copied().zip(self.0.iter_mut())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.iter_mut()
+    }
+
+    pub fn into_array(self) -> [T; 3] {
+        self.0
+    }
+}
+
+impl<T> Index<Player> for PerPlayer<T> {
+    type Output = T;
+    fn index(&self, player: Player) -> &Self::Output {
+        &self.0[player.index()]
+    }
+}
+
+impl<T> IndexMut<Player> for PerPlayer<T> {
+    fn index_mut(&mut self, player: Player) -> &mut Self::Output {
+        &mut self.0[player.index()]
+    }
+}
@@ -4,6 +4,7 @@
 // see https://opensource.org/licenses/mit-license.php
 
 use serde_derive::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Player {
@@ -16,11 +17,43 @@ pub enum Player {
 pub const PLAYERS: &[Player] = &[Player::Zero, Player::One, Player::Two];
 
 impl Player {
-    pub fn advance(&mut self) {
+    /// Position in `PLAYERS`.
+    pub fn index(&self) -> usize {
         match self {
-            Player::Zero => *self = Player::One,
-            Player::One => *self = Player::Two,
-            Player::Two => *self = Player::Zero,
+            Player::Zero => 0,
+            Player::One => 1,
+            Player::Two => 2,
         }
     }
+
+    /// Inverse of `index`.
+    pub fn from_index(index: usize) -> Option<Self> {
+        PLAYERS.get(index).copied()
+    }
+
+    /// Player of the seat `seat` (1, 2, or 3).
+    pub fn from_seat(seat: usize) -> Option<Self> {
+        seat.checked_sub(1).and_then(Self::from_index)
+    }
+
+    /// Seat (1, 2, or 3) of the player.
+    pub fn seat(&self) -> usize {
+        self.index() + 1
+    }
+
+    /// Number of turns after the turn of `first_player` in a round.
+    pub fn turn_order(&self, first_player: Player) -> usize {
+        (self.index() + PLAYERS.len() - first_player.index()) % PLAYERS.len()
+    }
+
+    pub fn advance(&mut self) {
+        *self = Self::from_index((self.index() + 1) % PLAYERS.len()).unwrap();
+    }
+}
+
+/// Seat (1, 2, or 3) of the player, which is shown in dumps instead of the variant name.
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.seat())
+    }
 }
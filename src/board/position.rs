@@ -0,0 +1,45 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use std::cmp::Ordering;
+
+/// Position `(x, y)` ordered row-major, i.e., by `y` and then by `x`,
+/// which is the order of `Board::selectable_positions`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Position(pub (usize, usize));
+
+impl Position {
+    pub fn x(&self) -> usize {
+        self.0 .0
+    }
+
+    pub fn y(&self) -> usize {
+        self.0 .1
+    }
+}
+
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y(), self.x()).cmp(&(other.y(), other.x()))
+    }
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    fn from(position: (usize, usize)) -> Self {
+        Self(position)
+    }
+}
+
+impl From<Position> for (usize, usize) {
+    fn from(position: Position) -> Self {
+        position.0
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+
+/// What the score of a player counts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scoring {
+    /// Stones on the board.
+    #[default]
+    Stones,
+    /// Stones and empty regions bordered only by stones of the player.
+    Territory,
+}
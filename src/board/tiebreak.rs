@@ -0,0 +1,22 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+
+/// Rule to order players having the same number of stones.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Tiebreak {
+    /// Ties are not resolved.
+    #[default]
+    None,
+    /// More stones on the three vertices of the board.
+    Corners,
+    /// Earlier in the turn order.
+    FirstMove,
+    /// Fewer skipped turns.
+    FewestSkips,
+}
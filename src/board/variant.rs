@@ -0,0 +1,21 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+
+/// Number of moves after which the second player may swap seats with the first in the swap variant.
+pub const SWAP_TURN: usize = 1;
+
+/// Variant of the rules.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Variant {
+    /// Plain rules.
+    #[default]
+    Standard,
+    /// Pie rule, where the second player may take over the seat of the first after its first move.
+    Swap,
+}
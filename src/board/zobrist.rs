@@ -0,0 +1,55 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Zobrist hashing of positions, where the hash is the XOR of the keys of all stones.
+
+use crate::board::{Board, Player};
+use crate::rng::{self, Component};
+use rand::Rng;
+use std::fmt;
+
+/// Seed of the keys, which is fixed so that the same position has the same hash in every run.
+const SEED: u64 = 0;
+
+/// Random key of each player in each position of a board.
+#[derive(Clone)]
+pub struct Zobrist {
+    keys: Vec<[u64; 3]>,
+}
+
+impl Zobrist {
+    pub fn new(range: usize) -> Self {
+        let mut rng = rng::child(SEED, Component::Zobrist, range as u64);
+        Self {
+            keys: (0..range * (range + 1) / 2)
+                .map(|_| [rng.gen(), rng.gen(), rng.gen()])
+                .collect(),
+        }
+    }
+
+    /// Key of a stone of `player` in a position.
+    pub fn key(&self, (x, y): (usize, usize), player: Player) -> u64 {
+        self.keys[y * (y + 1) / 2 + x][player.index()]
+    }
+
+    /// Hash of `board` computed from all of its stones.
+    pub fn hash(&self, board: &Board) -> u64 {
+        let mut hash = 0;
+        for y in 0..board.range() {
+            for x in 0..=y {
+                if let Some(player) = board.player((x, y)) {
+                    hash ^= self.key((x, y), player);
+                }
+            }
+        }
+        hash
+    }
+}
+
+impl fmt::Debug for Zobrist {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Zobrist {{ positions: {} }}", self.keys.len())
+    }
+}
@@ -3,49 +3,1374 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-use crate::app::board_display::{BoardDisplay, ParagraphBoard};
+use crate::ai::{Evaluation, MaxN, OpeningBook, SearchConfig, StrategyKind, Weights};
+use crate::analysis;
+use crate::app::board_display::paragraph_board::{FrameStyle, FrameStyleKind};
+use crate::app::board_display::{BoardDisplay, CompactBoard, ParagraphBoard, UnicodeBoard};
+use crate::app::key_binding::KeyBinding;
+use crate::app::match_state::{MatchScoring, MatchState};
+use crate::app::records::Records;
+use crate::app::saves::SavedGame;
 use crate::app::system::System;
 use crate::app::tui::Tui;
-use crate::board::Board;
-use anyhow::Result;
-use clap::Parser;
+use crate::app::{ColorConfig, Messages, Notification, SeatConfig, Settings, Theme};
+use crate::board::history::Record;
+use crate::board::{
+    parse_archive, parse_fen, parse_move_list, Board, CaptureRules, FirstPlayer, Move, Player,
+    Scoring, Tiebreak, Variant,
+};
+use crate::config::Config;
+use crate::engine::Engine;
+use crate::error::TriversiError;
+use crate::game::Game;
+use crate::net::{Client, GameRules, Host, Network};
+use crate::puzzle::Puzzle;
+use crate::rng;
+use crate::simulation::{self, Contestant, Ratings};
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 impl Cli {
     pub fn run() -> Result<()> {
         let arg = Cli::parse();
-        let paragraph_board = ParagraphBoard::try_new(arg.distance, &arg.player_names)?;
-        let board = Board::try_new(arg.range)?;
-        let mut system = System::try_new(board, paragraph_board)?;
+        if let Some(Command::Config {
+            command: ConfigCommand::Init { force },
+        }) = arg.command
+        {
+            let path = arg
+                .config
+                .or_else(Config::default_path)
+                .context("cannot determine the path of the configuration file")?;
+            Config::write_template(&path, force)?;
+            println!("Configuration file is written to {}.", path.display());
+            return Ok(());
+        }
+        if let Some(Command::Saves) = arg.command {
+            let dir =
+                SavedGame::default_dir().context("cannot determine the directory of the slots")?;
+            let saved_games = SavedGame::list(&dir)?;
+            if saved_games.is_empty() {
+                println!("No game is saved in {}.", dir.display());
+                return Ok(());
+            }
+            let name_width = saved_games
+                .iter()
+                .map(|(name, _)| name.len())
+                .max()
+                .unwrap_or_default()
+                .max("Name".len());
+            println!(
+                "{:<name_width$} {:<10} {:>5} {:>5}",
+                "Name", "Date", "Range", "Turns"
+            );
+            for (name, saved_game) in saved_games {
+                println!(
+                    "{:<name_width$} {:<10} {:>5} {:>5}",
+                    name,
+                    saved_game.date(),
+                    saved_game.record().range(),
+                    saved_game.record().player_positions().len()
+                );
+            }
+            return Ok(());
+        }
+        let saved_game = match &arg.load_slot {
+            Some(name) => {
+                if arg.command.is_some() {
+                    bail!("--load-slot can be given only to a local game.");
+                }
+                let dir = SavedGame::default_dir()
+                    .context("cannot determine the directory of the slots")?;
+                Some(SavedGame::load(&dir, name)?)
+            }
+            None => None,
+        };
+        let mut config = layered_config(
+            Config::load(arg.config.as_deref())?,
+            saved_game
+                .as_ref()
+                .map(|saved_game| saved_game.config().clone()),
+            arg.to_config(),
+        );
+        // Moves to view in the history are loaded in the same way as those to continue.
+        let moves_path = arg.moves.as_ref().or(arg.view.as_ref());
+        let mut joined = None;
+        // Player who moved first in the game being joined or replayed.
+        let mut recorded_first_player = None;
+        let mut swapped = false;
+        let mut think_times = Vec::new();
+        let mut loaded_record = None;
+        match &arg.command {
+            Some(Command::Host { .. }) if config.seats.is_none() && config.seat.is_none() => {
+                config.seats = Some(DEFAULT_HOST_SEATS.to_owned());
+            }
+            Some(Command::Join { address, seat }) => {
+                if moves_path.is_some() {
+                    bail!(
+                        "--moves and --view cannot be given to a client, which receives moves from the host."
+                    );
+                }
+                // Rules of the game are decided by the host.
+                let (client, rules, moves) = Client::connect(address, *seat)?;
+                config.range = Some(rules.range);
+                config.no_diagonals = Some(rules.no_diagonals);
+                config.auto_skip = Some(rules.auto_skip);
+                config.tiebreak = Some(rules.tiebreak);
+                config.scoring = Some(rules.scoring);
+                config.handicap = Some(format!("{},{}", rules.handicap[1], rules.handicap[2]));
+                recorded_first_player = Some(rules.first_player);
+                joined = Some((client, moves));
+            }
+            _ => (),
+        }
+        let loaded = match (moves_path, &saved_game) {
+            (Some(path), _) => Some(Self::read_moves(path, config.range.unwrap())?),
+            (None, Some(saved_game)) => Some(LoadedMoves::from_record(saved_game.record())),
+            (None, None) => None,
+        };
+        let loaded_moves = match loaded {
+            Some(loaded) => {
+                // Archived games are replayed on the board where they were played.
+                if loaded.range.is_some() {
+                    config.range = loaded.range;
+                }
+                config.seed = config.seed.or(loaded.seed);
+                if let Some(handicap) = loaded.handicap {
+                    config.handicap = Some(format!("{},{}", handicap[1], handicap[2]));
+                }
+                recorded_first_player = recorded_first_player.or(loaded.first_player);
+                // Seats are swapped only in the swap variant, which older records do not write.
+                if loaded.swapped {
+                    config.variant = Some(Variant::Swap);
+                }
+                if loaded.variant.is_some() {
+                    config.variant = loaded.variant;
+                }
+                swapped = loaded.swapped;
+                think_times = loaded.think_times;
+                loaded_record = loaded.record;
+                Some(loaded.moves)
+            }
+            None => None,
+        };
+        if arg.select_size {
+            if arg.command.is_some() {
+                bail!("--select-size can be given only to a local game.");
+            }
+            config.range = config.range.map(Board::nearest_range);
+        }
+        config.validate()?;
+        let seat_config = match (&joined, &arg.command) {
+            (Some((client, _)), _) => SeatConfig::with_local_player(client.player()),
+            // Every move of a puzzle is selected by the solver.
+            (None, Some(Command::Puzzle { .. })) => SeatConfig::default(),
+            (None, _) if arg.demo => SeatConfig::with_ai(config.ai.unwrap()),
+            (None, _) => config.seat_config()?,
+        };
+        let seed = config.seed.unwrap_or_else(rng::entropy_seed);
+        let first_player =
+            recorded_first_player.unwrap_or_else(|| config.first_player.unwrap().player(seed));
+        let mut board = Board::try_new(config.range.unwrap())?;
+        if config.no_diagonals.unwrap() {
+            board.set_capture_rules(CaptureRules::orthogonal());
+        }
+        board.set_scoring(config.scoring.unwrap());
+        board.try_set_handicap(config.handicap()?, first_player)?;
+        // Recorded moves are replayed by the rules first to tell which of them is illegal.
+        if let Some(record) = &loaded_record {
+            record.replay(board.clone())?;
+        }
+        let start_board = match &arg.from_code {
+            Some(code) => {
+                if arg.command.is_some() {
+                    bail!("--from-code can be given only to a local game.");
+                }
+                let mut start_board = Board::from_code(code)?;
+                if config.no_diagonals.unwrap() {
+                    start_board.set_capture_rules(CaptureRules::orthogonal());
+                }
+                start_board.set_scoring(config.scoring.unwrap());
+                board = start_board.clone();
+                Some(start_board)
+            }
+            None => None,
+        };
+        let search_config = SearchConfig {
+            table_size: config.table_size.unwrap(),
+            threads: config.ai_threads.unwrap(),
+            time_budget: config.ai_time.map(Duration::from_millis),
+            weights: Weights::of(config.evaluation.unwrap(), config.eval_weights.unwrap()),
+            book: config.book.unwrap(),
+            phases: config.phases.unwrap(),
+        };
+        if let Some(Command::Analyze {
+            position,
+            depth,
+            json,
+        }) = &arg.command
+        {
+            let fen = if Path::new(position).is_file() {
+                fs::read_to_string(position)
+                    .with_context(|| format!("cannot read a position from {}", position))?
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty() && !line.starts_with('#'))
+                    .unwrap_or_default()
+                    .to_owned()
+            } else {
+                position.clone()
+            };
+            let (mut board, player) = parse_fen(&fen)?;
+            if config.no_diagonals.unwrap() {
+                board.set_capture_rules(CaptureRules::orthogonal());
+            }
+            // Values of the analysis are numbers of stones.
+            let search_config = SearchConfig {
+                weights: Weights::COUNT,
+                ..search_config
+            };
+            let analysis = analysis::analyze_with(
+                &board,
+                player,
+                &MaxN::new(*depth).with_search_config(search_config),
+            );
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&analysis)?);
+            } else {
+                let player_names = config
+                    .player_names
+                    .unwrap()
+                    .split(',')
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>();
+                print!("{}", analysis.table(&player_names));
+            }
+            return Ok(());
+        }
+        if let Some(Command::Perft { depth }) = &arg.command {
+            let mut game = Game::new(board);
+            println!("Depth        Nodes   Time (ms)");
+            for depth in 1..=*depth {
+                let start = Instant::now();
+                let nodes = game.perft(depth);
+                println!(
+                    "{:>5} {:>12} {:>11}",
+                    depth,
+                    nodes,
+                    start.elapsed().as_millis()
+                );
+            }
+            return Ok(());
+        }
+        if let Some(Command::Simulate {
+            strategies,
+            games,
+            opening_moves,
+            ratings: ratings_path,
+        }) = &arg.command
+        {
+            // Searches of a fixed depth without the book play the same games from the same seed.
+            let search_config = SearchConfig {
+                time_budget: None,
+                book: false,
+                ..search_config
+            };
+            let contestants = strategies
+                .iter()
+                .map(|spec| Contestant::parse(spec, search_config))
+                .collect::<Result<Vec<_>, _>>()?;
+            let ids = contestants
+                .iter()
+                .map(|contestant| contestant.id())
+                .collect::<Vec<_>>();
+            if ids.iter().all(|id| *id == ids[0]) {
+                bail!(
+                    "Two or more different strategies are needed to simulate games between them."
+                );
+            }
+            let mut ratings = match ratings_path {
+                Some(path) => Ratings::load(path)?,
+                None => Ratings::default(),
+            };
+            simulation::simulate(
+                &board,
+                &contestants,
+                search_config,
+                *games,
+                *opening_moves,
+                seed,
+                &mut ratings,
+            );
+            print!("{}", ratings.table(&ids));
+            if let Some(path) = ratings_path {
+                ratings.save(path)?;
+                println!("Ratings are written to {}.", path.display());
+            }
+            return Ok(());
+        }
+        if let Some(Command::Book {
+            plies,
+            depth,
+            output,
+        }) = &arg.command
+        {
+            // The book is searched from scratch rather than read from itself.
+            let search_config = SearchConfig {
+                book: false,
+                ..search_config
+            };
+            let book = OpeningBook::generate(
+                &board,
+                first_player,
+                *plies,
+                &MaxN::new(*depth).with_search_config(search_config),
+            );
+            let json = serde_json::to_string(&book)?;
+            match output {
+                Some(path) => {
+                    fs::write(path, json + "\n")
+                        .with_context(|| format!("cannot write the book to {}", path.display()))?;
+                    println!(
+                        "Book of {} positions is written to {}.",
+                        book.len(),
+                        path.display()
+                    );
+                }
+                None => println!("{}", json),
+            }
+            return Ok(());
+        }
+        let puzzle = match &arg.command {
+            Some(Command::Puzzle { file }) => {
+                if moves_path.is_some() {
+                    bail!(
+                        "--moves and --view cannot be given to a puzzle, which starts from its own position."
+                    );
+                }
+                let puzzle = Puzzle::load(file)?;
+                board = puzzle.board().clone();
+                Some(puzzle)
+            }
+            _ => None,
+        };
+        let match_state = match (arg.match_games, &arg.resume_match) {
+            (Some(games), _) => Some(MatchState::new(games, arg.match_scoring)),
+            (None, Some(path)) => Some(MatchState::load(path)?),
+            (None, None) => None,
+        };
+        if match_state.is_some() {
+            if arg.command.is_some() {
+                bail!("A match can be played only in a local game.");
+            }
+            if moves_path.is_some() {
+                bail!("--moves and --view cannot be given to a match, whose games start from the initial position.");
+            }
+        }
+        if let Some(Command::Engine) = arg.command {
+            let mut engine = Engine::new(board, config.ai.unwrap(), search_config, seed);
+            engine.run(io::stdin().lock(), &mut io::stdout().lock())?;
+            return Ok(());
+        }
+        if config.variant == Some(Variant::Swap)
+            && matches!(
+                arg.command,
+                Some(Command::Host { .. } | Command::Join { .. })
+            )
+        {
+            bail!("The swap variant is not available in network games.");
+        }
+        if arg.view.is_some() && matches!(arg.command, Some(Command::Host { .. })) {
+            bail!("--view cannot be given to a network game, whose history is not browsed.");
+        }
+        let (network, moves) = match (arg.command, joined) {
+            (Some(Command::Host { port }), _) => {
+                let remote_players = seat_config.remote_players();
+                if remote_players.is_empty() {
+                    return Err(TriversiError::NoRemoteSeat.into());
+                }
+                let rules = GameRules {
+                    range: config.range.unwrap(),
+                    no_diagonals: config.no_diagonals.unwrap(),
+                    auto_skip: config.auto_skip.unwrap(),
+                    tiebreak: config.tiebreak.unwrap(),
+                    handicap: board.handicap(),
+                    first_player,
+                    scoring: config.scoring.unwrap(),
+                };
+                let host = Host::bind(port, remote_players, rules)
+                    .with_context(|| format!("cannot listen on port {}", port))?;
+                (Some(Network::Host(host)), None)
+            }
+            (_, Some((client, moves))) => {
+                let moves = moves
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i_move, player_move)| (i_move + 1, player_move))
+                    .collect();
+                (Some(Network::Client(client)), Some(moves))
+            }
+            _ => {
+                if !seat_config.remote_players().is_empty() {
+                    return Err(TriversiError::RemoteSeatWithoutHost.into());
+                }
+                (None, None)
+            }
+        };
+        let moves = loaded_moves.or(moves);
+        // Saved game is restored at the turn being played or browsed, which is browsed if moves follow it.
+        let saved_turn = saved_game.as_ref().and_then(|saved_game| {
+            let turn = saved_game.turn();
+            (turn < saved_game.record().player_positions().len()).then_some(turn)
+        });
+        let session = Session {
+            moves,
+            headless: arg.headless,
+            view: arg.view.is_some() || saved_turn.is_some(),
+            turn: arg.turn.or(saved_turn),
+            swapped,
+            think_times,
+            network,
+            puzzle,
+            start_board,
+            match_state,
+            select_size: arg.select_size,
+        };
+        let started_config = Config {
+            seed: Some(seed),
+            ..config.clone()
+        };
+        let player_marks = config.player_marks_or_initials().unwrap();
+        let player_names = config.player_names.unwrap();
+        let mut color_config = ColorConfig::from(config.theme.unwrap());
+        if let Some(player_colors) = config.player_colors {
+            color_config = color_config.try_with_player_colors(&player_colors)?;
+        }
+        if let Some(frame_color) = &config.frame_color {
+            color_config = color_config.try_with_frame_color(frame_color)?;
+        }
+        let settings = Settings {
+            dump_dir: config.dump_dir.unwrap(),
+            stats_csv: config.stats_csv,
+            event_log: config.event_log,
+            export_game: config.export_game,
+            screenshot_on_finish: config.screenshot_on_finish,
+            records: config
+                .keep_records
+                .unwrap()
+                .then(|| config.records.or_else(Records::default_path))
+                .flatten(),
+            saves_dir: SavedGame::default_dir(),
+            config: started_config,
+            color_config,
+            messages: match &config.lang {
+                Some(lang) => Messages::load(lang)?,
+                None => Messages::from_env(),
+            },
+            key_binding: KeyBinding::try_new(&config.key_binding)?,
+            seat_config,
+            ai_move_interval: arg.demo.then_some(DEMO_MOVE_INTERVAL),
+            suggestion_strategy: config.ai.unwrap(),
+            search_config,
+            tiebreak: config.tiebreak.unwrap(),
+            first_player,
+            variant: config.variant.unwrap(),
+            auto_skip: config.auto_skip.unwrap(),
+            keep_branches: config.keep_branches.unwrap(),
+            max_turns: config.max_turns,
+            coach_margin: config
+                .coach
+                .unwrap()
+                .then_some(config.coach_margin.unwrap()),
+            legend: config.legend_visibility.unwrap(),
+            capture_lines: config.capture_line_visibility.unwrap(),
+            flip_panel: config.flip_panel_visibility.unwrap(),
+            capture_arrows: config.capture_arrows.unwrap(),
+            auto_scroll: config.auto_scroll.unwrap(),
+            announce: config.announce.unwrap(),
+            describe_rows: config.describe_rows.unwrap(),
+            notification: config.notification.unwrap(),
+            debug: config.debug.unwrap(),
+            seed,
+        };
+        let mut frame_style = FrameStyle::from(config.frame_style.unwrap());
+        if let Some(frame_chars) = &config.frame_chars {
+            frame_style = frame_style.try_with_chars(frame_chars)?;
+        }
+        let distance = config.distance.unwrap();
+        let frame_visibility = config.frame_visibility.unwrap();
+        let home_region_visibility = config.home_region_visibility.unwrap();
+        let upside_down = config.upside_down.unwrap();
+        let result = match config.style.unwrap() {
+            BoardStyle::Ascii => {
+                let paragraph_board = ParagraphBoard::try_new(
+                    distance,
+                    &player_names,
+                    &player_marks,
+                    config.distinct_shapes.unwrap(),
+                    frame_style,
+                    frame_visibility,
+                    home_region_visibility,
+                    upside_down,
+                )?;
+                Self::run_with(board, paragraph_board, settings, session)
+            }
+            BoardStyle::Unicode => {
+                let unicode_board = UnicodeBoard::try_new(
+                    distance,
+                    &player_names,
+                    &config.stones.unwrap(),
+                    frame_visibility,
+                    home_region_visibility,
+                )?;
+                Self::run_with(board, unicode_board, settings, session)
+            }
+            BoardStyle::Compact => {
+                let compact_board = CompactBoard::try_new(
+                    &player_names,
+                    &player_marks,
+                    frame_visibility,
+                    home_region_visibility,
+                )?;
+                Self::run_with(board, compact_board, settings, session)
+            }
+        };
+        if config.seed.is_none() {
+            println!("Seed: {} (reproduce the game with --seed {})", seed, seed);
+        }
+        result
+    }
+
+    fn run_with<D: BoardDisplay>(
+        board: Board,
+        board_display: D,
+        settings: Settings,
+        session: Session,
+    ) -> Result<()> {
+        let mut system = System::try_new(board, board_display, settings, Vec::new())?;
+        if let Some(network) = session.network {
+            system.set_network(network);
+        }
+        if let Some(puzzle) = session.puzzle {
+            system.set_puzzle(puzzle);
+        }
+        if let Some(start_board) = session.start_board {
+            system.set_start_board(start_board);
+        }
+        if let Some(match_state) = session.match_state {
+            system.set_match(match_state);
+        }
+        if let Some(moves) = session.moves {
+            system.replay(&moves)?;
+            system.restore_think_times(&session.think_times);
+        }
+        if session.select_size {
+            system.select_size();
+        }
+        if session.swapped {
+            system.restore_swap();
+        }
+        if session.view {
+            system.view_turn(session.turn)?;
+        }
+        if session.headless {
+            let message = format!("{}{}", system.message(), system.message_warning());
+            let message = message.trim();
+            if !message.is_empty() {
+                println!("{}", message);
+            }
+            return Ok(());
+        }
         let mut tui = Tui::try_new()?;
         tui.run(&mut system)?;
+        drop(tui);
+        if let Some(summary) = system.match_summary() {
+            print!("{}", summary);
+        }
         Ok(())
     }
+
+    /// Moves read from `path`, or from the standard input if `path` is `-`.
+    /// The file is a JSON record, a game archive, or a move list, which is guessed by the extension and the content.
+    /// Positions of a move list are on the board of `range`, while the others record their ranges.
+    fn read_moves(path: &Path, range: usize) -> Result<LoadedMoves> {
+        let text = if path == Path::new("-") {
+            io::read_to_string(io::stdin()).context("cannot read moves from the standard input")?
+        } else {
+            fs::read_to_string(path)
+                .with_context(|| format!("cannot read moves from {}", path.display()))?
+        };
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        let head = text.trim_start();
+        if extension == Some("json") || head.starts_with('{') {
+            let record = serde_json::from_str::<Record>(&text).with_context(|| {
+                format!("cannot read a record of a game from {}", path.display())
+            })?;
+            record
+                .validate()
+                .with_context(|| format!("invalid record in {}", path.display()))?;
+            Ok(LoadedMoves::from_record(&record))
+        } else if extension == Some("tvr") || head.starts_with('[') {
+            let archive = parse_archive(&text)?;
+            Ok(LoadedMoves {
+                range: Some(archive.range),
+                seed: archive.seed,
+                handicap: Some(archive.handicap),
+                first_player: Some(archive.first_player),
+                swapped: archive.swapped,
+                variant: archive.variant,
+                think_times: Vec::new(),
+                record: None,
+                moves: archive.moves,
+            })
+        } else {
+            Ok(LoadedMoves {
+                range: None,
+                seed: None,
+                handicap: None,
+                first_player: None,
+                swapped: false,
+                variant: None,
+                think_times: Vec::new(),
+                record: None,
+                moves: parse_move_list(&text, range)?,
+            })
+        }
+    }
+
+    /// Values given as command line options.
+    fn to_config(&self) -> Config {
+        Config {
+            range: self.range,
+            distance: self.distance,
+            player_names: self.player_names.clone(),
+            player_marks: self.player_marks.clone(),
+            distinct_shapes: self.distinct_shapes.then_some(true),
+            player_colors: self.player_colors.clone(),
+            style: self.style,
+            stones: self.stones.clone(),
+            theme: self.theme,
+            lang: self.lang.clone(),
+            frame_visibility: None,
+            frame_style: self.frame_style,
+            frame_chars: None,
+            frame_color: None,
+            home_region_visibility: self.home_regions.then_some(true),
+            upside_down: self.upside_down.then_some(true),
+            legend_visibility: self.legend.then_some(true),
+            capture_line_visibility: self.capture_lines.then_some(true),
+            flip_panel_visibility: self.flip_panel.then_some(true),
+            capture_arrows: self.no_capture_arrows.then_some(false),
+            auto_scroll: self.no_auto_scroll.then_some(false),
+            announce: self.announce.then_some(true),
+            describe_rows: self.describe_rows.then_some(true),
+            notification: self.notification,
+            tiebreak: self.tiebreak,
+            scoring: self.scoring,
+            first_player: self.first_player,
+            variant: self.variant,
+            auto_skip: self.auto_skip.then_some(true),
+            keep_branches: self.keep_branches.then_some(true),
+            max_turns: self.max_turns,
+            coach: self.coach.then_some(true),
+            coach_margin: self.coach_margin,
+            seats: self.seats.clone(),
+            seat: self.seat,
+            ai: self.ai,
+            table_size: self.table_size,
+            ai_threads: self.ai_threads,
+            ai_time: self.ai_time,
+            evaluation: self.evaluation,
+            eval_weights: self.eval_weights,
+            phases: None,
+            book: self.no_book.then_some(false),
+            no_diagonals: self.no_diagonals.then_some(true),
+            handicap: self.handicap.clone(),
+            seed: self.seed,
+            debug: self.debug.then_some(true),
+            dump_dir: self.dump_dir.clone(),
+            stats_csv: self.stats_csv.clone(),
+            event_log: self.event_log.clone(),
+            export_game: self.export_game.clone(),
+            screenshot_on_finish: self.screenshot_on_finish.clone(),
+            keep_records: self.no_records.then_some(false),
+            records: self.records.clone(),
+            key_binding: HashMap::new(),
+        }
+    }
+}
+
+/// Configuration of a game, where the configuration file, the options of the saved game,
+/// and the command line take precedence over the built-in values in this order.
+fn layered_config(file: Config, saved: Option<Config>, command_line: Config) -> Config {
+    Config::builtin()
+        .overridden_by(file)
+        .overridden_by(saved.unwrap_or_default())
+        .overridden_by(command_line)
+}
+
+/// Seats of the host unless given, where the host plays the first seat.
+const DEFAULT_HOST_SEATS: &str = "human,remote,remote";
+/// Least time between moves of the computer in the demo, which lets them be followed by eye.
+const DEMO_MOVE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Options of a run which are not saved in the configuration file.
+struct Session {
+    /// Moves applied before the game is handed to players.
+    moves: Option<Vec<(usize, Move)>>,
+    /// Whether to exit after applying the moves without starting the terminal interface.
+    headless: bool,
+    /// Whether the seats were swapped in the replayed game.
+    swapped: bool,
+    /// Times spent on the replayed moves in milliseconds, which are empty if they are not recorded.
+    think_times: Vec<u64>,
+    /// Whether to start browsing the history of the moves at `turn`, or at the last move if it is `None`.
+    view: bool,
+    turn: Option<usize>,
+    network: Option<Network>,
+    puzzle: Option<Puzzle>,
+    /// Board given by `--from-code`, which is restored by initializing.
+    start_board: Option<Board>,
+    match_state: Option<MatchState>,
+    /// Whether to choose the range of the board from a menu first.
+    select_size: bool,
+}
+
+/// Moves given by `--moves`, with the settings of the game if they are recorded.
+struct LoadedMoves {
+    range: Option<usize>,
+    seed: Option<u64>,
+    handicap: Option<[usize; 3]>,
+    first_player: Option<Player>,
+    swapped: bool,
+    /// Variant the moves are played in, which is `None` if it is not recorded.
+    variant: Option<Variant>,
+    /// Times spent on the moves in milliseconds, which are empty if they are not recorded.
+    think_times: Vec<u64>,
+    /// Record the moves are read from, which is `None` for an archive or a move list.
+    record: Option<Record>,
+    /// Moves with their line numbers.
+    moves: Vec<(usize, Move)>,
+}
+
+impl LoadedMoves {
+    /// Moves of `record`, which are numbered from 1 in place of line numbers.
+    fn from_record(record: &Record) -> Self {
+        let moves = record
+            .moves()
+            .into_iter()
+            .enumerate()
+            .map(|(i_move, player_move)| (i_move + 1, player_move))
+            .collect();
+        Self {
+            range: Some(record.range()),
+            seed: *record.seed(),
+            handicap: Some(record.handicap()),
+            first_player: Some(record.first_player()),
+            swapped: record.swapped(),
+            variant: record.variant(),
+            think_times: record.think_times().clone(),
+            record: Some(record.clone()),
+            moves,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BoardStyle {
+    /// Single ASCII characters for stones and frames.
+    Ascii,
+    /// Unicode stones and box-drawing frames.
+    Unicode,
+    /// One ASCII character per position and no frames, for narrow terminals.
+    Compact,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage the configuration file
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Host a network game, where seats given as remote in --seats are played by clients
+    Host {
+        #[clap(long, default_value_t = 7777, help = "Port to listen on")]
+        port: u16,
+    },
+    /// Join a network game hosted at ADDRESS, e.g. example.com:7777
+    Join {
+        address: String,
+        #[clap(long, help = "Seat (1, 2, or 3) played on this terminal")]
+        seat: usize,
+    },
+    /// Answer commands of a line-based protocol on the standard input for external programs (see `help` command)
+    Engine,
+    /// Evaluate every position selectable by the player to move in a position
+    Analyze {
+        #[clap(
+            long,
+            help = "Position as rows of .123 separated by / and the seat to move, e.g. './../.../.12./.312. 1', or a file containing it"
+        )]
+        position: String,
+        #[clap(
+            long,
+            default_value_t = 3,
+            help = "Number of moves searched ahead, which is not used with --ai-time"
+        )]
+        depth: usize,
+        #[clap(long, help = "Print the result as JSON")]
+        json: bool,
+    },
+    /// List the games saved in slots by a key with their dates, ranges, and numbers of turns
+    Saves,
+    /// Find the best move of a position given in FILE (see puzzles/ for examples)
+    Puzzle { file: PathBuf },
+    /// Count the sequences of moves from the initial board of --range up to DEPTH moves to verify the move generation
+    Perft {
+        #[clap(long, help = "Number of moves, where every depth up to it is counted")]
+        depth: usize,
+    },
+    /// Play games between computer players in batches and rate them by a multi-player Elo rating
+    Simulate {
+        #[clap(
+            long = "strategy",
+            value_name = "STRATEGY",
+            required = true,
+            help = "Strategy playing the games, which is given twice or more and may be followed by parameters differing from the options, e.g. max-n:evaluation=count or phased:corner=20,endgame=60"
+        )]
+        strategies: Vec<String>,
+        #[clap(long, default_value_t = 24, help = "Number of games")]
+        games: usize,
+        #[clap(
+            long,
+            default_value_t = 4,
+            help = "Number of random moves opening each game so that deterministic strategies play different games"
+        )]
+        opening_moves: usize,
+        #[clap(
+            long,
+            help = "JSON file where the ratings are read and written so that successive runs accumulate them"
+        )]
+        ratings: Option<PathBuf>,
+    },
+    /// Generate an opening book of the max-n strategy by searching every position within PLIES moves of --range
+    Book {
+        #[clap(
+            long,
+            default_value_t = 3,
+            help = "Number of moves from the initial board within which positions are written"
+        )]
+        plies: usize,
+        #[clap(
+            long,
+            default_value_t = 4,
+            help = "Number of moves searched ahead from each position, which is not used with --ai-time"
+        )]
+        depth: usize,
+        #[clap(
+            long,
+            help = "File where the book is written as JSON [default: the standard output]"
+        )]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Write a configuration file with commented-out default values
+    Init {
+        #[clap(short, long, help = "Overwrite an existing file")]
+        force: bool,
+    },
 }
 
 #[derive(Parser)]
 #[clap(author, version, about, after_help = concat!("Repository: ", env!("CARGO_PKG_REPOSITORY")))]
 pub struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     #[clap(
         short,
         long,
-        default_value = "14",
-        help = "Number of positions in one edge (>= 5 & = 0,2 (mod3))"
+        global = true,
+        help = "Configuration file [default: ~/.config/triversi/config.toml]"
     )]
-    range: usize,
+    config: Option<PathBuf>,
 
     #[clap(
         short,
         long,
-        default_value = "3",
-        help = format!("Distance between positions (>= 2, <= {})", ParagraphBoard::MAX_DISTANCE)
+        help = "Number of positions in one edge (>= 5 & = 0,2 (mod3)) [default: 14]"
     )]
-    distance: usize,
+    range: Option<usize>,
 
     #[clap(
         short,
         long,
-        default_value = "Cyan,Magenta,Yellow",
-        help = "Marks of each player (delimiters are ','), "
+        help = format!("Distance between positions (>= 2, <= {}) [default: 3]", ParagraphBoard::MAX_DISTANCE)
+    )]
+    distance: Option<usize>,
+
+    #[clap(
+        short,
+        long,
+        help = "Names of each player (delimiters are ',') [default: Cyan,Magenta,Yellow]"
+    )]
+    player_names: Option<String>,
+
+    #[clap(
+        short = 'm',
+        long,
+        help = "Marks of each player in the ascii and compact styles (delimiters are ','), each of which is a string of 1 or 2 columns or one of circle, square, triangle, diamond, star, and heart [default: first characters of the names]"
+    )]
+    player_marks: Option<String>,
+
+    #[clap(
+        long,
+        help = "Enclose the marks of players in (), [], and <> respectively in the ascii style, which tell them apart without colors"
+    )]
+    distinct_shapes: bool,
+
+    #[clap(
+        long,
+        help = "Colors of each player (delimiters are ','), e.g. cyan,#ff8000,11 [default: colors of the theme]"
+    )]
+    player_colors: Option<String>,
+
+    #[clap(
+        short,
+        long,
+        visible_alias = "display",
+        value_enum,
+        help = "Style of the board [default: ascii]"
+    )]
+    style: Option<BoardStyle>,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "Characters of frames in the ascii style, which are drawn in a dim color [default: ascii]"
+    )]
+    frame_style: Option<FrameStyleKind>,
+
+    #[clap(
+        long,
+        help = "Stones of each player in the unicode style (delimiters are ',') [default: ●,▲,■]"
+    )]
+    stones: Option<String>,
+
+    #[clap(
+        long,
+        help = "Directory where plain text dumps of the board are written [default: .]"
+    )]
+    dump_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "CSV file where the thinking time and the result of each move are written at the end of the game"
+    )]
+    stats_csv: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "File or FIFO where events of the game (init, move, skip, pass, finish, and history) are written as JSON lines"
+    )]
+    event_log: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "File where the game is archived as text at the end of the game, which is also written by a key [default: a file in --dump-dir]"
+    )]
+    export_game: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "File where the screen is written as text when the game is finished, with colors if the extension is .ans or .ansi"
+    )]
+    screenshot_on_finish: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "JSON file where the best score of a single game for each range is kept [default: ~/.local/share/triversi/records.json]"
+    )]
+    records: Option<PathBuf>,
+
+    #[clap(long, help = "Neither read nor update the best scores")]
+    no_records: bool,
+
+    #[clap(
+        long,
+        help = "File of moves applied before playing, one per line as x,y or pass, or a game archived by --export-game ('-' for the standard input)"
+    )]
+    moves: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "FILE",
+        conflicts_with = "moves",
+        help = "File of moves read like --moves, which starts browsing the history at the last move or --turn"
+    )]
+    view: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "N",
+        requires = "view",
+        help = "Number of moves after which --view starts browsing the history (0 is the initial board) [default: all the moves]"
+    )]
+    turn: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "CODE",
+        conflicts_with_all = ["moves", "view", "match_games", "resume_match"],
+        help = "Start from the board of CODE, which is copied to the message by a key, instead of the initial board"
+    )]
+    from_code: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["moves", "view", "from_code", "load_slot", "match_games", "resume_match"],
+        help = "Choose the range of the board from a menu before the game starts, where an invalid --range is replaced by the nearest valid one"
+    )]
+    select_size: bool,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["moves", "view", "from_code", "match_games", "resume_match"],
+        help = "Restore the game saved in the slot NAME by a key, where other options override its settings"
+    )]
+    load_slot: Option<String>,
+
+    #[clap(
+        long,
+        requires = "moves",
+        help = "Print the message after applying --moves and exit without the terminal interface"
+    )]
+    headless: bool,
+
+    #[clap(
+        long = "match",
+        value_name = "GAMES",
+        help = "Play a match of GAMES games where the first player rotates, which ends early when the leader in points cannot be caught"
+    )]
+    match_games: Option<usize>,
+
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = MatchScoring::Points,
+        help = "What is summed up in the standings of a match"
+    )]
+    match_scoring: MatchScoring,
+
+    #[clap(
+        long,
+        conflicts_with = "match_games",
+        help = "Match saved by a key in the quit dialog, which is resumed from its next game"
+    )]
+    resume_match: Option<PathBuf>,
+
+    #[clap(short, long, value_enum, help = "Color theme [default: default]")]
+    theme: Option<Theme>,
+
+    #[clap(
+        long,
+        help = "Language of messages (en, ja, or a locale file ending with .toml) [default: the language of LANG]"
+    )]
+    lang: Option<String>,
+
+    #[clap(
+        long,
+        help = "Tint the corners of the board with the colors of players at startup"
+    )]
+    home_regions: bool,
+
+    #[clap(
+        long,
+        help = "Turn the board upside down with its wide edge at the top in the ascii style, which is also toggled by a key"
+    )]
+    upside_down: bool,
+
+    #[clap(
+        long,
+        help = "Show the legend of marks and names of players at startup, which is also toggled by a key"
+    )]
+    legend: bool,
+
+    #[clap(
+        long,
+        help = "Draw lines from the stone under the cursor or the last placed one through the stones flipped by it in the ascii style, which is also toggled by a key"
+    )]
+    capture_lines: bool,
+
+    #[clap(
+        long,
+        help = "List the stones flipped by the position under the cursor in a side panel at startup, which is also toggled by a key"
+    )]
+    flip_panel: bool,
+
+    #[clap(
+        long,
+        help = "Hide the arrows pointing from the selectable position under the cursor toward the directions it captures in the ascii style"
+    )]
+    no_capture_arrows: bool,
+
+    #[clap(
+        long,
+        help = "Keep the board still when the cursor is moved out of view, which is scrolled only by keys"
     )]
-    player_names: String,
+    no_auto_scroll: bool,
+
+    #[clap(
+        long,
+        help = "Write every move and skip to the message log in a sentence for screen readers"
+    )]
+    announce: bool,
+
+    #[clap(
+        long,
+        help = "Read out every row of the board in the description written to the message log by a key"
+    )]
+    describe_rows: bool,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "Feedback on an illegal selection, a skipped turn, and the end of the game [default: off]"
+    )]
+    notification: Option<Notification>,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "Rule to resolve a tie of the final score [default: none]"
+    )]
+    tiebreak: Option<Tiebreak>,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "What the final score counts, where territory adds empty regions bordered only by stones of the player [default: stones]"
+    )]
+    scoring: Option<Scoring>,
+
+    #[clap(
+        long,
+        value_enum,
+        conflicts_with_all = ["match_games", "resume_match"],
+        help = "Seat (1, 2, or 3) moving first, or random chosen from --seed [default: 1]"
+    )]
+    first_player: Option<FirstPlayer>,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "Variant of the rules, where swap lets the second player take over the seat of the first after its first move [default: standard]"
+    )]
+    variant: Option<Variant>,
+
+    #[clap(
+        long,
+        help = "Skip turns of players who cannot select any position without waiting for a key"
+    )]
+    auto_skip: bool,
+
+    #[clap(
+        long,
+        help = "Keep moves after a turn selected in the history as a branch instead of discarding them"
+    )]
+    keep_branches: bool,
+
+    #[clap(
+        long,
+        value_name = "N",
+        help = "Finish the game with the stones on the board when N stones have been placed [default: no limit]"
+    )]
+    max_turns: Option<usize>,
+
+    #[clap(
+        long,
+        help = "Tell human players a position flipping more stones after their moves, without preventing them"
+    )]
+    coach: bool,
+
+    #[clap(
+        long,
+        value_name = "MARGIN",
+        help = "Number of stones by which a position must flip more than the selected one to be told by --coach [default: 1]"
+    )]
+    coach_margin: Option<u64>,
+
+    #[clap(
+        long,
+        help = "Controllers of the three seats (delimiters are ','), each of which is human, remote, or a strategy, e.g. human,greedy,human [default: human,human,human, or human,remote,remote for host]"
+    )]
+    seats: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with = "seats",
+        help = "Seat (1, 2, or 3) played by you, where the others are played by the computer"
+    )]
+    seat: Option<usize>,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["seats", "seat"],
+        help = "Let the computer play every seat by --ai, showing each move for a while, where a key pauses it"
+    )]
+    demo: bool,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "Strategy of the computer playing seats other than --seat and suggesting positions [default: greedy]"
+    )]
+    ai: Option<StrategyKind>,
+
+    #[clap(
+        long,
+        value_name = "ENTRIES",
+        help = "Number of entries of the transposition table of the max-n strategy (0 disables it) [default: 65536]"
+    )]
+    table_size: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "N",
+        help = "Number of threads of the max-n strategy, which selects the same position as one thread [default: 1]"
+    )]
+    ai_threads: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Time of each search of the max-n strategy and the analyze command, which deepens the search from one move until it runs out [default: fixed depth]"
+    )]
+    ai_time: Option<u64>,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "How the max-n strategy values positions, where positional weighs corners, edges, and selectable positions [default: positional]"
+    )]
+    evaluation: Option<Evaluation>,
+
+    #[clap(
+        long,
+        value_name = "WEIGHTS",
+        value_parser = parse_weights,
+        help = "Weights of the positional evaluation (delimiters are ','), where missing ones take the defaults [default: corner=10,edge=3,mobility=1,count=1]"
+    )]
+    eval_weights: Option<Weights>,
+
+    #[clap(
+        long,
+        help = "Search every position instead of selecting moves of the built-in opening book for ranges 14 and 15 in the max-n strategy"
+    )]
+    no_book: bool,
+
+    #[clap(
+        long,
+        help = "Capture stones only horizontally and vertically, not along the left-up/right-down diagonals"
+    )]
+    no_diagonals: bool,
+
+    #[clap(
+        long,
+        help = "Numbers of extra starting stones of seats 2 and 3 (delimiters are ','), placed in their home regions, e.g. 0,1 [default: 0,0]"
+    )]
+    handicap: Option<String>,
+
+    #[clap(
+        long,
+        help = "Seed of randomized components such as the random strategy, which makes a game reproducible [default: chosen at random and printed at exit]"
+    )]
+    seed: Option<u64>,
+
+    #[clap(
+        long,
+        help = "Show the debug panel at startup, which is also toggled by a key"
+    )]
+    debug: bool,
+}
+
+fn parse_weights(s: &str) -> Result<Weights, TriversiError> {
+    Weights::try_from(s.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::key_binding::Action;
+    use std::iter;
+
+    fn command_line(args: &[&str]) -> Config {
+        Cli::try_parse_from(iter::once("triversi").chain(args.iter().copied()))
+            .unwrap()
+            .to_config()
+    }
+
+    #[test]
+    fn builtin_values_are_used_without_other_configurations() {
+        let config = layered_config(Config::default(), None, command_line(&[]));
+        assert_eq!(config.range, Some(14));
+        assert_eq!(config.distance, Some(3));
+        assert_eq!(config.theme, Some(Theme::Default));
+    }
+
+    #[test]
+    fn file_saved_game_and_command_line_take_precedence_in_order() {
+        let file = toml::from_str::<Config>(
+            r#"
+            range = 20
+            distance = 4
+            player_names = "Red,Green,Blue"
+            theme = "colorblind"
+            [key_binding]
+            quit = "x"
+            "#,
+        )
+        .unwrap();
+        let saved = Config {
+            range: Some(8),
+            theme: Some(Theme::Mono),
+            ..Config::default()
+        };
+        let config = layered_config(file, Some(saved), command_line(&["-d", "5"]));
+        // Set only by the file.
+        assert_eq!(config.player_names.as_deref(), Some("Red,Green,Blue"));
+        assert_eq!(
+            config.key_binding.get(&Action::Quit).map(String::as_str),
+            Some("x")
+        );
+        // Set by the file and the saved game.
+        assert_eq!(config.range, Some(8));
+        assert_eq!(config.theme, Some(Theme::Mono));
+        // Set by the file and the command line.
+        assert_eq!(config.distance, Some(5));
+        // Set by none of them.
+        assert_eq!(config.style, Some(BoardStyle::Ascii));
+    }
+
+    #[test]
+    fn command_line_overrides_saved_game() {
+        let saved = Config {
+            range: Some(8),
+            ..Config::default()
+        };
+        let config = layered_config(Config::default(), Some(saved), command_line(&["-r", "11"]));
+        assert_eq!(config.range, Some(11));
+    }
 }
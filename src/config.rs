@@ -0,0 +1,872 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Configuration file of Triversi.
+//!
+//! Values are resolved in the order of built-in defaults, the configuration file, and command line options,
+//! where the latter overrides the former.
+
+use crate::ai::{Evaluation, Phases, StrategyKind, Weights, DEFAULT_TABLE_SIZE};
+use crate::app::board_display::paragraph_board::{FrameStyle, FrameStyleKind, PlayerMark};
+use crate::app::board_display::unicode_board::StoneMark;
+use crate::app::board_display::{BoardDisplay, ParagraphBoard, UnicodeBoard};
+use crate::app::key_binding::{Action, KeyBinding, ACTIONS};
+use crate::app::{ColorConfig, Notification, SeatConfig, Theme};
+use crate::board::{Board, FirstPlayer, Scoring, Tiebreak, Variant};
+use crate::cli::BoardStyle;
+use crate::error::TriversiError;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub range: Option<usize>,
+    pub distance: Option<usize>,
+    pub player_names: Option<String>,
+    pub player_marks: Option<String>,
+    pub distinct_shapes: Option<bool>,
+    pub player_colors: Option<String>,
+    pub style: Option<BoardStyle>,
+    pub stones: Option<String>,
+    pub theme: Option<Theme>,
+    pub lang: Option<String>,
+    pub frame_visibility: Option<bool>,
+    pub frame_style: Option<FrameStyleKind>,
+    pub frame_chars: Option<String>,
+    pub frame_color: Option<String>,
+    pub home_region_visibility: Option<bool>,
+    pub upside_down: Option<bool>,
+    pub legend_visibility: Option<bool>,
+    pub capture_line_visibility: Option<bool>,
+    pub flip_panel_visibility: Option<bool>,
+    pub capture_arrows: Option<bool>,
+    pub auto_scroll: Option<bool>,
+    pub announce: Option<bool>,
+    pub describe_rows: Option<bool>,
+    pub notification: Option<Notification>,
+    pub tiebreak: Option<Tiebreak>,
+    pub scoring: Option<Scoring>,
+    pub first_player: Option<FirstPlayer>,
+    pub variant: Option<Variant>,
+    pub auto_skip: Option<bool>,
+    pub keep_branches: Option<bool>,
+    pub max_turns: Option<usize>,
+    pub coach: Option<bool>,
+    pub coach_margin: Option<u64>,
+    pub seats: Option<String>,
+    pub seat: Option<usize>,
+    pub ai: Option<StrategyKind>,
+    pub table_size: Option<usize>,
+    pub ai_threads: Option<usize>,
+    pub ai_time: Option<u64>,
+    pub evaluation: Option<Evaluation>,
+    pub eval_weights: Option<Weights>,
+    pub phases: Option<Phases>,
+    pub book: Option<bool>,
+    pub no_diagonals: Option<bool>,
+    pub handicap: Option<String>,
+    pub seed: Option<u64>,
+    pub debug: Option<bool>,
+    pub dump_dir: Option<PathBuf>,
+    pub stats_csv: Option<PathBuf>,
+    pub event_log: Option<PathBuf>,
+    pub export_game: Option<PathBuf>,
+    pub screenshot_on_finish: Option<PathBuf>,
+    pub keep_records: Option<bool>,
+    pub records: Option<PathBuf>,
+    pub key_binding: HashMap<Action, String>,
+}
+
+impl Config {
+    /// Built-in default values, where every field is set.
+    pub fn builtin() -> Self {
+        Self {
+            range: Some(14),
+            distance: Some(3),
+            player_names: Some("Cyan,Magenta,Yellow".to_owned()),
+            player_marks: None,
+            distinct_shapes: Some(false),
+            player_colors: None,
+            style: Some(BoardStyle::Ascii),
+            stones: Some("●,▲,■".to_owned()),
+            theme: Some(Theme::Default),
+            lang: None,
+            frame_visibility: Some(false),
+            frame_style: Some(FrameStyleKind::Ascii),
+            frame_chars: None,
+            frame_color: None,
+            home_region_visibility: Some(false),
+            upside_down: Some(false),
+            legend_visibility: Some(false),
+            capture_line_visibility: Some(false),
+            flip_panel_visibility: Some(false),
+            capture_arrows: Some(true),
+            auto_scroll: Some(true),
+            announce: Some(false),
+            describe_rows: Some(false),
+            notification: Some(Notification::Off),
+            tiebreak: Some(Tiebreak::None),
+            scoring: Some(Scoring::Stones),
+            first_player: Some(FirstPlayer::Seat1),
+            variant: Some(Variant::Standard),
+            auto_skip: Some(false),
+            keep_branches: Some(false),
+            max_turns: None,
+            coach: Some(false),
+            coach_margin: Some(1),
+            seats: None,
+            seat: None,
+            ai: Some(StrategyKind::Greedy),
+            table_size: Some(DEFAULT_TABLE_SIZE),
+            ai_threads: Some(1),
+            ai_time: None,
+            evaluation: Some(Evaluation::Positional),
+            eval_weights: Some(Weights::default()),
+            phases: Some(Phases::default()),
+            book: Some(true),
+            no_diagonals: Some(false),
+            handicap: Some("0,0".to_owned()),
+            seed: None,
+            debug: Some(false),
+            dump_dir: Some(PathBuf::from(".")),
+            stats_csv: None,
+            event_log: None,
+            export_game: None,
+            screenshot_on_finish: None,
+            keep_records: Some(true),
+            records: None,
+            key_binding: HashMap::new(),
+        }
+    }
+
+    /// Values of `other` which are set take precedence.
+    pub fn overridden_by(mut self, other: Config) -> Self {
+        self.key_binding.extend(other.key_binding);
+        Self {
+            range: other.range.or(self.range),
+            distance: other.distance.or(self.distance),
+            player_names: other.player_names.or(self.player_names),
+            player_marks: other.player_marks.or(self.player_marks),
+            distinct_shapes: other.distinct_shapes.or(self.distinct_shapes),
+            player_colors: other.player_colors.or(self.player_colors),
+            style: other.style.or(self.style),
+            stones: other.stones.or(self.stones),
+            theme: other.theme.or(self.theme),
+            lang: other.lang.or(self.lang),
+            frame_visibility: other.frame_visibility.or(self.frame_visibility),
+            frame_style: other.frame_style.or(self.frame_style),
+            frame_chars: other.frame_chars.or(self.frame_chars),
+            frame_color: other.frame_color.or(self.frame_color),
+            home_region_visibility: other.home_region_visibility.or(self.home_region_visibility),
+            upside_down: other.upside_down.or(self.upside_down),
+            legend_visibility: other.legend_visibility.or(self.legend_visibility),
+            capture_line_visibility: other
+                .capture_line_visibility
+                .or(self.capture_line_visibility),
+            flip_panel_visibility: other.flip_panel_visibility.or(self.flip_panel_visibility),
+            capture_arrows: other.capture_arrows.or(self.capture_arrows),
+            auto_scroll: other.auto_scroll.or(self.auto_scroll),
+            announce: other.announce.or(self.announce),
+            describe_rows: other.describe_rows.or(self.describe_rows),
+            notification: other.notification.or(self.notification),
+            tiebreak: other.tiebreak.or(self.tiebreak),
+            scoring: other.scoring.or(self.scoring),
+            first_player: other.first_player.or(self.first_player),
+            variant: other.variant.or(self.variant),
+            auto_skip: other.auto_skip.or(self.auto_skip),
+            keep_branches: other.keep_branches.or(self.keep_branches),
+            max_turns: other.max_turns.or(self.max_turns),
+            coach: other.coach.or(self.coach),
+            coach_margin: other.coach_margin.or(self.coach_margin),
+            seats: other.seats.or(self.seats),
+            seat: other.seat.or(self.seat),
+            ai: other.ai.or(self.ai),
+            table_size: other.table_size.or(self.table_size),
+            ai_threads: other.ai_threads.or(self.ai_threads),
+            ai_time: other.ai_time.or(self.ai_time),
+            evaluation: other.evaluation.or(self.evaluation),
+            eval_weights: other.eval_weights.or(self.eval_weights),
+            phases: other.phases.or(self.phases),
+            book: other.book.or(self.book),
+            no_diagonals: other.no_diagonals.or(self.no_diagonals),
+            handicap: other.handicap.or(self.handicap),
+            seed: other.seed.or(self.seed),
+            debug: other.debug.or(self.debug),
+            dump_dir: other.dump_dir.or(self.dump_dir),
+            stats_csv: other.stats_csv.or(self.stats_csv),
+            event_log: other.event_log.or(self.event_log),
+            export_game: other.export_game.or(self.export_game),
+            screenshot_on_finish: other.screenshot_on_finish.or(self.screenshot_on_finish),
+            keep_records: other.keep_records.or(self.keep_records),
+            records: other.records.or(self.records),
+            key_binding: self.key_binding,
+        }
+    }
+
+    /// `$XDG_CONFIG_HOME/triversi/config.toml` or `$HOME/.config/triversi/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("triversi").join("config.toml"))
+    }
+
+    /// Loads `path`, or the default path if `path` is `None`.
+    /// A missing file at the default path is not an error.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        let text = fs::read_to_string(&path).map_err(TriversiError::io(&path))?;
+        let config = toml::from_str::<Self>(&text)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        config
+            .validate()
+            .with_context(|| format!("invalid configuration in {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Checks values which are set, where all the invalid values are reported together.
+    pub fn validate(&self) -> Result<(), TriversiError> {
+        let mut errors = Vec::new();
+        if let Some(range) = self.range {
+            errors.extend(Board::validate_range(range).err());
+        }
+        if let Some(distance) = self.distance {
+            let max_distance = match self.style {
+                Some(BoardStyle::Unicode) => UnicodeBoard::MAX_DISTANCE,
+                _ => ParagraphBoard::MAX_DISTANCE,
+            };
+            if !(2..=max_distance).contains(&distance) {
+                errors.push(TriversiError::InvalidBoardDistance(distance));
+            }
+        }
+        if let Some(player_marks) = &self.player_marks {
+            errors.extend(PlayerMark::try_from(player_marks.to_owned()).err());
+        }
+        // Marks of the ascii style need room, which is checked only for valid distances and marks.
+        if let (Some(BoardStyle::Ascii), Some(distance @ 2..), Some(player_marks)) =
+            (self.style, self.distance, self.player_marks_or_initials())
+        {
+            if let Ok(mut player_mark) = PlayerMark::try_from(player_marks) {
+                if self.distinct_shapes == Some(true) {
+                    player_mark = player_mark.with_shapes();
+                }
+                errors.extend(ParagraphBoard::validate_distance(distance, &player_mark).err());
+            }
+        }
+        if let Some(player_names) = &self.player_names {
+            if player_names.split(',').count() != 3 {
+                errors.push(TriversiError::InvalidStringForPlayerNames(
+                    player_names.to_owned(),
+                ));
+            }
+        }
+        if let Some(player_colors) = &self.player_colors {
+            errors.extend(
+                ColorConfig::default()
+                    .try_with_player_colors(player_colors)
+                    .err(),
+            );
+        }
+        if let Some(frame_chars) = &self.frame_chars {
+            errors.extend(
+                FrameStyle::from(FrameStyleKind::default())
+                    .try_with_chars(frame_chars)
+                    .err(),
+            );
+        }
+        if let Some(frame_color) = &self.frame_color {
+            errors.extend(
+                ColorConfig::default()
+                    .try_with_frame_color(frame_color)
+                    .err(),
+            );
+        }
+        if let Some(stones) = &self.stones {
+            errors.extend(StoneMark::try_from(stones.to_owned()).err());
+        }
+        if let Some(seats) = &self.seats {
+            errors.extend(SeatConfig::try_from(seats.to_owned()).err());
+        }
+        if let Some(seat) = self.seat {
+            errors.extend(SeatConfig::try_with_human_seat(seat, StrategyKind::default()).err());
+        }
+        if let Some(handicap) = &self.handicap {
+            errors.extend(parse_handicap(handicap).err());
+        }
+        if let Some(phases) = &self.phases {
+            errors.extend(phases.validate().err());
+        }
+        if self.ai_threads == Some(0) {
+            errors.push(TriversiError::InvalidAiThreads(0));
+        }
+        if self.max_turns == Some(0) {
+            errors.push(TriversiError::InvalidMaxTurns(0));
+        }
+        errors.extend(KeyBinding::try_new(&self.key_binding).err());
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.pop().unwrap()),
+            _ => Err(TriversiError::MultipleInvalidArgs(errors)),
+        }
+    }
+
+    /// Marks of players, which are the first characters of the names unless they are set.
+    pub fn player_marks_or_initials(&self) -> Option<String> {
+        self.player_marks.clone().or_else(|| {
+            self.player_names.as_ref().map(|player_names| {
+                player_names
+                    .split(',')
+                    .map(|name| name.chars().take(1).collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+        })
+    }
+
+    /// Controllers of seats, where `seats` takes precedence over `seat`.
+    pub fn seat_config(&self) -> Result<SeatConfig, TriversiError> {
+        match (&self.seats, self.seat) {
+            (Some(seats), _) => SeatConfig::try_from(seats.to_owned()),
+            (None, Some(seat)) => {
+                SeatConfig::try_with_human_seat(seat, self.ai.unwrap_or_default())
+            }
+            (None, None) => Ok(SeatConfig::default()),
+        }
+    }
+
+    /// Numbers of extra starting stones indexed by `Player::index`.
+    pub fn handicap(&self) -> Result<[usize; 3], TriversiError> {
+        self.handicap.as_deref().map_or(Ok([0; 3]), parse_handicap)
+    }
+
+    /// Writes a configuration file where every value is the commented-out built-in default.
+    pub fn write_template(path: &Path, force: bool) -> Result<()> {
+        if path.exists() && !force {
+            bail!("{} already exists.", path.display());
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(TriversiError::io(dir))?;
+        }
+        fs::write(path, Self::template()).map_err(TriversiError::io(path))?;
+        Ok(())
+    }
+
+    fn template() -> String {
+        let builtin = Self::builtin();
+        let string = |s: &str| toml::Value::String(s.to_owned()).to_string();
+        let mut text = String::new();
+        writeln!(text, "# Configuration of Triversi.").unwrap();
+        writeln!(
+            text,
+            "# Command line options take precedence over this file."
+        )
+        .unwrap();
+        writeln!(text).unwrap();
+        writeln!(
+            text,
+            "# Number of positions in one edge (>= 5 & = 0,2 (mod3))."
+        )
+        .unwrap();
+        writeln!(text, "# range = {}", builtin.range.unwrap()).unwrap();
+        writeln!(text, "# Distance between positions.").unwrap();
+        writeln!(text, "# distance = {}", builtin.distance.unwrap()).unwrap();
+        writeln!(text, "# Names of each player (delimiters are ',').").unwrap();
+        writeln!(
+            text,
+            "# player_names = {}",
+            string(builtin.player_names.as_ref().unwrap())
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Marks of each player in the ascii and compact styles (delimiters are ','); strings of 1 or 2 columns, e.g. \"()\", or glyph names (circle, square, triangle, diamond, star, or heart), the first characters of the names by default."
+        )
+        .unwrap();
+        writeln!(text, "# player_marks = \"C,M,Y\"").unwrap();
+        writeln!(
+            text,
+            "# Whether the marks of players are enclosed in (), [], and <> respectively (ascii style)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# distinct_shapes = {}",
+            builtin.distinct_shapes.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Colors of each player (delimiters are ','); names, hex codes like \"#ff8000\", or indices of 256 colors."
+        )
+        .unwrap();
+        writeln!(text, "# player_colors = \"cyan,magenta,yellow\"").unwrap();
+        writeln!(text, "# Style of the board (ascii, unicode, or compact).").unwrap();
+        writeln!(
+            text,
+            "# style = {}",
+            string(&value_name(builtin.style.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Stones of each player in the unicode style (delimiters are ',')."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# stones = {}",
+            string(builtin.stones.as_ref().unwrap())
+        )
+        .unwrap();
+        writeln!(text, "# Color theme (default, colorblind, or mono).").unwrap();
+        writeln!(
+            text,
+            "# theme = {}",
+            string(&value_name(builtin.theme.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Language of messages (en, ja, or a locale file ending with .toml), which follows LANG if it is not set."
+        )
+        .unwrap();
+        writeln!(text, "# lang = \"ja\"").unwrap();
+        writeln!(text, "# Whether frames are shown at startup.").unwrap();
+        writeln!(
+            text,
+            "# frame_visibility = {}",
+            builtin.frame_visibility.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Characters of frames in the ascii style (ascii, light, or heavy)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# frame_style = {}",
+            string(&value_name(builtin.frame_style.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Characters of the bottom, left, and right frames replacing those of frame_style (delimiters are ','), each of which takes one column."
+        )
+        .unwrap();
+        writeln!(text, "# frame_chars = {}", string("-,/,\\")).unwrap();
+        writeln!(
+            text,
+            "# Color of frames in the ascii style written like player_colors, which is dimmed."
+        )
+        .unwrap();
+        writeln!(text, "# frame_color = \"dark-gray\"").unwrap();
+        writeln!(
+            text,
+            "# Whether the corners of the board are tinted with the colors of players at startup."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# home_region_visibility = {}",
+            builtin.home_region_visibility.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether the board is turned upside down with its wide edge at the top at startup (ascii style)."
+        )
+        .unwrap();
+        writeln!(text, "# upside_down = {}", builtin.upside_down.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Whether the legend of marks and names of players is shown at startup."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# legend_visibility = {}",
+            builtin.legend_visibility.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether lines from a placed stone through the stones flipped by it are drawn at startup (ascii style)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# capture_line_visibility = {}",
+            builtin.capture_line_visibility.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether the stones flipped by the position under the cursor are listed in a side panel at startup."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# flip_panel_visibility = {}",
+            builtin.flip_panel_visibility.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether arrows point from the selectable position under the cursor toward the directions it captures."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# capture_arrows = {}",
+            builtin.capture_arrows.unwrap()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether the board is scrolled to keep the cursor in view when the cursor is moved."
+        )
+        .unwrap();
+        writeln!(text, "# auto_scroll = {}", builtin.auto_scroll.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Whether every move and skip is written to the message log in a sentence for screen readers."
+        )
+        .unwrap();
+        writeln!(text, "# announce = {}", builtin.announce.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Whether the description of the board written by a key reads out every row."
+        )
+        .unwrap();
+        writeln!(text, "# describe_rows = {}", builtin.describe_rows.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Feedback on an illegal selection, a skipped turn, and the end of the game (off, bell, or flash)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# notification = {}",
+            string(&value_name(builtin.notification.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Rule to resolve a tie (none, corners, first-move, or fewest-skips)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# tiebreak = {}",
+            string(&value_name(builtin.tiebreak.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# What the final score counts (stones, or territory adding empty regions bordered only by stones of the player)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# scoring = {}",
+            string(&value_name(builtin.scoring.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Seat (1, 2, or 3) moving first, or random chosen from the seed."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# first_player = {}",
+            string(&value_name(builtin.first_player.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Variant of the rules (standard, or swap where the second player may take over the seat of the first after its first move)."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# variant = {}",
+            string(&value_name(builtin.variant.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether turns of players who cannot select any position are skipped without a key."
+        )
+        .unwrap();
+        writeln!(text, "# auto_skip = {}", builtin.auto_skip.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Whether moves after a turn selected in the history are kept as a branch instead of being discarded."
+        )
+        .unwrap();
+        writeln!(text, "# keep_branches = {}", builtin.keep_branches.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Number of turns after which the game is finished with the stones on the board; no limit by default."
+        )
+        .unwrap();
+        writeln!(text, "# max_turns = 100").unwrap();
+        writeln!(
+            text,
+            "# Whether human players are told of a position flipping more stones after their moves."
+        )
+        .unwrap();
+        writeln!(text, "# coach = {}", builtin.coach.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Number of stones by which a position must flip more than the selected one to be told by the coach."
+        )
+        .unwrap();
+        writeln!(text, "# coach_margin = {}", builtin.coach_margin.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Controllers of the three seats (delimiters are ','), each of which is human, remote (with the host command), or a strategy of the computer."
+        )
+        .unwrap();
+        writeln!(text, "# seats = \"human,greedy,greedy\"").unwrap();
+        writeln!(
+            text,
+            "# Seat (1, 2, or 3) played by the human, where the others are played by the computer."
+        )
+        .unwrap();
+        writeln!(text, "# seat = 1").unwrap();
+        writeln!(
+            text,
+            "# Strategy of the computer used with seat and suggesting positions."
+        )
+        .unwrap();
+        writeln!(text, "# ai = {}", string(&value_name(builtin.ai.unwrap()))).unwrap();
+        writeln!(
+            text,
+            "# Number of entries of the transposition table of the max-n strategy (0 disables it)."
+        )
+        .unwrap();
+        writeln!(text, "# table_size = {}", builtin.table_size.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Number of threads of the max-n strategy, which selects the same position as one thread."
+        )
+        .unwrap();
+        writeln!(text, "# ai_threads = {}", builtin.ai_threads.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Time of each search of the max-n strategy in milliseconds, which deepens the search until it runs out; a fixed depth by default."
+        )
+        .unwrap();
+        writeln!(text, "# ai_time = 500").unwrap();
+        writeln!(
+            text,
+            "# How the max-n strategy values positions, where the weights of positional are in [eval_weights]."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# evaluation = {}",
+            string(&value_name(builtin.evaluation.unwrap()))
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Whether the max-n strategy selects positions in the built-in opening book without searching."
+        )
+        .unwrap();
+        writeln!(text, "# book = {}", builtin.book.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Whether stones are captured only horizontally and vertically."
+        )
+        .unwrap();
+        writeln!(text, "# no_diagonals = {}", builtin.no_diagonals.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# Numbers of extra starting stones of seats 2 and 3 (delimiters are ',')."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# handicap = {}",
+            string(builtin.handicap.as_ref().unwrap())
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# Seed of randomized components, which is chosen at random if not set."
+        )
+        .unwrap();
+        writeln!(text, "# seed = 0").unwrap();
+        writeln!(
+            text,
+            "# Directory where plain text dumps of the board are written."
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# dump_dir = {}",
+            string(&builtin.dump_dir.as_ref().unwrap().to_string_lossy())
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "# File where statistics of moves are written at the end of the game."
+        )
+        .unwrap();
+        writeln!(text, "# stats_csv = \"triversi-stats.csv\"").unwrap();
+        writeln!(
+            text,
+            "# File or FIFO where events of the game are written as JSON lines."
+        )
+        .unwrap();
+        writeln!(text, "# event_log = \"triversi-events.jsonl\"").unwrap();
+        writeln!(
+            text,
+            "# File where the game is archived as text at the end of the game."
+        )
+        .unwrap();
+        writeln!(text, "# export_game = \"game.tvr\"").unwrap();
+        writeln!(
+            text,
+            "# File where the screen is written as text when the game is finished, with colors if its extension is .ans or .ansi."
+        )
+        .unwrap();
+        writeln!(text, "# screenshot_on_finish = \"final.ans\"").unwrap();
+        writeln!(
+            text,
+            "# Whether the best score of a single game for each range is kept."
+        )
+        .unwrap();
+        writeln!(text, "# keep_records = {}", builtin.keep_records.unwrap()).unwrap();
+        writeln!(
+            text,
+            "# File where the best scores are kept (default: ~/.local/share/triversi/records.json)."
+        )
+        .unwrap();
+        writeln!(text, "# records = \"records.json\"").unwrap();
+        writeln!(text, "# Whether the debug panel is shown at startup.").unwrap();
+        writeln!(text, "# debug = {}", builtin.debug.unwrap()).unwrap();
+        writeln!(text).unwrap();
+        writeln!(
+            text,
+            "# Weights of stones on the corners and the edges, selectable positions, and stones in the positional evaluation."
+        )
+        .unwrap();
+        writeln!(text, "[eval_weights]").unwrap();
+        let weights = builtin.eval_weights.unwrap();
+        writeln!(text, "# corner = {}", weights.corner).unwrap();
+        writeln!(text, "# edge = {}", weights.edge).unwrap();
+        writeln!(text, "# mobility = {}", weights.mobility).unwrap();
+        writeln!(text, "# count = {}", weights.count).unwrap();
+        writeln!(text).unwrap();
+        writeln!(
+            text,
+            "# Percentages of the board filled with stones where the phased strategy stops selecting at random and starts searching by max-n."
+        )
+        .unwrap();
+        writeln!(text, "[phases]").unwrap();
+        let phases = builtin.phases.unwrap();
+        writeln!(text, "# midgame = {}", phases.midgame).unwrap();
+        writeln!(text, "# endgame = {}", phases.endgame).unwrap();
+        writeln!(text).unwrap();
+        writeln!(
+            text,
+            "# Keys are characters, or names like Enter, Esc, Up, Home, F1, Ctrl-c, and Alt-x."
+        )
+        .unwrap();
+        writeln!(text, "[key_binding]").unwrap();
+        let key_binding = KeyBinding::default();
+        for action in ACTIONS {
+            writeln!(
+                text,
+                "# {} = {}",
+                action.name(),
+                string(&key_binding.key_str(*action))
+            )
+            .unwrap();
+        }
+        text
+    }
+}
+
+fn value_name<T: ValueEnum>(value: T) -> String {
+    value.to_possible_value().unwrap().get_name().to_owned()
+}
+
+/// Numbers of extra stones of seats 2 and 3 like `1,2`, indexed by `Player::index`.
+fn parse_handicap(handicap: &str) -> Result<[usize; 3], TriversiError> {
+    let invalid = || TriversiError::InvalidHandicap(handicap.to_owned());
+    let extras = handicap
+        .split(',')
+        .map(|extra| extra.trim().parse::<usize>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    match extras[..] {
+        [second, third] => Ok([0, second, third]),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_too_small_for_marks_is_reported_with_other_errors() {
+        let config = Config::builtin().overridden_by(Config {
+            range: Some(7),
+            distance: Some(2),
+            player_marks: Some("██,A,B".to_owned()),
+            ..Config::default()
+        });
+        match config.validate() {
+            Err(TriversiError::MultipleInvalidArgs(errors)) => {
+                assert!(matches!(errors[0], TriversiError::InvalidBoardRange(7)));
+                assert!(matches!(
+                    errors[1],
+                    TriversiError::DistanceTooSmallForMarks(2, 2, 3)
+                ));
+                assert_eq!(errors.len(), 2);
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+    }
+
+    #[test]
+    fn distance_for_marks_follows_style_and_shapes() {
+        let config = Config::builtin().overridden_by(Config {
+            distance: Some(2),
+            distinct_shapes: Some(true),
+            ..Config::default()
+        });
+        assert!(matches!(
+            config.validate(),
+            Err(TriversiError::DistanceTooSmallForMarks(2, 3, 3))
+        ));
+        let compact = config.clone().overridden_by(Config {
+            style: Some(BoardStyle::Compact),
+            ..Config::default()
+        });
+        assert!(compact.validate().is_ok());
+        assert!(Config::builtin().validate().is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Line-based protocol for external programs, which is similar to GTP of Go.
+//!
+//! Each command is answered with `= ` followed by the result, or `? ` followed by an error,
+//! and the answer is terminated by an empty line.
+//! Players are given by their seats (1, 2, or 3) and positions are written as `x,y`.
+
+use crate::ai::{SearchConfig, StrategyKind};
+use crate::board::{Board, Move, Player, PLAYERS};
+use crate::game::{Game, GameResult};
+use crate::rng::{self, Component};
+use std::io::{self, BufRead, Write};
+
+/// Commands with their arguments and descriptions, which are shown by `help`.
+const COMMANDS: &[(&str, &str, &str)] = &[
+    (
+        "newgame",
+        "[range]",
+        "Starts a new game, keeping the range if not given and the handicap.",
+    ),
+    ("play", "<seat> <x,y|pass>", "Plays a move of the seat."),
+    (
+        "genmove",
+        "<seat>",
+        "Plays a move of the seat selected by the strategy and prints it.",
+    ),
+    ("legal", "<seat>", "Prints positions the seat can select."),
+    ("turn", "", "Prints the seat to move next."),
+    ("board", "", "Prints the board, where stones are the seats."),
+    ("score", "", "Prints the numbers of stones of the seats."),
+    (
+        "result",
+        "",
+        "Prints win and the seat of the winner, or draw and the seats sharing the best score.",
+    ),
+    ("undo", "", "Takes back the last move."),
+    ("help", "", "Prints this reference."),
+    ("quit", "", "Exits the engine."),
+];
+
+pub struct Engine {
+    game: Game,
+    strategy_kind: StrategyKind,
+    search_config: SearchConfig,
+    seed: u64,
+}
+
+impl Engine {
+    /// Engine starting with `board`, whose capture rules and handicap are kept by `newgame`.
+    pub fn new(
+        board: Board,
+        strategy_kind: StrategyKind,
+        search_config: SearchConfig,
+        seed: u64,
+    ) -> Self {
+        Self {
+            game: Game::new(board),
+            strategy_kind,
+            search_config,
+            seed,
+        }
+    }
+
+    /// Answers commands read from `input` until `quit` or the end of `input`.
+    pub fn run(&mut self, input: impl BufRead, output: &mut impl Write) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match self.execute(line) {
+                Ok(result) if result.is_empty() => writeln!(output, "=")?,
+                Ok(result) => writeln!(output, "= {}", result)?,
+                Err(error) => writeln!(output, "? {}", error)?,
+            }
+            writeln!(output)?;
+            output.flush()?;
+            if line == "quit" {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Result of a command, or an error message.
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args = words.collect::<Vec<_>>();
+        match (command, args.as_slice()) {
+            ("newgame", []) => self.new_game(self.game.board().range()),
+            ("newgame", [range]) => self.new_game(parse(range, "range")?),
+            ("play", [seat, player_move]) => {
+                let player = parse_seat(seat)?;
+                let player_move = Move::parse(player_move, self.game.board().range())
+                    .map_err(|error| error.to_string())?;
+                self.game
+                    .play(player, player_move)
+                    .map_err(|error| error.to_string())?;
+                Ok(String::new())
+            }
+            ("genmove", [seat]) => self.generate_move(parse_seat(seat)?),
+            ("legal", [seat]) => Ok(self
+                .game
+                .legal_positions(parse_seat(seat)?)
+                .into_iter()
+                .map(|position| Move::Put(position).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")),
+            ("turn", []) => Ok(self.game.current_player().to_string()),
+            ("board", []) => Ok(format!("\n{}", self.game.board()).trim_end().to_owned()),
+            ("score", []) => Ok(PLAYERS
+                .iter()
+                .map(|player| self.game.board().count()[*player].to_string())
+                .collect::<Vec<_>>()
+                .join(" ")),
+            ("result", []) => match self.game.result() {
+                Some(GameResult::Win { winner, .. }) => Ok(format!("win {}", winner)),
+                Some(GameResult::Draw { players, .. }) => Ok(format!(
+                    "draw {}",
+                    players
+                        .iter()
+                        .map(|player| player.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )),
+                None => Err("the game is not over".to_owned()),
+            },
+            ("undo", []) => {
+                if self.game.undo() {
+                    Ok(String::new())
+                } else {
+                    Err("no move to undo".to_owned())
+                }
+            }
+            ("help", []) => Ok(help()),
+            ("quit", []) => Ok(String::new()),
+            (command, _) if COMMANDS.iter().any(|(name, _, _)| *name == command) => {
+                Err(format!("invalid arguments of {}", command))
+            }
+            (command, _) => Err(format!("unknown command {}", command)),
+        }
+    }
+
+    fn new_game(&mut self, range: usize) -> Result<String, String> {
+        let capture_rules = self.game.board().capture_rules().clone();
+        let mut board = Board::try_new(range).map_err(|error| error.to_string())?;
+        board.set_capture_rules(capture_rules);
+        board
+            .try_set_handicap(self.game.board().handicap(), Player::default())
+            .map_err(|error| error.to_string())?;
+        self.game = Game::new(board);
+        Ok(String::new())
+    }
+
+    fn generate_move(&mut self, player: Player) -> Result<String, String> {
+        let rng = rng::child(self.seed, Component::Ai, self.game.move_count() as u64);
+        let player_move = match self.strategy_kind.strategy(rng, self.search_config).select(
+            self.game.board(),
+            &self.game.availables(),
+            player,
+        ) {
+            Some(position) => Move::Put(position),
+            None => Move::Pass,
+        };
+        self.game
+            .play(player, player_move)
+            .map_err(|error| error.to_string())?;
+        Ok(player_move.to_string())
+    }
+}
+
+fn parse(word: &str, name: &str) -> Result<usize, String> {
+    word.parse()
+        .map_err(|_| format!("{} is an invalid {}", word, name))
+}
+
+fn parse_seat(word: &str) -> Result<Player, String> {
+    Player::from_seat(parse(word, "seat")?).ok_or_else(|| format!("{} is an invalid seat", word))
+}
+
+fn help() -> String {
+    let mut text = String::new();
+    for (name, args, description) in COMMANDS {
+        let usage = format!("{} {}", name, args);
+        text.push_str(&format!("\n{:<26}{}", usage.trim_end(), description));
+    }
+    text
+}
@@ -3,16 +3,243 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+use std::io;
+use std::path::Path;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum TriversiError {
     #[error("{0} is invalid board range.")]
     InvalidBoardRange(usize),
+    #[error("The opening of range {0} is invalid: {1}.")]
+    InvalidOpening(usize, String),
     #[error("{0} is invalid distance.")]
     InvalidBoardDistance(usize),
     #[error("{0} is an invalid string to get player marks.")]
     InvalidStringForPlayerMarks(String),
+    #[error("Player mark {0} takes {1} columns, but a mark must take 1 or 2 columns.")]
+    PlayerMarkTooWide(String, usize),
+    #[error("Distance {0} is too small for player marks taking {1} columns, which need distance {2} or more.")]
+    DistanceTooSmallForMarks(usize, usize, usize),
     #[error("{0} is an invalid string to get player names.")]
     InvalidStringForPlayerNames(String),
+    #[error("{0} is an invalid string to get stone marks.")]
+    InvalidStringForStoneMarks(String),
+    #[error("{0} is an invalid color, which is a name like cyan or light-red, #rrggbb, or an index from 0 to 255.")]
+    InvalidColor(String),
+    #[error("{0} is an invalid string to get player colors.")]
+    InvalidStringForPlayerColors(String),
+    #[error("{0} is an invalid string to get frame characters, which are three characters of one column, e.g. -,/,\\.")]
+    InvalidStringForFrameChars(String),
+    #[error("{0} is an invalid string to get controllers of seats.")]
+    InvalidStringForSeats(String),
+    #[error("{0} is an invalid string to get weights of the evaluation, e.g. corner=10,edge=3,mobility=1,count=1.")]
+    InvalidStringForWeights(String),
+    #[error("{0} is an invalid string to get a strategy to simulate, which is a strategy followed by parameters, e.g. max-n:evaluation=count or phased:corner=20,endgame=60.")]
+    InvalidStringForContestant(String),
+    #[error("{0} is an invalid seat.")]
+    InvalidSeat(usize),
+    #[error("Phases beginning the midgame at {0}% and the endgame at {1}% are invalid, which must be in order and at most 100%.")]
+    InvalidPhases(u64, u64),
+    #[error("{0} is an invalid number of threads, which is at least 1.")]
+    InvalidAiThreads(usize),
+    #[error("{0} is an invalid limit of turns, which is at least 1.")]
+    InvalidMaxTurns(usize),
+    #[error("{0} is an unknown language, which is en, ja, or a locale file ending with .toml.")]
+    UnknownLanguage(String),
+    #[error("{0} is an invalid name of a slot, which is up to 32 letters, digits, - and _.")]
+    InvalidSlotName(String),
+    #[error(
+        "No game is saved in the slot {0} of {1}, whose slots are listed by the saves command."
+    )]
+    UnknownSlot(String, String),
+    #[error("{0} is an invalid key.")]
+    InvalidKey(String),
+    #[error("{0} is assigned to both {1} and {2}.")]
+    DuplicatedKey(String, String, String),
+    #[error("{0} is not a move, which is written as x,y or pass.")]
+    InvalidMove(String),
+    #[error("{0} is an invalid position, {1}.")]
+    InvalidPosition(String, String),
+    #[error(
+        "({0}, {1}) is outside the board of range {2}, whose positions are x,y with x <= y < {2}."
+    )]
+    PositionOutOfRange(usize, usize, usize),
+    #[error("({0}, {1}) is already occupied by Player-{2}.")]
+    PositionOccupied(usize, usize, String),
+    #[error("Line {0}: {1}")]
+    InvalidMoveLine(usize, Box<TriversiError>),
+    #[error("({}, {}) cannot be selected by Player-{player}, which flips no stones there.", .position.0, .position.1)]
+    IllegalMove {
+        player: String,
+        position: (usize, usize),
+    },
+    #[error("Player-{0} cannot pass, which can select a position.")]
+    IllegalPass(String),
+    #[error("Player-{0} has to pass, which cannot select any position.")]
+    MustPass(String),
+    #[error("It is the turn of Player-{1}, not Player-{0}.")]
+    NotPlayersTurn(String, String),
+    #[error("The game is finished, where no move can be played.")]
+    GameFinished,
+    #[error("Line {0}: {1} is an illegal move. {2}")]
+    IllegalMoveLine(usize, String, Box<TriversiError>),
+    #[error("Turn {turn} of the record, ({}, {}) of Player-{player}, is an illegal move on the replayed board.", .position.0, .position.1)]
+    IllegalMoveInReplay {
+        /// Number of the recorded move from 1, which excludes skipped turns as turns of game events.
+        turn: usize,
+        player: String,
+        position: (usize, usize),
+    },
+    #[error("Turn {turn} of the record is invalid: {reason}.")]
+    RecordParse {
+        /// Number of the recorded move from 1, which excludes skipped turns as turns of game events,
+        /// where a skipped turn has the number of the move before it.
+        turn: usize,
+        reason: String,
+    },
+    #[error("The record has {0} names or marks of players, which must be 3.")]
+    InvalidRecordPlayers(usize),
+    #[error("The game is recorded by Triversi {0}, which cannot be read by this version {1}.")]
+    IncompatibleVersion(String, String),
+    #[error("{0} is a variant unsupported by this version of Triversi, which supports standard and swap.")]
+    UnsupportedVariant(String),
+    #[error("{0} is an invalid position, which is written as rows of .123 separated by / and the seat to move, e.g. ./../.../.12./.312. 1")]
+    InvalidFen(String),
+    #[error("{0} is an invalid code of a board, which is given by the key to copy the code.")]
+    InvalidCode(String),
+    #[error("Line {0}: {1} is not a tag or a move of the game archive.")]
+    InvalidArchiveLine(usize, String),
+    #[error("The game archive has no {0} tag.")]
+    MissingArchiveTag(String),
+    #[error("{0} is an invalid handicap, which is the numbers of extra stones of seats 2 and 3, e.g. 1,2.")]
+    InvalidHandicap(String),
+    #[error("Seat {0} can have at most {1} extra stones on this board.")]
+    HandicapTooLarge(usize, usize),
+    #[error("Extra stones overlap the opening or leave no position for seat {0} to select first.")]
+    HandicapBlocksOpening(usize),
+    #[error("Turn {0} is beyond the game, which has {1} moves.")]
+    TurnBeyondGame(usize, usize),
+    #[error("{0} is not a position selectable in the puzzle, which is written as x,y.")]
+    InvalidPuzzleSolution(String),
+    #[error("The puzzle has no solution.")]
+    NoPuzzleSolution,
+    #[error("No seat is played remotely by the host, e.g. --seats human,remote,remote.")]
+    NoRemoteSeat,
+    #[error("Seats played remotely require the host command.")]
+    RemoteSeatWithoutHost,
+    #[error("Connection is rejected: {0}.")]
+    ConnectionRejected(String),
+    #[error("Cannot access {0}: {1}.")]
+    Io(String, io::Error),
+    #[error("{}", format_errors(.0))]
+    MultipleInvalidArgs(Vec<TriversiError>),
+}
+
+impl TriversiError {
+    /// Converter of an error of reading or writing `path` into `Io`, e.g. `.map_err(TriversiError::io(path))`.
+    pub fn io(path: &Path) -> impl FnOnce(io::Error) -> Self + '_ {
+        move |error| Self::Io(path.display().to_string(), error)
+    }
+}
+
+fn format_errors(errors: &[TriversiError]) -> String {
+    let mut text = format!("{} arguments are invalid.", errors.len());
+    for error in errors {
+        text.push_str("\n  ");
+        text.push_str(&error.to_string());
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::color_config::parse_color;
+    use crate::board::{Board, Move, Player};
+    use crate::config::Config;
+    use crate::game::Game;
+    use std::env;
+
+    fn message<T: std::fmt::Debug>(result: Result<T, TriversiError>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn illegal_moves_tell_the_position_and_the_player() {
+        let mut board = Board::try_new(5).unwrap();
+        assert_eq!(
+            message(board.try_apply_move(Player::Zero, (3, 2))),
+            "(3, 2) is outside the board of range 5, whose positions are x,y with x <= y < 5."
+        );
+        assert_eq!(
+            message(board.try_apply_move(Player::Zero, (0, 5))),
+            "(0, 5) is outside the board of range 5, whose positions are x,y with x <= y < 5."
+        );
+        assert_eq!(
+            message(board.try_apply_move(Player::Zero, (1, 2))),
+            "(1, 2) is already occupied by Player-1."
+        );
+        let mut board = Board::try_new(8).unwrap();
+        assert_eq!(
+            message(board.try_apply_move(Player::One, (0, 0))),
+            "(0, 0) cannot be selected by Player-2, which flips no stones there."
+        );
+    }
+
+    #[test]
+    fn moves_out_of_turn_tell_the_players() {
+        let mut game = Game::new(Board::try_new(5).unwrap());
+        assert_eq!(
+            message(game.play(Player::One, Move::Put((0, 0)))),
+            "It is the turn of Player-1, not Player-2."
+        );
+        assert_eq!(
+            message(game.play(Player::Zero, Move::Pass)),
+            "Player-1 cannot pass, which can select a position."
+        );
+        for (player, player_move) in [
+            (Player::Zero, Move::Put((0, 4))),
+            (Player::One, Move::Put((0, 0))),
+            (Player::Two, Move::Pass),
+            (Player::Zero, Move::Put((4, 4))),
+        ] {
+            game.play(player, player_move).unwrap();
+        }
+        assert_eq!(
+            message(game.play(Player::One, Move::Pass)),
+            "The game is finished, where no move can be played."
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_quoted() {
+        assert_eq!(
+            message(parse_color("purple")),
+            "purple is an invalid color, which is a name like cyan or light-red, #rrggbb, or an index from 0 to 255."
+        );
+        assert_eq!(message(Board::try_new(7)), "7 is invalid board range.");
+        let error = TriversiError::MultipleInvalidArgs(vec![
+            TriversiError::InvalidBoardRange(7),
+            TriversiError::InvalidBoardDistance(1),
+        ]);
+        assert_eq!(
+            error.to_string(),
+            "2 arguments are invalid.\n  7 is invalid board range.\n  1 is invalid distance."
+        );
+    }
+
+    #[test]
+    fn failed_access_tells_the_path() {
+        let path = env::temp_dir()
+            .join("triversi-missing-dir")
+            .join("config.toml");
+        let error = Config::load(Some(&path)).unwrap_err();
+        let prefix = format!("Cannot access {}: ", path.display());
+        assert!(error.to_string().starts_with(&prefix), "{}", error);
+        assert!(matches!(
+            error.downcast_ref::<TriversiError>(),
+            Some(TriversiError::Io(..))
+        ));
+    }
 }
@@ -0,0 +1,307 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Game without a terminal, which keeps the turn order and the moves to undo.
+
+use crate::board::{Availables, Board, Count, Move, MoveUndo, Player, Tiebreak, PLAYERS};
+use crate::error::TriversiError;
+use getset::{CopyGetters, Getters};
+
+/// Outcome of a finished game with the final scores and the number of moves including passes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GameResult {
+    Win {
+        winner: Player,
+        scores: Count,
+        turns: usize,
+    },
+    /// Players sharing the best score.
+    Draw {
+        players: Vec<Player>,
+        scores: Count,
+        turns: usize,
+    },
+}
+
+#[derive(Clone, Debug, CopyGetters, Getters)]
+pub struct Game {
+    #[getset(get = "pub")]
+    board: Board,
+    /// Player to move next.
+    #[getset(get_copy = "pub")]
+    current_player: Player,
+    /// Moves played so far, where `None` is a pass.
+    undos: Vec<(Player, Option<MoveUndo>)>,
+}
+
+impl Game {
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            current_player: Player::default(),
+            undos: Vec::new(),
+        }
+    }
+
+    /// Number of moves played so far including passes.
+    pub fn move_count(&self) -> usize {
+        self.undos.len()
+    }
+
+    /// Positions `player` can select in the order of rows.
+    pub fn legal_positions(&self, player: Player) -> Vec<(usize, usize)> {
+        self.board.selectable_positions(player)
+    }
+
+    pub fn availables(&self) -> Availables {
+        let mut availables = Availables::default();
+        self.board.update_availables(&mut availables);
+        availables
+    }
+
+    /// Whether no player can select any position.
+    pub fn is_over(&self) -> bool {
+        PLAYERS
+            .iter()
+            .all(|player| self.legal_positions(*player).is_empty())
+    }
+
+    /// Result of the game without a tiebreak, or `None` if the game is not over.
+    pub fn result(&self) -> Option<GameResult> {
+        if !self.is_over() {
+            return None;
+        }
+        let scores = self.board.scores();
+        let turns = self.move_count();
+        let mut leaders = self
+            .board
+            .leaders(Tiebreak::None, &Count::default(), Player::default());
+        Some(if leaders.len() == 1 {
+            GameResult::Win {
+                winner: leaders.pop().unwrap(),
+                scores,
+                turns,
+            }
+        } else {
+            GameResult::Draw {
+                players: leaders,
+                scores,
+                turns,
+            }
+        })
+    }
+
+    /// Plays a move of `player` and returns the flipped positions,
+    /// or the error of the illegal move without changing the game.
+    /// A pass is legal only if `player` cannot select any position.
+    pub fn play(
+        &mut self,
+        player: Player,
+        player_move: Move,
+    ) -> Result<Vec<(usize, usize)>, TriversiError> {
+        if self.is_over() {
+            return Err(TriversiError::GameFinished);
+        }
+        if player != self.current_player {
+            return Err(TriversiError::NotPlayersTurn(
+                player.to_string(),
+                self.current_player.to_string(),
+            ));
+        }
+        let undo = match player_move {
+            Move::Put(position) => Some(self.board.try_apply_move(player, position)?),
+            Move::Pass if self.legal_positions(player).is_empty() => None,
+            Move::Pass => return Err(TriversiError::IllegalPass(player.to_string())),
+        };
+        let flipped = undo.as_ref().map_or_else(Vec::new, |undo| {
+            undo.flipped()
+                .iter()
+                .map(|(position, _)| *position)
+                .collect()
+        });
+        self.undos.push((player, undo));
+        self.current_player.advance();
+        Ok(flipped)
+    }
+
+    /// Number of sequences of `depth` moves from the current position, where a pass is a move
+    /// of a player who cannot select any position and a finished game has no following moves.
+    /// Moves are played and taken back on this game without cloning the board.
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        if self.is_over() {
+            return 0;
+        }
+        let player = self.current_player;
+        let positions = self.legal_positions(player);
+        let moves = if positions.is_empty() {
+            vec![Move::Pass]
+        } else {
+            positions.into_iter().map(Move::Put).collect()
+        };
+        let mut nodes = 0;
+        for player_move in moves {
+            self.play(player, player_move).unwrap();
+            nodes += self.perft(depth - 1);
+            self.undo();
+        }
+        nodes
+    }
+
+    /// Takes back the last move, or returns false if no move has been played.
+    pub fn undo(&mut self) -> bool {
+        match self.undos.pop() {
+            Some((player, undo)) => {
+                if let Some(undo) = undo {
+                    self.board.undo_move(undo);
+                }
+                self.current_player = player;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::parse_fen;
+
+    /// Game on a board of `range` after `moves` played in turn order.
+    fn scripted_game(range: usize, moves: &[Move]) -> Game {
+        let mut game = Game::new(Board::try_new(range).unwrap());
+        for player_move in moves {
+            assert_eq!(game.result(), None);
+            game.play(game.current_player(), *player_move).unwrap();
+        }
+        game
+    }
+
+    /// Game from a position written by `parse_fen`.
+    fn game_from_fen(fen: &str) -> Game {
+        let (board, player) = parse_fen(fen).unwrap();
+        let mut game = Game::new(board);
+        game.current_player = player;
+        game
+    }
+
+    fn scores(counts: [u64; 3]) -> Count {
+        let mut scores = Count::default();
+        for (player, count) in PLAYERS.iter().zip(counts) {
+            scores.add(*player, count);
+        }
+        scores
+    }
+
+    #[test]
+    fn result_counts_moves_and_passes() {
+        let game = scripted_game(
+            6,
+            &[
+                Move::Put((0, 1)),
+                Move::Put((1, 1)),
+                Move::Put((0, 0)),
+                Move::Put((0, 4)),
+                Move::Put((1, 5)),
+                Move::Put((4, 4)),
+                Move::Put((4, 5)),
+                Move::Put((5, 5)),
+                Move::Put((0, 5)),
+            ],
+        );
+        assert_eq!(
+            game.result(),
+            Some(GameResult::Win {
+                winner: Player::Two,
+                scores: scores([4, 7, 10]),
+                turns: 9,
+            })
+        );
+
+        // The pass of the third player is a turn.
+        let game = scripted_game(
+            5,
+            &[
+                Move::Put((0, 4)),
+                Move::Put((0, 0)),
+                Move::Pass,
+                Move::Put((4, 4)),
+            ],
+        );
+        assert_eq!(
+            game.result(),
+            Some(GameResult::Win {
+                winner: Player::Zero,
+                scores: scores([8, 5, 2]),
+                turns: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn result_of_tied_players_is_a_draw() {
+        assert_eq!(
+            game_from_fen("1/23/123/1231/23123 1").result(),
+            Some(GameResult::Draw {
+                players: PLAYERS.to_vec(),
+                scores: scores([5, 5, 5]),
+                turns: 0,
+            })
+        );
+        assert_eq!(
+            game_from_fen("1/23/123/1212/12123 2").result(),
+            Some(GameResult::Draw {
+                players: vec![Player::Zero, Player::One],
+                scores: scores([6, 6, 3]),
+                turns: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn illegal_moves_do_not_change_the_game() {
+        let mut game = Game::new(Board::try_new(5).unwrap());
+        assert!(matches!(
+            game.play(Player::One, Move::Put((0, 4))),
+            Err(TriversiError::NotPlayersTurn(..))
+        ));
+        assert!(matches!(
+            game.play(Player::Zero, Move::Pass),
+            Err(TriversiError::IllegalPass(_))
+        ));
+        assert!(game.play(Player::Zero, Move::Put((1, 1))).is_err());
+        assert_eq!(game.move_count(), 0);
+
+        let mut game = game_from_fen("1/23/123/1231/23123 1");
+        assert!(matches!(
+            game.play(Player::Zero, Move::Pass),
+            Err(TriversiError::GameFinished)
+        ));
+    }
+
+    #[test]
+    fn perft_counts_are_kept() {
+        // Counts in the table of the README, where deeper counts take long without optimization.
+        for (range, counts) in [(5, &[3, 5, 4, 2, 0, 0][..]), (8, &[13, 138, 1188, 9549])] {
+            let mut game = Game::new(Board::try_new(range).unwrap());
+            let hash = game.board().hash();
+            for (depth, count) in counts.iter().enumerate() {
+                assert_eq!(
+                    game.perft(depth + 1),
+                    *count,
+                    "range {} depth {}",
+                    range,
+                    depth + 1
+                );
+            }
+            assert_eq!(game.perft(0), 1);
+            assert_eq!(game.move_count(), 0);
+            assert_eq!(game.board().hash(), hash);
+        }
+    }
+}
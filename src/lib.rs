@@ -3,7 +3,17 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+pub mod ai;
+pub mod analysis;
 pub mod app;
 pub mod board;
 pub mod cli;
+pub mod config;
+pub mod engine;
 pub mod error;
+pub mod game;
+pub mod net;
+pub mod puzzle;
+pub mod rng;
+pub mod simulation;
+pub mod util;
@@ -0,0 +1,64 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Network play over TCP, where the host owns the game and clients play remote seats.
+
+pub mod client;
+pub mod host;
+pub mod protocol;
+
+pub use client::Client;
+pub use host::Host;
+pub use protocol::{AppliedMove, GameRules, PROTOCOL_VERSION};
+
+use crate::board::{Move, Player};
+
+/// Role of this terminal in a network game.
+#[derive(Debug)]
+pub enum Network {
+    Host(Host),
+    Client(Client),
+}
+
+/// Event received from the network.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NetEvent {
+    /// The player has connected or reconnected.
+    Joined(Player),
+    /// The player has disconnected, which pauses the game.
+    Left(Player),
+    /// Move requested by a client, which is checked by the host.
+    Requested(Player, Move),
+    /// Move applied by the host.
+    Applied(AppliedMove),
+    /// Connection to the host is lost.
+    Lost,
+}
+
+impl Network {
+    /// Next event if any, which never blocks.
+    pub fn poll(&mut self) -> Option<NetEvent> {
+        match self {
+            Network::Host(host) => host.poll(),
+            Network::Client(client) => client.poll(),
+        }
+    }
+
+    /// Whether the player is played on another terminal which is not connected now.
+    pub fn is_offline(&self, player: Player) -> bool {
+        match self {
+            Network::Host(host) => host.is_offline(player),
+            Network::Client(client) => client.is_offline(player),
+        }
+    }
+
+    /// Whether the game is paused because some players are offline.
+    pub fn is_paused(&self) -> bool {
+        match self {
+            Network::Host(host) => host.is_paused(),
+            Network::Client(client) => client.is_paused(),
+        }
+    }
+}
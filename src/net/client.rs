@@ -0,0 +1,127 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::{Move, Player};
+use crate::error::TriversiError;
+use crate::net::protocol::{self, ClientMessage, ServerMessage};
+use crate::net::{GameRules, NetEvent, PROTOCOL_VERSION};
+use anyhow::{Context, Result};
+use getset::CopyGetters;
+use std::io::{self, BufReader};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Terminal playing one seat of a game owned by the host.
+#[derive(Debug, CopyGetters)]
+pub struct Client {
+    /// Player of the seat played on this terminal.
+    #[getset(get_copy = "pub")]
+    player: Player,
+    stream: TcpStream,
+    /// Messages from the host, where `None` means the connection is lost.
+    receiver: Receiver<Option<ServerMessage>>,
+    offline_players: Vec<Player>,
+    is_connected: bool,
+}
+
+impl Client {
+    /// Joins the game of the host at `address` to play the seat `seat` (1, 2, or 3),
+    /// and returns the rules of the game and the moves so far as well.
+    pub fn connect(address: &str, seat: usize) -> Result<(Self, GameRules, Vec<Move>)> {
+        let mut stream = TcpStream::connect(address)
+            .with_context(|| format!("cannot connect to {}", address))?;
+        protocol::send(
+            &mut stream,
+            &ClientMessage::Hello {
+                version: PROTOCOL_VERSION,
+                seat,
+            },
+        )?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let (player, rules, moves, offline_players) = match protocol::receive(&mut reader)? {
+            Some(ServerMessage::Welcome {
+                version,
+                player,
+                rules,
+                moves,
+                offline_players,
+            }) if version == PROTOCOL_VERSION => (player, rules, moves, offline_players),
+            Some(ServerMessage::Welcome { version, .. }) => {
+                return Err(TriversiError::ConnectionRejected(format!(
+                    "protocol version {} of the host is not supported",
+                    version
+                ))
+                .into())
+            }
+            Some(ServerMessage::Rejected { reason }) => {
+                return Err(TriversiError::ConnectionRejected(reason).into())
+            }
+            _ => {
+                return Err(
+                    TriversiError::ConnectionRejected("the host does not reply".to_owned()).into(),
+                )
+            }
+        };
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(Some(message)) = protocol::receive(&mut reader) {
+                if sender.send(Some(message)).is_err() {
+                    return;
+                }
+            }
+            let _ = sender.send(None);
+        });
+        let client = Self {
+            player,
+            stream,
+            receiver,
+            offline_players,
+            is_connected: true,
+        };
+        Ok((client, rules, moves))
+    }
+
+    pub fn poll(&mut self) -> Option<NetEvent> {
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                Some(ServerMessage::Applied(applied_move)) => {
+                    return Some(NetEvent::Applied(applied_move))
+                }
+                Some(ServerMessage::Paused { player }) => {
+                    self.offline_players.push(player);
+                    return Some(NetEvent::Left(player));
+                }
+                Some(ServerMessage::Resumed { player }) => {
+                    self.offline_players.retain(|offline| *offline != player);
+                    return Some(NetEvent::Joined(player));
+                }
+                Some(_) => (),
+                None => {
+                    self.is_connected = false;
+                    return Some(NetEvent::Lost);
+                }
+            }
+        }
+        None
+    }
+
+    /// Sends a move of the seat of this terminal, which is applied when the host broadcasts it.
+    pub fn send_move(&mut self, player_move: Move) -> io::Result<()> {
+        protocol::send(&mut self.stream, &ClientMessage::Move { player_move })
+    }
+
+    pub fn is_offline(&self, player: Player) -> bool {
+        if self.is_connected {
+            self.offline_players.contains(&player)
+        } else {
+            player != self.player
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !self.is_connected || !self.offline_players.is_empty()
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::board::{Move, Player};
+use crate::net::protocol::{self, ClientMessage, ServerMessage};
+use crate::net::{AppliedMove, GameRules, NetEvent, PROTOCOL_VERSION};
+use std::collections::HashMap;
+use std::io::{self, BufReader};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Authoritative side of a network game, which accepts clients for `remote_players`
+/// and broadcasts every applied move to them.
+#[derive(Debug)]
+pub struct Host {
+    rules: GameRules,
+    remote_players: Vec<Player>,
+    connections: HashMap<Player, Connection>,
+    /// Applied moves including passes, which are sent to (re)joining clients.
+    moves: Vec<Move>,
+    receiver: Receiver<Incoming>,
+}
+
+#[derive(Debug)]
+struct Connection {
+    id: usize,
+    stream: TcpStream,
+}
+
+/// Messages from threads reading connections, which are identified by numbers.
+#[derive(Debug)]
+enum Incoming {
+    Hello {
+        id: usize,
+        stream: TcpStream,
+        version: u32,
+        seat: usize,
+    },
+    Message {
+        id: usize,
+        message: ClientMessage,
+    },
+    Closed {
+        id: usize,
+    },
+}
+
+impl Host {
+    pub fn bind(port: u16, remote_players: Vec<Player>, rules: GameRules) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (id, stream) in listener.incoming().enumerate() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let sender = sender.clone();
+                thread::spawn(move || read_connection(id, stream, sender));
+            }
+        });
+        Ok(Self {
+            rules,
+            remote_players,
+            connections: HashMap::new(),
+            moves: Vec::new(),
+            receiver,
+        })
+    }
+
+    pub fn poll(&mut self) -> Option<NetEvent> {
+        while let Ok(incoming) = self.receiver.try_recv() {
+            match incoming {
+                Incoming::Hello {
+                    id,
+                    mut stream,
+                    version,
+                    seat,
+                } => match self.check_hello(version, seat) {
+                    Ok(player) => {
+                        let welcome = ServerMessage::Welcome {
+                            version: PROTOCOL_VERSION,
+                            player,
+                            rules: self.rules,
+                            moves: self.moves.clone(),
+                            offline_players: self.offline_players(),
+                        };
+                        if protocol::send(&mut stream, &welcome).is_err() {
+                            continue;
+                        }
+                        self.broadcast(&ServerMessage::Resumed { player });
+                        self.connections.insert(player, Connection { id, stream });
+                        return Some(NetEvent::Joined(player));
+                    }
+                    Err(reason) => {
+                        let _ = protocol::send(&mut stream, &ServerMessage::Rejected { reason });
+                        let _ = stream.shutdown(Shutdown::Both);
+                    }
+                },
+                Incoming::Message {
+                    id,
+                    message: ClientMessage::Move { player_move },
+                } => {
+                    if let Some(player) = self.player_of(id) {
+                        return Some(NetEvent::Requested(player, player_move));
+                    }
+                }
+                Incoming::Message { .. } => (),
+                Incoming::Closed { id } => {
+                    if let Some(player) = self.player_of(id) {
+                        self.connections.remove(&player);
+                        self.broadcast(&ServerMessage::Paused { player });
+                        return Some(NetEvent::Left(player));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Records `applied_move` and sends it to all clients.
+    pub fn broadcast_move(&mut self, applied_move: AppliedMove) {
+        self.moves.push(applied_move.player_move);
+        self.broadcast(&ServerMessage::Applied(applied_move));
+    }
+
+    pub fn is_offline(&self, player: Player) -> bool {
+        self.remote_players.contains(&player) && !self.connections.contains_key(&player)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !self.offline_players().is_empty()
+    }
+
+    fn offline_players(&self) -> Vec<Player> {
+        self.remote_players
+            .iter()
+            .copied()
+            .filter(|player| self.is_offline(*player))
+            .collect()
+    }
+
+    fn check_hello(&self, version: u32, seat: usize) -> Result<Player, String> {
+        if version != PROTOCOL_VERSION {
+            return Err(format!(
+                "protocol version {} is not supported by the host using version {}",
+                version, PROTOCOL_VERSION
+            ));
+        }
+        let player =
+            Player::from_seat(seat).ok_or_else(|| format!("{} is an invalid seat", seat))?;
+        if !self.remote_players.contains(&player) {
+            return Err(format!("seat {} is not played remotely", seat));
+        }
+        if self.connections.contains_key(&player) {
+            return Err(format!("seat {} is already taken", seat));
+        }
+        Ok(player)
+    }
+
+    fn player_of(&self, id: usize) -> Option<Player> {
+        self.connections
+            .iter()
+            .find(|(_, connection)| connection.id == id)
+            .map(|(player, _)| *player)
+    }
+
+    /// Sends `message` to all clients, where failures are reported later as closed connections.
+    fn broadcast(&mut self, message: &ServerMessage) {
+        for connection in self.connections.values_mut() {
+            let _ = protocol::send(&mut connection.stream, message);
+        }
+    }
+}
+
+/// Forwards messages of a connection until it is closed.
+fn read_connection(id: usize, stream: TcpStream, sender: Sender<Incoming>) {
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(stream);
+    if let Ok(Some(ClientMessage::Hello { version, seat })) = protocol::receive(&mut reader) {
+        let hello = Incoming::Hello {
+            id,
+            stream: writer,
+            version,
+            seat,
+        };
+        if sender.send(hello).is_err() {
+            return;
+        }
+        while let Ok(Some(message)) = protocol::receive(&mut reader) {
+            if sender.send(Incoming::Message { id, message }).is_err() {
+                return;
+            }
+        }
+    }
+    let _ = sender.send(Incoming::Closed { id });
+}
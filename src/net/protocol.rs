@@ -0,0 +1,217 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Messages between the host and clients, which are written as one JSON object per line.
+
+use crate::board::{Move, Player, Scoring, Tiebreak};
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use serde_derive::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+/// Version of the protocol, which has to be the same between the host and clients.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Rules of the game decided by the host.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GameRules {
+    pub range: usize,
+    pub no_diagonals: bool,
+    pub auto_skip: bool,
+    pub tiebreak: Tiebreak,
+    /// Numbers of extra starting stones indexed by `Player::index`.
+    #[serde(default)]
+    pub handicap: [usize; 3],
+    #[serde(default)]
+    pub first_player: Player,
+    #[serde(default)]
+    pub scoring: Scoring,
+}
+
+/// Move applied by the host.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AppliedMove {
+    pub player: Player,
+    pub player_move: Move,
+    /// Positions flipped by the move, excluding the position where the stone is placed.
+    pub flipped: Vec<(usize, usize)>,
+    /// Player whose turn it is after the move.
+    pub next_player: Player,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// First message of a connection to play the seat `seat` (1, 2, or 3).
+    Hello { version: u32, seat: usize },
+    /// Move of the seat of the client.
+    Move { player_move: Move },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Reply to `Hello` with the moves so far, which also serves rejoining clients.
+    Welcome {
+        version: u32,
+        player: Player,
+        rules: GameRules,
+        moves: Vec<Move>,
+        offline_players: Vec<Player>,
+    },
+    /// Reply to `Hello` refusing the connection.
+    Rejected {
+        reason: String,
+    },
+    Applied(AppliedMove),
+    /// The game is paused until the player reconnects.
+    Paused {
+        player: Player,
+    },
+    Resumed {
+        player: Player,
+    },
+}
+
+/// Writes `message` as one line.
+pub fn send<T: Serialize>(writer: &mut impl Write, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
+
+/// Reads a message of one line, or `None` at the end of the stream.
+pub fn receive<T: DeserializeOwned>(reader: &mut impl BufRead) -> io::Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> GameRules {
+        GameRules {
+            range: 14,
+            no_diagonals: true,
+            auto_skip: false,
+            tiebreak: Tiebreak::Corners,
+            handicap: [0, 1, 2],
+            first_player: Player::Two,
+            scoring: Scoring::Territory,
+        }
+    }
+
+    /// Messages sent one per line and received back in the same order.
+    fn round_trip<T>(messages: &[T])
+    where
+        T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let mut wire = Vec::new();
+        for message in messages {
+            send(&mut wire, message).unwrap();
+        }
+        assert_eq!(
+            wire.iter().filter(|&&byte| byte == b'\n').count(),
+            messages.len()
+        );
+        let mut reader = wire.as_slice();
+        for message in messages {
+            assert_eq!(receive::<T>(&mut reader).unwrap().as_ref(), Some(message));
+        }
+        assert_eq!(receive::<T>(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn client_messages_round_trip() {
+        round_trip(&[
+            ClientMessage::Hello {
+                version: PROTOCOL_VERSION,
+                seat: 2,
+            },
+            ClientMessage::Move {
+                player_move: Move::Put((3, 7)),
+            },
+            ClientMessage::Move {
+                player_move: Move::Pass,
+            },
+        ]);
+    }
+
+    #[test]
+    fn server_messages_round_trip() {
+        round_trip(&[
+            ServerMessage::Welcome {
+                version: PROTOCOL_VERSION,
+                player: Player::One,
+                rules: rules(),
+                moves: vec![Move::Put((1, 4)), Move::Pass],
+                offline_players: vec![Player::Two],
+            },
+            ServerMessage::Rejected {
+                reason: "seat 2 is taken".to_owned(),
+            },
+            ServerMessage::Applied(AppliedMove {
+                player: Player::Zero,
+                player_move: Move::Put((2, 5)),
+                flipped: vec![(2, 4), (3, 5)],
+                next_player: Player::One,
+            }),
+            ServerMessage::Paused {
+                player: Player::Two,
+            },
+            ServerMessage::Resumed {
+                player: Player::Two,
+            },
+        ]);
+    }
+
+    /// The wire format is fixed by the protocol version, so a change of it needs a new version.
+    #[test]
+    fn messages_are_tagged_json_lines() {
+        let mut wire = Vec::new();
+        send(
+            &mut wire,
+            &ClientMessage::Hello {
+                version: PROTOCOL_VERSION,
+                seat: 3,
+            },
+        )
+        .unwrap();
+        send(
+            &mut wire,
+            &ClientMessage::Move {
+                player_move: Move::Put((0, 1)),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(wire).unwrap(),
+            "{\"type\":\"hello\",\"version\":1,\"seat\":3}\n\
+             {\"type\":\"move\",\"player_move\":{\"put\":[0,1]}}\n"
+        );
+    }
+
+    #[test]
+    fn rules_of_older_hosts_take_defaults() {
+        let rules = serde_json::from_str::<GameRules>(
+            r#"{"range":8,"no_diagonals":false,"auto_skip":true,"tiebreak":"none"}"#,
+        )
+        .unwrap();
+        assert_eq!(rules.handicap, [0, 0, 0]);
+        assert_eq!(rules.first_player, Player::Zero);
+        assert_eq!(rules.scoring, Scoring::Stones);
+    }
+
+    #[test]
+    fn malformed_line_is_an_error() {
+        let mut reader = "{\"type\":\"warp\"}\n".as_bytes();
+        assert!(receive::<ClientMessage>(&mut reader).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Puzzle asking for the best move of a position, written in a TOML file.
+//!
+//! ```toml
+//! title = "Take the edge"
+//! position = "./1./21./2213/2323./.12313/..2311./..2111.. 1"
+//! solutions = ["0,5"]
+//! ```
+
+use crate::analysis;
+use crate::board::{parse_fen, Board, Move, Player};
+use crate::error::TriversiError;
+use anyhow::{Context, Result};
+use getset::{CopyGetters, Getters};
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Number of moves searched ahead to find moves as good as the solutions.
+const DEFAULT_DEPTH: usize = 3;
+
+/// Contents of a puzzle file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PuzzleFile {
+    #[serde(default)]
+    pub title: String,
+    /// Position in the format of `parse_fen`.
+    pub position: String,
+    /// Intended best moves written as `x,y`.
+    pub solutions: Vec<String>,
+    /// Number of moves searched ahead to evaluate moves.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+}
+
+fn default_depth() -> usize {
+    DEFAULT_DEPTH
+}
+
+#[derive(Clone, Debug, CopyGetters, Getters)]
+pub struct Puzzle {
+    #[getset(get = "pub")]
+    title: String,
+    #[getset(get = "pub")]
+    board: Board,
+    #[getset(get_copy = "pub")]
+    player: Player,
+    #[getset(get = "pub")]
+    solutions: Vec<(usize, usize)>,
+    /// Solutions and positions evaluated at least as well as them.
+    #[getset(get = "pub")]
+    accepted: Vec<(usize, usize)>,
+}
+
+impl Puzzle {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(TriversiError::io(path))?;
+        let puzzle_file = toml::from_str::<PuzzleFile>(&text)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        let puzzle = Self::try_from(puzzle_file)
+            .with_context(|| format!("invalid puzzle in {}", path.display()))?;
+        Ok(puzzle)
+    }
+
+    /// Whether selecting `position` solves the puzzle.
+    pub fn is_solved_by(&self, position: (usize, usize)) -> bool {
+        self.accepted.contains(&position)
+    }
+}
+
+impl TryFrom<PuzzleFile> for Puzzle {
+    type Error = TriversiError;
+
+    /// Reads the position and evaluates its moves, where the solutions must be selectable.
+    fn try_from(puzzle_file: PuzzleFile) -> Result<Self, Self::Error> {
+        let (board, player) = parse_fen(&puzzle_file.position)?;
+        let selectables = board.selectable_positions(player);
+        let mut solutions = Vec::new();
+        for solution in &puzzle_file.solutions {
+            match Move::parse(solution, board.range())? {
+                Move::Put(position) if selectables.contains(&position) => solutions.push(position),
+                _ => return Err(TriversiError::InvalidPuzzleSolution(solution.clone())),
+            }
+        }
+        if solutions.is_empty() {
+            return Err(TriversiError::NoPuzzleSolution);
+        }
+        let analysis = analysis::analyze(&board, player, puzzle_file.depth);
+        let value = |position: (usize, usize)| {
+            analysis
+                .moves()
+                .iter()
+                .find(|analysis| analysis.position() == position)
+                .map_or(0, |analysis| analysis.counts()[player.index()])
+        };
+        let best = solutions
+            .iter()
+            .map(|solution| value(*solution))
+            .max()
+            .unwrap_or_default();
+        let accepted = analysis
+            .moves()
+            .iter()
+            .map(|analysis| analysis.position())
+            .filter(|position| solutions.contains(position) || value(*position) >= best)
+            .collect();
+        Ok(Self {
+            title: puzzle_file.title,
+            board,
+            player,
+            solutions,
+            accepted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Puzzle files bundled in `puzzles/`.
+    fn bundled_puzzles() -> Vec<PathBuf> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("puzzles");
+        let mut paths = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn bundled_puzzles_are_valid() {
+        let paths = bundled_puzzles();
+        assert!(!paths.is_empty());
+        for path in paths {
+            let puzzle = Puzzle::load(&path).unwrap();
+            assert!(!puzzle.title().is_empty(), "{}", path.display());
+            for solution in puzzle.solutions() {
+                assert!(puzzle.is_solved_by(*solution), "{}", path.display());
+            }
+            // A puzzle accepting every position asks nothing.
+            let selectables = puzzle.board().selectable_positions(puzzle.player());
+            assert!(
+                puzzle
+                    .accepted()
+                    .iter()
+                    .all(|position| selectables.contains(position)),
+                "{}",
+                path.display()
+            );
+            assert!(
+                puzzle.accepted().len() < selectables.len(),
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn solutions_must_be_selectable() {
+        let puzzle_file = |solutions: &[&str]| PuzzleFile {
+            title: String::new(),
+            position: "./1./21./2213/2323./.12313/..2311./..2111.. 1".to_owned(),
+            solutions: solutions
+                .iter()
+                .map(|solution| solution.to_string())
+                .collect(),
+            depth: 1,
+        };
+        assert!(Puzzle::try_from(puzzle_file(&["0,5"])).is_ok());
+        assert!(matches!(
+            Puzzle::try_from(puzzle_file(&["0,0"])),
+            Err(TriversiError::InvalidPuzzleSolution(solution)) if solution == "0,0"
+        ));
+        assert!(matches!(
+            Puzzle::try_from(puzzle_file(&["pass"])),
+            Err(TriversiError::InvalidPuzzleSolution(_))
+        ));
+        assert!(matches!(
+            Puzzle::try_from(puzzle_file(&[])),
+            Err(TriversiError::NoPuzzleSolution)
+        ));
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Random number generators derived from one seed so that a whole game is reproducible.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Randomized components, each of which draws from its own sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Component {
+    /// Computer players selecting positions.
+    Ai,
+    /// Strategy suggesting positions to human players.
+    Suggestion,
+    /// Choice of the player who moves first.
+    Seating,
+    /// Keys of positions hashed by `board::zobrist`, drawn from a fixed seed.
+    Zobrist,
+    /// Games played by the simulate command and their random opening moves.
+    Simulation,
+}
+
+/// Seed chosen from the entropy of the operating system.
+pub fn entropy_seed() -> u64 {
+    rand::random()
+}
+
+/// Generator seeded by `seed` itself.
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Generator of `component` at the `index`-th use, e.g., a turn,
+/// which is independent of the generators of other components and indices.
+pub fn child(seed: u64, component: Component, index: u64) -> StdRng {
+    let component_seed = mix(seed ^ mix(component as u64 + 1));
+    StdRng::seed_from_u64(mix(component_seed ^ index))
+}
+
+/// Finalizer of SplitMix64, which scatters nearby values.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,447 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Games between computer players in batches, shown by the simulate command,
+//! whose strengths are compared by a multi-player Elo rating.
+
+use crate::ai::{Phases, SearchConfig, StrategyKind, Weights};
+use crate::board::{Board, Count, Move, PLAYERS};
+use crate::error::TriversiError;
+use crate::game::Game;
+use crate::rng::{self, Component};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use getset::CopyGetters;
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Rating of a strategy before its first game.
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// Largest change of a rating by a game.
+const K_FACTOR: f64 = 32.0;
+
+/// Margin of about 95% confidence of a rating after one game against an even opponent,
+/// which shrinks by the square root of the number of games.
+const MARGIN_OF_ONE_GAME: f64 = 680.0;
+
+/// Computer player with the parameters changing its moves.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Contestant {
+    kind: StrategyKind,
+    weights: Weights,
+    phases: Phases,
+}
+
+impl Contestant {
+    /// Contestant of `kind` whose parameters are taken from `search_config`.
+    pub fn new(kind: StrategyKind, search_config: SearchConfig) -> Self {
+        Self {
+            kind,
+            weights: search_config.weights,
+            phases: search_config.phases,
+        }
+    }
+
+    /// Contestant written like `max-n:evaluation=count` or `phased:corner=20,endgame=60`,
+    /// where the kind is followed by the parameters differing from those of `search_config`.
+    pub fn parse(spec: &str, search_config: SearchConfig) -> Result<Self, TriversiError> {
+        let invalid = || TriversiError::InvalidStringForContestant(spec.to_owned());
+        let (kind, params) = match spec.split_once(':') {
+            Some((kind, params)) => (kind, Some(params)),
+            None => (spec, None),
+        };
+        let kind = StrategyKind::from_str(kind.trim(), true).map_err(|_| invalid())?;
+        let mut contestant = Self::new(kind, search_config);
+        for param in params.into_iter().flat_map(|params| params.split(',')) {
+            let (name, value) = param.split_once('=').ok_or_else(invalid)?;
+            let (name, value) = (name.trim(), value.trim());
+            if name == "evaluation" {
+                match value {
+                    "count" => contestant.weights = Weights::COUNT,
+                    "positional" => contestant.weights = Weights::default(),
+                    _ => return Err(invalid()),
+                }
+                continue;
+            }
+            let value = value.parse::<u64>().map_err(|_| invalid())?;
+            let param = match name {
+                "corner" => &mut contestant.weights.corner,
+                "edge" => &mut contestant.weights.edge,
+                "mobility" => &mut contestant.weights.mobility,
+                "count" => &mut contestant.weights.count,
+                "midgame" => &mut contestant.phases.midgame,
+                "endgame" => &mut contestant.phases.endgame,
+                _ => return Err(invalid()),
+            };
+            *param = value;
+        }
+        contestant.phases.validate()?;
+        Ok(contestant)
+    }
+
+    /// Name of the kind followed by every parameter it uses, e.g. `max-n:corner=10,edge=3,mobility=1,count=1`,
+    /// which keys the ratings so that contestants of different parameters are rated apart.
+    pub fn id(&self) -> String {
+        let mut id = self.kind.to_possible_value().unwrap().get_name().to_owned();
+        if matches!(self.kind, StrategyKind::MaxN | StrategyKind::Phased) {
+            let Weights {
+                corner,
+                edge,
+                mobility,
+                count,
+            } = self.weights;
+            write!(
+                id,
+                ":corner={},edge={},mobility={},count={}",
+                corner, edge, mobility, count
+            )
+            .unwrap();
+        }
+        if self.kind == StrategyKind::Phased {
+            write!(
+                id,
+                ",midgame={},endgame={}",
+                self.phases.midgame, self.phases.endgame
+            )
+            .unwrap();
+        }
+        id
+    }
+
+    /// Search of this contestant, where the resources are taken from `search_config`.
+    fn search_config(&self, search_config: SearchConfig) -> SearchConfig {
+        SearchConfig {
+            weights: self.weights,
+            phases: self.phases,
+            ..search_config
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, CopyGetters, Serialize, Deserialize)]
+#[getset(get_copy = "pub")]
+pub struct Rating {
+    rating: f64,
+    /// Number of games rated.
+    games: u64,
+    /// Number of games won alone.
+    wins: u64,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            rating: INITIAL_RATING,
+            games: 0,
+            wins: 0,
+        }
+    }
+}
+
+impl Rating {
+    /// Margin of about 95% confidence around the rating, which is `None` before the first game.
+    pub fn margin(&self) -> Option<f64> {
+        (self.games > 0).then(|| MARGIN_OF_ONE_GAME / (self.games as f64).sqrt())
+    }
+}
+
+/// Ratings of contestants keyed by their ids, which accumulate across runs if they are saved.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Ratings {
+    ratings: BTreeMap<String, Rating>,
+}
+
+impl Ratings {
+    /// Loads ratings saved by `save`, where a missing file has no ratings.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path).map_err(TriversiError::io(path))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse ratings in {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(TriversiError::io(dir))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).map_err(TriversiError::io(path))?;
+        Ok(())
+    }
+
+    /// Rating of `id`, which is the initial rating before its first game.
+    pub fn get(&self, id: &str) -> Rating {
+        self.ratings.get(id).copied().unwrap_or_default()
+    }
+
+    /// Rates a game from the ids and the scores of the seats.
+    /// Every pair of seats of different ids is rated as a game of two players won by the higher score,
+    /// and the change of an id is averaged over its pairs so that an id playing two seats is not rated twice.
+    pub fn update(&mut self, seats: &[(&str, u64)]) {
+        let mut ids = seats.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        let before = ids
+            .iter()
+            .map(|id| (*id, self.get(id).rating))
+            .collect::<BTreeMap<_, _>>();
+        let best = seats.iter().map(|(_, score)| *score).max();
+        for id in ids {
+            let mut change = 0.0;
+            let mut n_pairs = 0;
+            for (_, score) in seats.iter().filter(|(seat_id, _)| *seat_id == id) {
+                for (other_id, other_score) in seats.iter().filter(|(seat_id, _)| *seat_id != id) {
+                    let expected =
+                        1.0 / (1.0 + 10f64.powf((before[other_id] - before[id]) / 400.0));
+                    let actual = match score.cmp(other_score) {
+                        Ordering::Greater => 1.0,
+                        Ordering::Equal => 0.5,
+                        Ordering::Less => 0.0,
+                    };
+                    change += actual - expected;
+                    n_pairs += 1;
+                }
+            }
+            let won_alone = seats
+                .iter()
+                .all(|(seat_id, score)| *seat_id == id || Some(*score) < best);
+            let rating = self.ratings.entry(id.to_owned()).or_default();
+            if n_pairs > 0 {
+                rating.rating += K_FACTOR * change / n_pairs as f64;
+            }
+            rating.games += 1;
+            if won_alone {
+                rating.wins += 1;
+            }
+        }
+    }
+
+    /// Table of the ratings of `ids` from the highest.
+    pub fn table(&self, ids: &[String]) -> String {
+        let mut ids = ids.iter().collect::<Vec<_>>();
+        ids.sort_by(|a, b| self.get(b).rating.total_cmp(&self.get(a).rating));
+        ids.dedup();
+        let id_width = ids
+            .iter()
+            .map(|id| id.len())
+            .max()
+            .unwrap_or_default()
+            .max("Strategy".len());
+        let mut text = format!(
+            "{:<id_width$} {:>6} {:>6} {:>6} {:>6}\n",
+            "Strategy", "Rating", "±95%", "Games", "Wins"
+        );
+        for id in ids {
+            let rating = self.get(id);
+            writeln!(
+                text,
+                "{:<id_width$} {:>6.0} {:>6} {:>6} {:>6}",
+                id,
+                rating.rating,
+                rating
+                    .margin()
+                    .map_or("-".to_owned(), |margin| format!("{:.0}", margin)),
+                rating.games,
+                rating.wins
+            )
+            .unwrap();
+        }
+        text
+    }
+}
+
+/// Contestants of the seats in the `i_game`-th game, which goes through every assignment
+/// of `n_contestants` to the seats except those of a single contestant.
+pub fn seating(n_contestants: usize, i_game: usize) -> [usize; 3] {
+    let assignments = (0..n_contestants.pow(3))
+        .map(|i| {
+            [
+                i / (n_contestants * n_contestants),
+                i / n_contestants % n_contestants,
+                i % n_contestants,
+            ]
+        })
+        .filter(|[a, b, c]| !(a == b && b == c))
+        .collect::<Vec<_>>();
+    assignments[i_game % assignments.len()]
+}
+
+/// Scores of a game on `board` between `contestants` indexed by `Player::index`,
+/// which opens with `opening_moves` random moves so that deterministic contestants play different games.
+pub fn play_game(
+    board: Board,
+    contestants: [&Contestant; 3],
+    search_config: SearchConfig,
+    opening_moves: usize,
+    seed: u64,
+) -> Count {
+    let mut opening_rng = rng::child(seed, Component::Simulation, 0);
+    let strategies = PLAYERS
+        .iter()
+        .map(|player| {
+            let contestant = contestants[player.index()];
+            contestant.kind.strategy(
+                rng::child(seed, Component::Ai, player.index() as u64),
+                contestant.search_config(search_config),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut game = Game::new(board);
+    while !game.is_over() {
+        let player = game.current_player();
+        let positions = game.legal_positions(player);
+        let position = if positions.is_empty() {
+            None
+        } else if game.move_count() < opening_moves {
+            Some(positions[opening_rng.gen_range(0..positions.len())])
+        } else {
+            strategies[player.index()].select(game.board(), &game.availables(), player)
+        };
+        game.play(player, position.map_or(Move::Pass, Move::Put))
+            .expect("strategies select legal positions");
+    }
+    game.board().scores()
+}
+
+/// Plays `games` games on `board` between `contestants` seated by `seating` and rates them game by game.
+pub fn simulate(
+    board: &Board,
+    contestants: &[Contestant],
+    search_config: SearchConfig,
+    games: usize,
+    opening_moves: usize,
+    seed: u64,
+    ratings: &mut Ratings,
+) {
+    let ids = contestants
+        .iter()
+        .map(|contestant| contestant.id())
+        .collect::<Vec<_>>();
+    for i_game in 0..games {
+        let seats = seating(contestants.len(), i_game);
+        let scores = play_game(
+            board.clone(),
+            seats.map(|i| &contestants[i]),
+            search_config,
+            opening_moves,
+            rng::child(seed, Component::Simulation, i_game as u64).gen(),
+        );
+        let results = PLAYERS
+            .iter()
+            .map(|player| (ids[seats[player.index()]].as_str(), scores[*player]))
+            .collect::<Vec<_>>();
+        ratings.update(&results);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_tell_the_parameters() {
+        let search_config = SearchConfig::default();
+        let parse = |spec: &str| Contestant::parse(spec, search_config);
+        assert_eq!(parse("greedy").unwrap().id(), "greedy");
+        assert_eq!(parse(" Random ").unwrap().id(), "random");
+        assert_eq!(
+            parse("max-n").unwrap().id(),
+            "max-n:corner=0,edge=0,mobility=0,count=1"
+        );
+        assert_eq!(
+            parse("max-n:evaluation=positional, edge=5").unwrap().id(),
+            "max-n:corner=10,edge=5,mobility=1,count=1"
+        );
+        assert_eq!(
+            parse("phased:endgame=60").unwrap().id(),
+            "phased:corner=0,edge=0,mobility=0,count=1,midgame=30,endgame=60"
+        );
+        for contestant in [
+            parse("max-n:corner=7").unwrap(),
+            parse("phased:midgame=10").unwrap(),
+        ] {
+            assert_eq!(parse(&contestant.id()).unwrap(), contestant);
+        }
+        for spec in [
+            "",
+            "minimax",
+            "max-n:",
+            "max-n:corner",
+            "max-n:corner=-1",
+            "max-n:depth=3",
+            "max-n:evaluation=material",
+        ] {
+            assert!(
+                matches!(
+                    parse(spec),
+                    Err(TriversiError::InvalidStringForContestant(_))
+                ),
+                "{:?}",
+                spec
+            );
+        }
+        assert!(matches!(
+            parse("phased:midgame=80,endgame=60"),
+            Err(TriversiError::InvalidPhases(80, 60))
+        ));
+    }
+
+    #[test]
+    fn ratings_move_by_the_scores() {
+        let mut ratings = Ratings::default();
+        ratings.update(&[("a", 10), ("b", 5), ("c", 5)]);
+        let [a, b, c] = ["a", "b", "c"].map(|id| ratings.get(id));
+        assert!(a.rating() > INITIAL_RATING);
+        assert_eq!(b.rating(), c.rating());
+        assert!((a.rating() + b.rating() + c.rating() - 3.0 * INITIAL_RATING).abs() < 1e-9);
+        assert_eq!([a.wins(), b.wins(), c.wins()], [1, 0, 0]);
+
+        // An id playing two seats is rated once, and the change is shared with its opponent.
+        ratings.update(&[("b", 3), ("c", 9), ("b", 4)]);
+        assert_eq!(ratings.get("b").games(), 2);
+        assert_eq!(ratings.get("c").wins(), 1);
+        let change = ratings.get("c").rating() - c.rating();
+        assert!(change > 0.0);
+        assert!((ratings.get("b").rating() - b.rating() + change).abs() < 1e-9);
+
+        // A shared best score is not a win.
+        ratings.update(&[("a", 6), ("c", 6), ("b", 1)]);
+        assert_eq!(ratings.get("a").wins(), 1);
+        assert_eq!(ratings.get("c").wins(), 1);
+        assert_eq!(
+            ratings.get("a").margin(),
+            Some(MARGIN_OF_ONE_GAME / 2f64.sqrt())
+        );
+        assert_eq!(ratings.get("d").margin(), None);
+    }
+
+    #[test]
+    fn seating_goes_through_every_assignment() {
+        let assignments = (0..6).map(|i_game| seating(2, i_game)).collect::<Vec<_>>();
+        for assignment in &assignments {
+            assert!(assignment.contains(&0) && assignment.contains(&1));
+        }
+        let mut distinct = assignments.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 6);
+        assert_eq!(seating(2, 6), assignments[0]);
+        // Each of three contestants plays each seat equally often.
+        for seat in 0..3 {
+            let mut seats = [0; 3];
+            for i_game in 0..24 {
+                seats[seating(3, i_game)[seat]] += 1;
+            }
+            assert_eq!(seats, [8, 8, 8]);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+//! Parsers shared by inputs of positions.
+
+use crate::error::TriversiError;
+
+/// Parses a position `x,y` on the board of `range`, where spaces around the values are ignored
+/// and the position must be in the triangle `x <= y < range`.
+pub fn parse_position(s: &str, range: usize) -> Result<(usize, usize), TriversiError> {
+    let position = s
+        .split_once(',')
+        .and_then(|(x, y)| {
+            Some((
+                x.trim().parse::<usize>().ok()?,
+                y.trim().parse::<usize>().ok()?,
+            ))
+        })
+        .ok_or_else(|| {
+            TriversiError::InvalidPosition(
+                s.trim().to_owned(),
+                "which is written as x,y of non-negative integers".to_owned(),
+            )
+        })?;
+    validate_position(position, range)?;
+    Ok(position)
+}
+
+/// Checks that `(x, y)` is in the triangle `x <= y < range` of the board of `range`.
+pub fn validate_position((x, y): (usize, usize), range: usize) -> Result<(), TriversiError> {
+    if x > y || y >= range {
+        return Err(TriversiError::PositionOutOfRange(x, y, range));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spaces_around_values_are_ignored() {
+        for s in ["2,3", " 2,3 ", "2 , 3", "\t2,\t3\n"] {
+            assert_eq!(parse_position(s, 5).unwrap(), (2, 3), "{:?}", s);
+        }
+        assert_eq!(parse_position("0,0", 5).unwrap(), (0, 0));
+        assert_eq!(parse_position("4,4", 5).unwrap(), (4, 4));
+    }
+
+    #[test]
+    fn malformed_positions_are_invalid() {
+        for s in [
+            "-1,3", "1,-3", "1.5,3", "1 3", "1,", ",3", "x,y", "1,2,3", "",
+        ] {
+            assert!(
+                matches!(
+                    parse_position(s, 5),
+                    Err(TriversiError::InvalidPosition(written, _)) if written == s.trim()
+                ),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn positions_outside_the_triangle_are_out_of_range() {
+        for (s, (x, y)) in [("3,2", (3, 2)), ("0,5", (0, 5)), ("5,5", (5, 5))] {
+            assert!(
+                matches!(
+                    parse_position(s, 5),
+                    Err(TriversiError::PositionOutOfRange(error_x, error_y, 5)) if (error_x, error_y) == (x, y)
+                ),
+                "{:?}",
+                s
+            );
+        }
+    }
+}
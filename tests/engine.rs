@@ -0,0 +1,94 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use std::io::Cursor;
+use triversi::ai::{SearchConfig, StrategyKind};
+use triversi::board::Board;
+use triversi::engine::Engine;
+
+/// Engine on a board of `range` with the greedy strategy.
+fn engine(range: usize, seed: u64) -> Engine {
+    Engine::new(
+        Board::try_new(range).unwrap(),
+        StrategyKind::Greedy,
+        SearchConfig::default(),
+        seed,
+    )
+}
+
+/// Answers of `engine` to the lines of `script`, each without the terminating empty line.
+fn session(engine: &mut Engine, script: &str) -> Vec<String> {
+    let mut output = Vec::new();
+    engine.run(Cursor::new(script), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    output
+        .split("\n\n")
+        .filter(|answer| !answer.is_empty())
+        .map(|answer| answer.to_owned())
+        .collect()
+}
+
+/// Moves of a full game generated by the seats to move, and the final score and result.
+fn self_play(engine: &mut Engine) -> (Vec<(String, String)>, String, String) {
+    let mut moves = Vec::new();
+    while engine.execute("result").is_err() {
+        let seat = engine.execute("turn").unwrap();
+        let player_move = engine.execute(&format!("genmove {}", seat)).unwrap();
+        moves.push((seat, player_move));
+    }
+    (
+        moves,
+        engine.execute("score").unwrap(),
+        engine.execute("result").unwrap(),
+    )
+}
+
+#[test]
+fn full_game_is_played_through_the_protocol() {
+    let (moves, score, result) = self_play(&mut engine(8, 1));
+    assert!(!moves.is_empty());
+    let stones = score
+        .split(' ')
+        .map(|count| count.parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(stones.len(), 3);
+    assert!(stones.iter().sum::<usize>() <= 8 * 9 / 2);
+    assert!(result.starts_with("win ") || result.starts_with("draw "));
+
+    // Playing the generated moves on another engine reaches the same end.
+    let mut script = String::from("newgame 8\n");
+    for (seat, player_move) in &moves {
+        script.push_str(&format!("play {} {}\n", seat, player_move));
+    }
+    script.push_str("score\nresult\nquit\n");
+    let answers = session(&mut engine(5, 2), &script);
+    assert_eq!(answers.len(), moves.len() + 4);
+    assert!(answers[..=moves.len()].iter().all(|answer| answer == "="));
+    assert_eq!(answers[moves.len() + 1], format!("= {}", score));
+    assert_eq!(answers[moves.len() + 2], format!("= {}", result));
+    assert_eq!(answers[moves.len() + 3], "=");
+}
+
+#[test]
+fn same_seed_plays_the_same_game() {
+    assert_eq!(self_play(&mut engine(8, 3)), self_play(&mut engine(8, 3)));
+}
+
+#[test]
+fn errors_are_answered_without_ending_the_session() {
+    let answers = session(
+        &mut engine(5, 0),
+        "# comment\n\nresult\nplay 4 0,0\nplay 1 9,9\nfly\nnewgame\nundo\nturn\nquit\nturn\n",
+    );
+    assert_eq!(answers.len(), 8);
+    assert_eq!(answers[0], "? the game is not over");
+    assert!(answers[1].starts_with("? 4 is an invalid seat"));
+    assert!(answers[2].starts_with("? "));
+    assert_eq!(answers[3], "? unknown command fly");
+    assert_eq!(answers[4], "=");
+    assert_eq!(answers[5], "? no move to undo");
+    assert_eq!(answers[6], "= 1");
+    assert_eq!(answers[7], "=");
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use triversi::simulation::Ratings;
+
+const WEIGHTED: &str = "max-n:corner=10,edge=3,mobility=1,count=1";
+const COUNT_ONLY: &str = "max-n:corner=0,edge=0,mobility=0,count=1";
+
+/// Empty directory of this test, which is also the home so that no configuration file is read.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("triversi-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Output of the simulate command of `games` games from `seed` between the weighted and the count-only evaluations.
+fn simulate(dir: &Path, games: usize, seed: u64) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_triversi"))
+        .env("HOME", dir)
+        .env("XDG_CONFIG_HOME", dir)
+        .args(["--range", "8", "--seed", &seed.to_string(), "simulate"])
+        .args(["--strategy", "max-n:evaluation=positional"])
+        .args(["--strategy", "max-n:evaluation=count"])
+        .args(["--games", &games.to_string(), "--ratings"])
+        .arg(dir.join("ratings.json"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn weighted_evaluation_beats_count_only() {
+    let dir = temp_dir("simulation");
+    let text = simulate(&dir, 48, 2);
+    let lines = text.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("Strategy"), "{}", text);
+    assert!(lines[1].starts_with(WEIGHTED), "{}", text);
+    assert!(lines[2].starts_with(COUNT_ONLY), "{}", text);
+
+    let ratings = Ratings::load(&dir.join("ratings.json")).unwrap();
+    let (weighted, count_only) = (ratings.get(WEIGHTED), ratings.get(COUNT_ONLY));
+    assert_eq!((weighted.games(), count_only.games()), (48, 48));
+    assert!(weighted.rating() > count_only.rating());
+    assert!(weighted.wins() > 2 * count_only.wins(), "{}", text);
+
+    // Ratings of another run accumulate in the file.
+    simulate(&dir, 12, 3);
+    let ratings = Ratings::load(&dir.join("ratings.json")).unwrap();
+    assert_eq!(ratings.get(WEIGHTED).games(), 60);
+    assert!(ratings.get(WEIGHTED).rating() > ratings.get(COUNT_ONLY).rating());
+    fs::remove_dir_all(&dir).unwrap();
+}
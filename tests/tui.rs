@@ -0,0 +1,189 @@
+// Copyright (c) 2023 Yuichi Ishida <yu1guana@gmail.com>
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use triversi::app::board_display::paragraph_board::{FrameStyleKind, ParagraphBoard};
+use triversi::app::key_binding::key;
+use triversi::app::system::{Play, Status, System};
+use triversi::app::tui::Tui;
+use triversi::app::{ColorConfig, Settings, Theme};
+use triversi::board::Board;
+use tui::backend::TestBackend;
+use tui::buffer::Buffer;
+use tui::style::Modifier;
+
+const MARKS: [&str; 3] = ["A", "B", "C"];
+
+/// System on a board of `range` drawn in the mono theme, where the cursor is reversed.
+fn system(range: usize) -> System<ParagraphBoard> {
+    let paragraph_board = ParagraphBoard::try_new(
+        3,
+        "Cyan,Magenta,Yellow",
+        &MARKS.join(","),
+        false,
+        FrameStyleKind::Ascii.into(),
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+    let settings = Settings {
+        color_config: ColorConfig::from(Theme::Mono),
+        ..Settings::default()
+    };
+    System::try_new(
+        Board::try_new(range).unwrap(),
+        paragraph_board,
+        settings,
+        Vec::new(),
+    )
+    .unwrap()
+}
+
+fn tui() -> Tui<TestBackend> {
+    Tui::with_backend(TestBackend::new(160, 60)).unwrap()
+}
+
+fn buffer(tui: &Tui<TestBackend>) -> &Buffer {
+    tui.terminal().backend().buffer()
+}
+
+/// Cells drawn in reverse video.
+fn reversed_cells(tui: &Tui<TestBackend>) -> Vec<(u16, u16)> {
+    let buffer = buffer(tui);
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .flat_map(|y| (area.left()..area.right()).map(move |x| (x, y)))
+        .filter(|(x, y)| buffer.get(*x, *y).modifier.contains(Modifier::REVERSED))
+        .collect()
+}
+
+/// Cell of `position` on the screen, which is told by moving the cursor there.
+fn screen_cell(
+    system: &mut System<ParagraphBoard>,
+    tui: &mut Tui<TestBackend>,
+    position: (usize, usize),
+) -> (u16, u16) {
+    let cursor = system.cursor();
+    system.set_cursor(position).unwrap();
+    tui.draw(system).unwrap();
+    let cells = reversed_cells(tui);
+    system.set_cursor(cursor).unwrap();
+    assert_eq!(cells.len(), 1, "cursor at {:?}", position);
+    cells[0]
+}
+
+fn symbol(tui: &Tui<TestBackend>, (x, y): (u16, u16)) -> String {
+    buffer(tui).get(x, y).symbol.clone()
+}
+
+/// Whole screen as lines of text.
+fn screen_text(tui: &Tui<TestBackend>) -> String {
+    let buffer = buffer(tui);
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buffer.get(x, y).symbol.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn cursor_cell_is_reversed() {
+    let mut system = system(8);
+    let mut tui = tui();
+    tui.draw(&mut system).unwrap();
+    let cells = reversed_cells(&tui);
+    assert_eq!(cells.len(), 1);
+    let (x, y) = cells[0];
+
+    // The cursor moves a position down, which is three rows below at the distance of three.
+    let (cursor_x, cursor_y) = system.cursor();
+    system.transition(key::MOVE_DOWN);
+    assert_eq!(system.cursor(), (cursor_x, cursor_y + 1));
+    tui.draw(&mut system).unwrap();
+    let cells = reversed_cells(&tui);
+    assert_eq!(cells.len(), 1);
+    assert_eq!(cells[0].1, y + 3);
+    assert_ne!(cells[0], (x, y));
+}
+
+#[test]
+fn flipped_stones_change_mark() {
+    let mut system = system(8);
+    let mut tui = tui();
+    let player = system.current_player();
+    let position = system.board().selectable_positions(player)[0];
+    let flips = system.board().flips(player, position);
+    assert!(!flips.is_empty());
+    let cells = flips
+        .iter()
+        .map(|flip| screen_cell(&mut system, &mut tui, *flip))
+        .collect::<Vec<_>>();
+    let placed = screen_cell(&mut system, &mut tui, position);
+    tui.draw(&mut system).unwrap();
+    let mark = MARKS[player.index()];
+    for cell in &cells {
+        assert_ne!(symbol(&tui, *cell), mark);
+    }
+    assert_eq!(symbol(&tui, placed), " ");
+
+    system.set_cursor(position).unwrap();
+    system.transition(key::SELECT);
+    assert_ne!(system.current_player(), player);
+    tui.draw(&mut system).unwrap();
+    for cell in cells.iter().chain([&placed]) {
+        assert_eq!(symbol(&tui, *cell), mark);
+    }
+}
+
+#[test]
+fn finished_message_appears() {
+    let mut system = system(5);
+    let mut tui = tui();
+    while system.current_status() != Status::Play(Play::Finished) {
+        let player = system.current_player();
+        if let Some(position) = system.board().selectable_positions(player).first() {
+            system.set_cursor(*position).unwrap();
+        }
+        system.transition(key::SELECT);
+        tui.draw(&mut system).unwrap();
+    }
+    let text = screen_text(&tui);
+    assert!(text.contains("Game is finished!"), "{}", text);
+    assert!(text.contains("Game over"), "{}", text);
+}
+
+#[test]
+fn large_board_is_drawn_around_the_cursor() {
+    let mut system = system(200);
+    let mut tui = Tui::with_backend(TestBackend::new(150, 60)).unwrap();
+    tui.draw(&mut system).unwrap();
+    let stones = system.board().iter_occupied().collect::<Vec<_>>();
+    let (stone, player) = stones[stones.len() / 2];
+    for (position, mark) in [
+        ((0, 0), " "),
+        ((0, 199), " "),
+        ((199, 199), " "),
+        ((100, 150), " "),
+        (stone, MARKS[player.index()]),
+    ] {
+        system.set_cursor(position).unwrap();
+        system.transition(key::CENTER_CURSOR);
+        tui.draw(&mut system).unwrap();
+        let cells = reversed_cells(&tui);
+        assert_eq!(cells.len(), 1, "cursor at {:?}", position);
+        assert_eq!(symbol(&tui, cells[0]), mark, "cursor at {:?}", position);
+    }
+
+    // Scrolling far beyond the board leaves the cursor out of view.
+    for _ in 0..100 {
+        system.transition(key::SCROLL_LEFT);
+    }
+    tui.draw(&mut system).unwrap();
+    assert!(reversed_cells(&tui).is_empty());
+}